@@ -0,0 +1,119 @@
+use biome_js_syntax::{
+    AnyJsArrayBindingPatternElement, AnyJsBinding, AnyJsBindingPattern,
+    AnyJsObjectBindingPatternMember, JsInitializerClause,
+};
+use biome_rowan::AstNode;
+use type_info::{symbol::Symbol, Type, TypeId};
+
+use crate::{expr::widen_literal_type, TResult, TypeAnalyzer};
+
+impl TypeAnalyzer {
+    /// Binds every name introduced by `pattern`. Each binding is typed by
+    /// property/element access on `ty`, falling back to its default value.
+    pub fn analyze_binding_pattern(
+        &self,
+        pattern: &AnyJsBindingPattern,
+        ty: TypeId,
+    ) -> TResult<Vec<Symbol>> {
+        let mut symbols = vec![];
+        self.bind_pattern(pattern, ty, &mut symbols)?;
+        Ok(symbols)
+    }
+
+    fn bind_pattern(
+        &self,
+        pattern: &AnyJsBindingPattern,
+        ty: TypeId,
+        symbols: &mut Vec<Symbol>,
+    ) -> TResult<()> {
+        match pattern {
+            AnyJsBindingPattern::AnyJsBinding(binding) => {
+                if let Some(name) = binding_name(binding) {
+                    let symbol = Symbol::new(name, ty);
+                    symbols.push(symbol.declared_at(self.location(binding.syntax())));
+                }
+            }
+            AnyJsBindingPattern::JsObjectBindingPattern(object) => {
+                let mut picked = vec![];
+                for member in object.properties().into_iter().flatten() {
+                    match member {
+                        AnyJsObjectBindingPatternMember::JsObjectBindingPatternProperty(prop) => {
+                            let Some(name) = prop.member()?.name() else {
+                                continue;
+                            };
+                            let name = name.text().to_string();
+                            let member_ty = self.with_default(
+                                self.resolve_member_type(ty, &name, &self.current_path),
+                                prop.init(),
+                            );
+                            self.bind_pattern(&prop.pattern()?, member_ty, symbols)?;
+                            picked.push(name);
+                        }
+                        AnyJsObjectBindingPatternMember::JsObjectBindingPatternShorthandProperty(
+                            prop,
+                        ) => {
+                            let identifier = prop.identifier()?;
+                            let Some(name) = binding_name(&identifier) else {
+                                continue;
+                            };
+                            let member_ty = self.with_default(
+                                self.resolve_member_type(ty, &name, &self.current_path),
+                                prop.init(),
+                            );
+                            let symbol = Symbol::new(name.clone(), member_ty);
+                            symbols.push(symbol.declared_at(self.location(identifier.syntax())));
+                            picked.push(name);
+                        }
+                        AnyJsObjectBindingPatternMember::JsObjectBindingPatternRest(rest) => {
+                            let binding = rest.binding()?;
+                            if let Some(name) = binding_name(&binding) {
+                                let rest_ty =
+                                    self.resolve_object_rest_type(ty, &picked, &self.current_path);
+                                let symbol = Symbol::new(name, rest_ty);
+                                symbols.push(symbol.declared_at(self.location(binding.syntax())));
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            AnyJsBindingPattern::JsArrayBindingPattern(array) => {
+                for (index, element) in array.elements().into_iter().flatten().enumerate() {
+                    match element {
+                        AnyJsArrayBindingPatternElement::JsArrayHole(_) => {}
+                        AnyJsArrayBindingPatternElement::JsArrayBindingPatternElement(element) => {
+                            let element_ty = self
+                                .with_default(self.resolve_element_type(ty, index), element.init());
+                            self.bind_pattern(&element.pattern()?, element_ty, symbols)?;
+                        }
+                        AnyJsArrayBindingPatternElement::JsArrayBindingPatternRestElement(rest) => {
+                            let rest_ty = self.resolve_array_rest_type(ty, index);
+                            self.bind_pattern(&rest.pattern()?, rest_ty, symbols)?;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// A binding without a known type takes the (widened) type of its default.
+    fn with_default(&self, ty: TypeId, init: Option<JsInitializerClause>) -> TypeId {
+        match (&*self.get_type(ty), init) {
+            (Type::Unknown, Some(init)) => match init.expression() {
+                Ok(expr) => widen_literal_type(&self.types, self.analyze_expression(&expr)),
+                Err(_) => ty,
+            },
+            _ => ty,
+        }
+    }
+}
+
+pub fn binding_name(binding: &AnyJsBinding) -> Option<String> {
+    match binding {
+        AnyJsBinding::JsIdentifierBinding(bind) => {
+            Some(bind.name_token().ok()?.text_trimmed().to_string())
+        }
+        _ => None,
+    }
+}
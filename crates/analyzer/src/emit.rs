@@ -0,0 +1,974 @@
+use biome_js_syntax::{
+    AnyJsBindingPattern, AnyJsExportClause, AnyJsExpression, AnyJsModuleItem, AnyJsRoot,
+    JsArrowFunctionExpression, JsClassDeclaration, JsClassExportDefaultDeclaration,
+    JsClassMemberList, JsConstructorClassMember, JsExport, JsExportDefaultDeclarationClause,
+    JsExportDefaultExpressionClause, JsFunctionDeclaration, JsFunctionExportDefaultDeclaration,
+    JsGetterClassMember, JsMethodClassMember, JsModuleItemList, JsParameters,
+    JsPropertyClassMember, JsReferenceIdentifier, JsSetterClassMember, JsSyntaxKind, JsSyntaxNode,
+    JsVariableDeclaration, JsVariableDeclarationClause, JsVariableDeclarator, JsVariableKind,
+    JsVariableStatement, TsDeclareFunctionDeclaration, TsDeclareStatement, TsEnumDeclaration,
+    TsInterfaceDeclaration, TsModuleDeclaration, TsReturnTypeAnnotation, TsTypeAliasDeclaration,
+    TsTypeAnnotation, TsTypeParameters,
+};
+use biome_rowan::{AstNode, SyntaxResult};
+use rustc_hash::FxHashSet;
+use type_info::{
+    FunctionParam, PrintOptions, TsFunctionSignature, TsKeywordTypeKind, TsLiteralTypeKind, Type,
+    TypeArena, TypeId,
+};
+
+use crate::{
+    binding::binding_name, expr::widen_literal_type, ts::identifier_binding_name, TypeAnalyzer,
+};
+
+/// Class member modifiers that are kept in declarations.
+const KEPT_MODIFIERS: [&str; 5] = ["private", "protected", "static", "readonly", "abstract"];
+
+impl TypeAnalyzer {
+    /// The `.d.ts` declarations of `root`, the file of the current scope,
+    /// like `tsc --declaration` emits them. Inferred types are printed from
+    /// the analysis; interfaces, aliases and re-exports are copied.
+    pub fn emit_declarations(&self, root: &AnyJsRoot) -> String {
+        let mut emitter = Emitter {
+            analyzer: self,
+            out: String::new(),
+            indent: 0,
+            in_namespace: false,
+            exported_names: FxHashSet::default(),
+            overloaded: FxHashSet::default(),
+        };
+        match root {
+            AnyJsRoot::JsModule(module) => emitter.module(module.items()),
+            AnyJsRoot::JsScript(script) => {
+                for statement in script.statements() {
+                    emitter.declaration(statement.syntax(), Visibility::Global);
+                }
+            }
+            AnyJsRoot::TsDeclarationModule(_) => {
+                emitter.copy(&root.syntax().text_trimmed().to_string())
+            }
+            _ => {}
+        }
+        emitter.out
+    }
+}
+
+/// How a declaration is visible, which decides whether it's emitted and
+/// with which keywords.
+#[derive(Clone, Copy, PartialEq)]
+enum Visibility {
+    /// `export function f() {}`.
+    Exported,
+    /// Declared by a script, for every file.
+    Global,
+    /// Declared by a module, emitted only when `export { name }` exports it.
+    Local,
+}
+
+struct Emitter<'a> {
+    analyzer: &'a TypeAnalyzer,
+    out: String,
+    indent: usize,
+    /// Inside a namespace, declarations are ambient already and exported
+    /// without `export`.
+    in_namespace: bool,
+    /// Names a module exports by `export { name }` or `export default name`.
+    exported_names: FxHashSet<String>,
+    /// Functions and methods with overload signatures. Only the signatures
+    /// are emitted, not the implementation.
+    overloaded: FxHashSet<String>,
+}
+
+impl Emitter<'_> {
+    /// Files without imports or exports are scripts, whose declarations
+    /// are all global.
+    fn module(&mut self, items: JsModuleItemList) {
+        let is_module = items.iter().any(|item| {
+            matches!(
+                item,
+                AnyJsModuleItem::JsImport(_) | AnyJsModuleItem::JsExport(_)
+            )
+        });
+        let visibility = if is_module {
+            Visibility::Local
+        } else {
+            Visibility::Global
+        };
+        self.exported_names = items
+            .iter()
+            .filter_map(|item| match item {
+                AnyJsModuleItem::JsExport(export) => export.export_clause().ok(),
+                _ => None,
+            })
+            .flat_map(|clause| exported_locals(&clause))
+            .collect();
+        for item in items {
+            match item {
+                AnyJsModuleItem::JsImport(import) => self.copy_statement(import.syntax()),
+                AnyJsModuleItem::JsExport(export) => self.export(&export),
+                AnyJsModuleItem::AnyJsStatement(statement) => {
+                    self.declaration(statement.syntax(), visibility)
+                }
+            }
+        }
+    }
+
+    fn export(&mut self, export: &JsExport) {
+        let Ok(clause) = export.export_clause() else {
+            return;
+        };
+        match clause {
+            AnyJsExportClause::AnyJsDeclarationClause(clause) => {
+                self.declaration(clause.syntax(), Visibility::Exported)
+            }
+            AnyJsExportClause::JsExportDefaultDeclarationClause(clause) => {
+                self.default_declaration(export, &clause)
+            }
+            AnyJsExportClause::JsExportDefaultExpressionClause(clause) => {
+                self.default_expression(export, &clause)
+            }
+            // `export { name }`, re-exports and `export =` are kept as they are.
+            _ => self.copy_statement(export.syntax()),
+        }
+    }
+
+    fn declaration(&mut self, node: &JsSyntaxNode, visibility: Visibility) {
+        if let Some(statement) = TsDeclareStatement::cast_ref(node) {
+            if let Ok(declaration) = statement.declaration() {
+                self.declaration(declaration.syntax(), visibility);
+            }
+        } else if let Some(function) = JsFunctionDeclaration::cast_ref(node) {
+            self.function(&function, visibility);
+        } else if let Some(function) = TsDeclareFunctionDeclaration::cast_ref(node) {
+            self.declare_function(&function, visibility);
+        } else if let Some(statement) = JsVariableStatement::cast_ref(node) {
+            if let Ok(declaration) = statement.declaration() {
+                self.variables(&declaration, visibility);
+            }
+        } else if let Some(clause) = JsVariableDeclarationClause::cast_ref(node) {
+            if let Ok(declaration) = clause.declaration() {
+                self.variables(&declaration, visibility);
+            }
+        } else if let Some(class) = JsClassDeclaration::cast_ref(node) {
+            let Some(name) = class.id().ok().and_then(|id| binding_name(&id)) else {
+                return;
+            };
+            if !self.is_emitted(visibility, &name) {
+                return;
+            }
+            let head = format!(
+                "{}{}class {}{}{}",
+                self.keywords(visibility, true),
+                if class.abstract_token().is_some() {
+                    "abstract "
+                } else {
+                    ""
+                },
+                name,
+                type_parameters(class.type_parameters()),
+                heritage(class.syntax()),
+            );
+            self.class(head, class.members());
+        } else if let Some(enumeration) = TsEnumDeclaration::cast_ref(node) {
+            self.enumeration(&enumeration, visibility);
+        } else if let Some(namespace) = TsModuleDeclaration::cast_ref(node) {
+            self.namespace(&namespace, visibility);
+        } else if let Some(interface) = TsInterfaceDeclaration::cast_ref(node) {
+            let name = interface
+                .id()
+                .ok()
+                .and_then(|id| identifier_binding_name(&id));
+            self.copy_declaration(node, name, visibility);
+        } else if let Some(alias) = TsTypeAliasDeclaration::cast_ref(node) {
+            let name = alias
+                .binding_identifier()
+                .ok()
+                .and_then(|id| identifier_binding_name(&id));
+            self.copy_declaration(node, name, visibility);
+        } else if matches!(
+            node.kind(),
+            JsSyntaxKind::TS_EXTERNAL_MODULE_DECLARATION | JsSyntaxKind::TS_GLOBAL_DECLARATION
+        ) {
+            // Ambient modules and augmentations apply whether exported or not.
+            let text = format!(
+                "{}{}",
+                self.keywords(Visibility::Global, true),
+                node.text_trimmed()
+            );
+            self.copy(&text);
+        }
+    }
+
+    fn function(&mut self, function: &JsFunctionDeclaration, visibility: Visibility) {
+        let Some(name) = function.id().ok().and_then(|id| binding_name(&id)) else {
+            return;
+        };
+        if !self.is_emitted(visibility, &name) || self.overloaded.contains(&name) {
+            return;
+        }
+        let Ok(parameters) = function.parameters() else {
+            return;
+        };
+        let signature = self.signature_of(
+            function.syntax(),
+            &parameters,
+            function.return_type_annotation(),
+            function.async_token().is_some(),
+        );
+        let line = format!(
+            "{}function {}{}{};",
+            self.keywords(visibility, true),
+            name,
+            type_parameters(function.type_parameters()),
+            self.signature(&signature),
+        );
+        self.line(&line);
+    }
+
+    /// `declare function` and overload signatures, which hide the
+    /// implementation after them.
+    fn declare_function(
+        &mut self,
+        function: &TsDeclareFunctionDeclaration,
+        visibility: Visibility,
+    ) {
+        let Some(name) = function.id().ok().and_then(|id| binding_name(&id)) else {
+            return;
+        };
+        if !self.is_emitted(visibility, &name) {
+            return;
+        }
+        let Ok(ty) = self
+            .analyzer
+            .analyze_ts_declare_function_declaration(function)
+        else {
+            return;
+        };
+        let Type::Function(signature) = &*self.analyzer.get_type(ty) else {
+            return;
+        };
+        let line = format!(
+            "{}function {}{}{};",
+            self.keywords(visibility, true),
+            name,
+            type_parameters(function.type_parameters()),
+            self.signature(signature),
+        );
+        self.line(&line);
+        self.overloaded.insert(name);
+    }
+
+    fn variables(&mut self, declaration: &JsVariableDeclaration, visibility: Visibility) {
+        let kind = match declaration.variable_kind() {
+            Ok(JsVariableKind::Const) => "const",
+            Ok(JsVariableKind::Let) => "let",
+            _ => "var",
+        };
+        for declarator in declaration.declarators().into_iter().flatten() {
+            let Ok(AnyJsBindingPattern::AnyJsBinding(binding)) = declarator.id() else {
+                continue;
+            };
+            let Some(name) = binding_name(&binding) else {
+                continue;
+            };
+            if !self.is_emitted(visibility, &name) {
+                continue;
+            }
+            let keywords = self.keywords(visibility, true);
+            let ty = self.declarator_type(&declarator);
+            // Like tsc, constants of a literal type keep their value.
+            let line = if kind == "const" && is_primitive_literal(&self.analyzer.get_type(ty)) {
+                format!("{}{} {} = {};", keywords, kind, name, self.print(ty))
+            } else {
+                format!("{}{} {}: {};", keywords, kind, name, self.print(ty))
+            };
+            self.line(&line);
+        }
+    }
+
+    /// The type of a variable, inferring what unannotated arrow functions
+    /// return.
+    fn declarator_type(&self, declarator: &JsVariableDeclarator) -> TypeId {
+        let arrow = declarator
+            .initializer()
+            .and_then(|initializer| initializer.expression().ok())
+            .and_then(|expression| match expression {
+                AnyJsExpression::JsArrowFunctionExpression(arrow) => Some(arrow),
+                _ => None,
+            })
+            .filter(|_| declarator.variable_annotation().is_none());
+        if let Some(arrow) = arrow {
+            return self.arrow_function_type(&arrow);
+        }
+        let ty = self
+            .analyzer
+            .analyze_js_variable_declarator(declarator)
+            .unwrap_or_else(|_| self.analyzer.intern(Type::Unknown));
+        widen_properties(self.analyzer.types(), ty)
+    }
+
+    fn arrow_function_type(&self, arrow: &JsArrowFunctionExpression) -> TypeId {
+        let Ok(ty) = self.analyzer.analyze_js_arrow_function_expression(arrow) else {
+            return self.analyzer.intern(Type::Unknown);
+        };
+        match &*self.analyzer.get_type(ty) {
+            Type::Function(signature) if arrow.return_type_annotation().is_none() => {
+                let inferred = self
+                    .analyzer
+                    .infer_return_type(arrow.syntax(), signature.is_async);
+                self.analyzer.intern(Type::Function(TsFunctionSignature {
+                    return_type: widen_properties(self.analyzer.types(), inferred),
+                    ..signature.clone()
+                }))
+            }
+            _ => ty,
+        }
+    }
+
+    fn default_declaration(
+        &mut self,
+        export: &JsExport,
+        clause: &JsExportDefaultDeclarationClause,
+    ) {
+        let Ok(declaration) = clause.declaration() else {
+            return;
+        };
+        let node = declaration.syntax();
+        if let Some(function) = JsFunctionExportDefaultDeclaration::cast_ref(node) {
+            let Ok(parameters) = function.parameters() else {
+                return;
+            };
+            let name = function
+                .id()
+                .and_then(|id| binding_name(&id))
+                .unwrap_or_default();
+            let signature = self.signature_of(
+                node,
+                &parameters,
+                function.return_type_annotation(),
+                function.async_token().is_some(),
+            );
+            let line = format!(
+                "export default function {}{}{};",
+                name,
+                type_parameters(function.type_parameters()),
+                self.signature(&signature),
+            );
+            self.line(&line);
+        } else if let Some(class) = JsClassExportDefaultDeclaration::cast_ref(node) {
+            let name = class.id().and_then(|id| binding_name(&id));
+            let head = format!(
+                "export default {}class{}{}{}",
+                if class.abstract_token().is_some() {
+                    "abstract "
+                } else {
+                    ""
+                },
+                name.map(|name| format!(" {}", name)).unwrap_or_default(),
+                type_parameters(class.type_parameters()),
+                heritage(class.syntax()),
+            );
+            self.class(head, class.members());
+        } else {
+            self.copy_statement(export.syntax());
+        }
+    }
+
+    /// `export default name` is kept. Other expressions are declared as
+    /// `_default`, like tsc does.
+    fn default_expression(&mut self, export: &JsExport, clause: &JsExportDefaultExpressionClause) {
+        let Ok(expression) = clause.expression() else {
+            return;
+        };
+        if let AnyJsExpression::JsIdentifierExpression(_) = expression {
+            self.copy_statement(export.syntax());
+            return;
+        }
+        let ty = match &expression {
+            AnyJsExpression::JsArrowFunctionExpression(arrow) => self.arrow_function_type(arrow),
+            expression => widen_properties(
+                self.analyzer.types(),
+                self.analyzer.analyze_expression(expression),
+            ),
+        };
+        let line = format!("declare const _default: {};", self.print(ty));
+        self.line(&line);
+        self.line("export default _default;");
+    }
+
+    fn class(&mut self, head: String, members: JsClassMemberList) {
+        self.line(&format!("{} {{", head));
+        self.indent += 1;
+        let overloaded = std::mem::take(&mut self.overloaded);
+
+        // Like tsc, private names are summed up by one `#private` member.
+        if members
+            .iter()
+            .any(|member| has_private_name(member.syntax()))
+        {
+            self.line("#private;");
+        }
+        if let Some(constructor) = members
+            .iter()
+            .find_map(|member| JsConstructorClassMember::cast(member.into_syntax()))
+        {
+            self.parameter_properties(&constructor);
+        }
+        for member in members.iter() {
+            let node = member.syntax();
+            if has_private_name(node) {
+                continue;
+            }
+            if let Some(property) = JsPropertyClassMember::cast_ref(node) {
+                self.property(&property);
+            } else if let Some(method) = JsMethodClassMember::cast_ref(node) {
+                self.method(&method);
+            } else if let Some(getter) = JsGetterClassMember::cast_ref(node) {
+                let modifiers = modifiers(getter.modifiers().syntax());
+                let name = member_name(getter.name().map(|name| name.into_syntax()));
+                let line = if modifiers.iter().any(|m| m == "private") {
+                    format!("get {}();", name)
+                } else {
+                    let ty = match getter.return_type() {
+                        Some(annotation) => self.analyzer.analyze_type_annotation(annotation),
+                        None => widen_properties(
+                            self.analyzer.types(),
+                            self.analyzer.infer_return_type(node, false),
+                        ),
+                    };
+                    format!("get {}(): {};", name, self.print(ty))
+                };
+                self.member(&modifiers, &line);
+            } else if let Some(setter) = JsSetterClassMember::cast_ref(node) {
+                let modifiers = modifiers(setter.modifiers().syntax());
+                let name = member_name(setter.name().map(|name| name.into_syntax()));
+                let parameter = setter.parameter().ok().and_then(|parameter| {
+                    self.analyzer
+                        .analyze_formal_parameter(&parameter, 0)
+                        .ok()
+                        .flatten()
+                });
+                let line = match parameter {
+                    Some(parameter) if !modifiers.iter().any(|m| m == "private") => {
+                        format!("set {}({});", name, self.parameter(&parameter))
+                    }
+                    _ => format!("set {}(value);", name),
+                };
+                self.member(&modifiers, &line);
+            } else if let Some(constructor) = JsConstructorClassMember::cast_ref(node) {
+                let modifiers = modifiers(constructor.modifiers().syntax());
+                let parameters = constructor
+                    .parameters()
+                    .ok()
+                    .and_then(|parameters| {
+                        self.analyzer
+                            .analyze_js_constructor_parameters(&parameters)
+                            .ok()
+                    })
+                    .unwrap_or_default();
+                let line = if modifiers.iter().any(|m| m == "private") {
+                    "constructor();".to_string()
+                } else {
+                    format!("constructor({});", self.parameters(&parameters))
+                };
+                self.member(&modifiers, &line);
+            } else if matches!(
+                node.kind(),
+                JsSyntaxKind::TS_PROPERTY_SIGNATURE_CLASS_MEMBER
+                    | JsSyntaxKind::TS_METHOD_SIGNATURE_CLASS_MEMBER
+                    | JsSyntaxKind::TS_GETTER_SIGNATURE_CLASS_MEMBER
+                    | JsSyntaxKind::TS_SETTER_SIGNATURE_CLASS_MEMBER
+                    | JsSyntaxKind::TS_CONSTRUCTOR_SIGNATURE_CLASS_MEMBER
+                    | JsSyntaxKind::TS_INDEX_SIGNATURE_CLASS_MEMBER
+            ) {
+                // Already a declaration, which hides the implementation of
+                // an overloaded method.
+                if node.kind() == JsSyntaxKind::TS_METHOD_SIGNATURE_CLASS_MEMBER {
+                    if let Some(name) = node.children().find(|child| is_member_name(child.kind())) {
+                        self.overloaded.insert(name.text_trimmed().to_string());
+                    }
+                }
+                self.copy_statement(node);
+            }
+        }
+
+        self.overloaded = overloaded;
+        self.indent -= 1;
+        self.line("}");
+    }
+
+    /// `constructor(private name: string)` declares a property too.
+    fn parameter_properties(&mut self, constructor: &JsConstructorClassMember) {
+        let Ok(parameters) = constructor.parameters() else {
+            return;
+        };
+        let Ok(types) = self.analyzer.analyze_js_constructor_parameters(&parameters) else {
+            return;
+        };
+        for (parameter, ty) in parameters.parameters().into_iter().zip(types) {
+            let Ok(parameter) = parameter else {
+                continue;
+            };
+            if parameter.syntax().kind() != JsSyntaxKind::TS_PROPERTY_PARAMETER {
+                continue;
+            }
+            let modifiers = parameter
+                .syntax()
+                .children()
+                .find(|child| child.kind() == JsSyntaxKind::TS_PROPERTY_PARAMETER_MODIFIER_LIST)
+                .map(|list| modifiers(&list))
+                .unwrap_or_default();
+            let optional = if ty.is_optional { "?" } else { "" };
+            let line = if modifiers.iter().any(|m| m == "private") {
+                format!("{}{};", ty.name, optional)
+            } else {
+                format!("{}{}: {};", ty.name, optional, self.print(ty.param_type))
+            };
+            self.member(&modifiers, &line);
+        }
+    }
+
+    fn property(&mut self, property: &JsPropertyClassMember) {
+        let modifiers = modifiers(property.modifiers().syntax());
+        let name = member_name(property.name().map(|name| name.into_syntax()));
+        let annotation = property.property_annotation();
+        let optional = match &annotation {
+            Some(annotation)
+                if annotation.syntax().kind() == JsSyntaxKind::TS_OPTIONAL_PROPERTY_ANNOTATION =>
+            {
+                "?"
+            }
+            _ => "",
+        };
+        if modifiers.iter().any(|m| m == "private") {
+            self.member(&modifiers, &format!("{}{};", name, optional));
+            return;
+        }
+        let annotation = annotation.and_then(|annotation| {
+            annotation
+                .syntax()
+                .descendants()
+                .find_map(TsTypeAnnotation::cast)
+        });
+        let ty = match annotation {
+            Some(annotation) => self.analyzer.analyze_type_annotation(annotation),
+            None => property
+                .value()
+                .and_then(|initializer| initializer.expression().ok())
+                .map_or_else(
+                    || self.analyzer.intern(Type::Unknown),
+                    |expression| self.analyzer.analyze_expression(&expression),
+                ),
+        };
+        let is_readonly = modifiers.iter().any(|m| m == "readonly");
+        let line = if is_readonly && is_primitive_literal(&self.analyzer.get_type(ty)) {
+            format!("{}{} = {};", name, optional, self.print(ty))
+        } else {
+            let types = self.analyzer.types();
+            let ty = widen_properties(types, widen_literal_type(types, ty));
+            format!("{}{}: {};", name, optional, self.print(ty))
+        };
+        self.member(&modifiers, &line);
+    }
+
+    fn method(&mut self, method: &JsMethodClassMember) {
+        let modifiers = modifiers(method.modifiers().syntax());
+        let name = member_name(method.name().map(|name| name.into_syntax()));
+        if self.overloaded.contains(&name) {
+            return;
+        }
+        let optional = if method.question_mark_token().is_some() {
+            "?"
+        } else {
+            ""
+        };
+        if modifiers.iter().any(|m| m == "private") {
+            self.member(&modifiers, &format!("{}{};", name, optional));
+            return;
+        }
+        let Ok(parameters) = method.parameters() else {
+            return;
+        };
+        let signature = self.signature_of(
+            method.syntax(),
+            &parameters,
+            method.return_type_annotation(),
+            method.async_token().is_some(),
+        );
+        let line = format!(
+            "{}{}{}{};",
+            name,
+            optional,
+            type_parameters(method.type_parameters()),
+            self.signature(&signature),
+        );
+        self.member(&modifiers, &line);
+    }
+
+    fn enumeration(&mut self, enumeration: &TsEnumDeclaration, visibility: Visibility) {
+        let Some(name) = enumeration.id().ok().and_then(|id| binding_name(&id)) else {
+            return;
+        };
+        if !self.is_emitted(visibility, &name) {
+            return;
+        }
+        let line = format!(
+            "{}{}enum {} {{",
+            self.keywords(visibility, true),
+            if enumeration.const_token().is_some() {
+                "const "
+            } else {
+                ""
+            },
+            name
+        );
+        self.line(&line);
+        self.indent += 1;
+        let members: Vec<_> = enumeration.members().into_iter().flatten().collect();
+        // Members without an initializer count up from the previous one.
+        let mut next = Some(0);
+        for (index, member) in members.iter().enumerate() {
+            let name = member_name(member.name().map(|name| name.into_syntax()));
+            let value = match member
+                .initializer()
+                .and_then(|initializer| initializer.expression().ok())
+            {
+                Some(expression) => {
+                    match &*self
+                        .analyzer
+                        .get_type(self.analyzer.analyze_expression(&expression))
+                    {
+                        Type::Literal(
+                            literal @ (TsLiteralTypeKind::Number(_) | TsLiteralTypeKind::String(_)),
+                        ) => Some(literal.clone()),
+                        _ => None,
+                    }
+                }
+                None => next.map(TsLiteralTypeKind::Number),
+            };
+            next = match value {
+                Some(TsLiteralTypeKind::Number(value)) => Some(value + 1),
+                _ => None,
+            };
+            let separator = if index + 1 < members.len() { "," } else { "" };
+            let line = match value {
+                Some(value) => format!(
+                    "{} = {}{}",
+                    name,
+                    self.print(self.analyzer.intern(Type::Literal(value))),
+                    separator
+                ),
+                None => format!("{}{}", name, separator),
+            };
+            self.line(&line);
+        }
+        self.indent -= 1;
+        self.line("}");
+    }
+
+    /// Only what a namespace exports is emitted.
+    fn namespace(&mut self, namespace: &TsModuleDeclaration, visibility: Visibility) {
+        let (Ok(name), Ok(body)) = (namespace.name(), namespace.body()) else {
+            return;
+        };
+        let name = name.syntax().text_trimmed().to_string();
+        if !self.is_emitted(visibility, &name) {
+            return;
+        }
+        let line = format!("{}namespace {} {{", self.keywords(visibility, true), name);
+        self.line(&line);
+        self.indent += 1;
+        let in_namespace = std::mem::replace(&mut self.in_namespace, true);
+        for item in body.items() {
+            let AnyJsModuleItem::JsExport(export) = item else {
+                continue;
+            };
+            if let Ok(AnyJsExportClause::AnyJsDeclarationClause(clause)) = export.export_clause() {
+                self.declaration(clause.syntax(), Visibility::Exported);
+            }
+        }
+        self.in_namespace = in_namespace;
+        self.indent -= 1;
+        self.line("}");
+    }
+
+    /// Interfaces and aliases are copied from the source, like tsc.
+    fn copy_declaration(
+        &mut self,
+        node: &JsSyntaxNode,
+        name: Option<String>,
+        visibility: Visibility,
+    ) {
+        let Some(name) = name else {
+            return;
+        };
+        if !self.is_emitted(visibility, &name) {
+            return;
+        }
+        let mut text = format!(
+            "{}{}",
+            self.keywords(visibility, false),
+            node.text_trimmed()
+        );
+        if node.kind() == JsSyntaxKind::TS_TYPE_ALIAS_DECLARATION && !text.ends_with(';') {
+            text.push(';');
+        }
+        self.copy(&text);
+    }
+
+    /// Copies a statement, ending it with a semicolon.
+    fn copy_statement(&mut self, node: &JsSyntaxNode) {
+        let mut text = node.text_trimmed().to_string();
+        if !text.ends_with(';') && !text.ends_with('}') {
+            text.push(';');
+        }
+        self.copy(&text);
+    }
+
+    /// Copies source text, indented like the last line, to the current level.
+    fn copy(&mut self, text: &str) {
+        let margin = text.lines().last().map_or(0, indentation);
+        for (index, line) in text.lines().enumerate() {
+            if index == 0 {
+                self.line(line);
+            } else {
+                self.line(&line[indentation(line).min(margin)..]);
+            }
+        }
+    }
+
+    /// `(this: T, a: A, b?: B): R`.
+    fn signature(&self, signature: &TsFunctionSignature) -> String {
+        let mut parameters = vec![];
+        if let Some(this) = signature.this_param {
+            parameters.push(format!("this: {}", self.print(this)));
+        }
+        if !signature.params.is_empty() {
+            parameters.push(self.parameters(&signature.params));
+        }
+        format!(
+            "({}): {}",
+            parameters.join(", "),
+            self.print(signature.return_type)
+        )
+    }
+
+    fn parameters(&self, parameters: &[FunctionParam]) -> String {
+        let parameters: Vec<_> = parameters
+            .iter()
+            .map(|parameter| self.parameter(parameter))
+            .collect();
+        parameters.join(", ")
+    }
+
+    fn parameter(&self, parameter: &FunctionParam) -> String {
+        format!(
+            "{}{}{}: {}",
+            if parameter.is_rest { "..." } else { "" },
+            parameter.name,
+            if parameter.is_optional { "?" } else { "" },
+            self.print(parameter.param_type)
+        )
+    }
+
+    /// The signature of a function or method, with the return type inferred
+    /// when it isn't annotated.
+    fn signature_of(
+        &self,
+        function: &JsSyntaxNode,
+        parameters: &JsParameters,
+        return_type: Option<TsReturnTypeAnnotation>,
+        is_async: bool,
+    ) -> TsFunctionSignature {
+        let return_type = match return_type.and_then(|annotation| annotation.ty().ok()) {
+            Some(ty) => self
+                .analyzer
+                .analyze_any_ts_return_type(&ty)
+                .unwrap_or_else(|_| self.analyzer.intern(Type::Unknown)),
+            None => widen_properties(
+                self.analyzer.types(),
+                self.analyzer.infer_return_type(function, is_async),
+            ),
+        };
+        TsFunctionSignature {
+            type_params: vec![],
+            this_param: self
+                .analyzer
+                .analyze_this_parameter(parameters)
+                .unwrap_or_default(),
+            params: self
+                .analyzer
+                .analyze_js_parameters(parameters)
+                .unwrap_or_default(),
+            return_type,
+            is_async,
+        }
+    }
+
+    /// The type in TypeScript syntax. Object types span several lines,
+    /// indented to the current level.
+    fn print(&self, ty: TypeId) -> String {
+        let options = PrintOptions {
+            multiline: true,
+            ..Default::default()
+        };
+        let types = self.analyzer.types();
+        types
+            .print(unknown_as_any(types, ty), &options)
+            .replace('\n', &format!("\n{}", "    ".repeat(self.indent)))
+    }
+
+    fn keywords(&self, visibility: Visibility, declare: bool) -> &'static str {
+        match (
+            self.in_namespace,
+            visibility == Visibility::Exported,
+            declare,
+        ) {
+            (true, _, _) => "",
+            (false, true, true) => "export declare ",
+            (false, true, false) => "export ",
+            (false, false, true) => "declare ",
+            (false, false, false) => "",
+        }
+    }
+
+    fn is_emitted(&self, visibility: Visibility, name: &str) -> bool {
+        visibility != Visibility::Local || self.exported_names.contains(name)
+    }
+
+    fn member(&mut self, modifiers: &[String], text: &str) {
+        let mut line = String::new();
+        for modifier in modifiers {
+            line.push_str(modifier);
+            line.push(' ');
+        }
+        line.push_str(text);
+        self.line(&line);
+    }
+
+    fn line(&mut self, text: &str) {
+        if !text.is_empty() {
+            self.out.push_str(&"    ".repeat(self.indent));
+        }
+        self.out.push_str(text);
+        self.out.push('\n');
+    }
+}
+
+/// The local names `clause` exports, e.g. `a` for `export { a as b }`.
+fn exported_locals(clause: &AnyJsExportClause) -> Vec<String> {
+    let references: Vec<JsReferenceIdentifier> = match clause {
+        AnyJsExportClause::JsExportNamedClause(clause) => clause
+            .specifiers()
+            .into_iter()
+            .flatten()
+            .filter_map(|specifier| {
+                specifier
+                    .syntax()
+                    .descendants()
+                    .find_map(JsReferenceIdentifier::cast)
+            })
+            .collect(),
+        AnyJsExportClause::JsExportDefaultExpressionClause(clause) => match clause.expression() {
+            Ok(AnyJsExpression::JsIdentifierExpression(expression)) => {
+                expression.name().into_iter().collect()
+            }
+            _ => vec![],
+        },
+        _ => vec![],
+    };
+    references
+        .into_iter()
+        .filter_map(|reference| Some(reference.value_token().ok()?.text_trimmed().to_string()))
+        .collect()
+}
+
+fn type_parameters(node: Option<TsTypeParameters>) -> String {
+    node.map_or(String::new(), |node| {
+        node.syntax().text_trimmed().to_string()
+    })
+}
+
+/// The `extends` and `implements` clauses of a class, as written.
+fn heritage(class: &JsSyntaxNode) -> String {
+    class
+        .children()
+        .filter(|child| {
+            matches!(
+                child.kind(),
+                JsSyntaxKind::JS_EXTENDS_CLAUSE | JsSyntaxKind::TS_IMPLEMENTS_CLAUSE
+            )
+        })
+        .map(|clause| format!(" {}", clause.text_trimmed()))
+        .collect()
+}
+
+fn modifiers(list: &JsSyntaxNode) -> Vec<String> {
+    list.children()
+        .map(|modifier| modifier.text_trimmed().to_string())
+        .filter(|modifier| KEPT_MODIFIERS.contains(&modifier.as_str()))
+        .collect()
+}
+
+fn member_name(name: SyntaxResult<JsSyntaxNode>) -> String {
+    name.map_or(String::new(), |name| name.text_trimmed().to_string())
+}
+
+fn is_member_name(kind: JsSyntaxKind) -> bool {
+    matches!(
+        kind,
+        JsSyntaxKind::JS_LITERAL_MEMBER_NAME
+            | JsSyntaxKind::JS_COMPUTED_MEMBER_NAME
+            | JsSyntaxKind::JS_PRIVATE_CLASS_MEMBER_NAME
+    )
+}
+
+fn has_private_name(member: &JsSyntaxNode) -> bool {
+    member
+        .children()
+        .any(|child| child.kind() == JsSyntaxKind::JS_PRIVATE_CLASS_MEMBER_NAME)
+}
+
+/// Leading spaces and tabs.
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start_matches([' ', '\t']).len()
+}
+
+fn is_primitive_literal(ty: &Type) -> bool {
+    matches!(
+        ty,
+        Type::Literal(
+            TsLiteralTypeKind::Number(_)
+                | TsLiteralTypeKind::String(_)
+                | TsLiteralTypeKind::Boolean(_)
+        )
+    )
+}
+
+/// Widens the literals in the properties of an object type, which are
+/// mutable, e.g. `{ retries: number; }` for `{ retries: 3 }`.
+fn widen_properties(types: &TypeArena, ty: TypeId) -> TypeId {
+    match &*types.get(ty) {
+        Type::Literal(TsLiteralTypeKind::Object(_)) => types.map(ty, |property| {
+            widen_properties(types, widen_literal_type(types, property))
+        }),
+        _ => ty,
+    }
+}
+
+/// tsc writes `any` for the types it can't tell, which the analyzer
+/// leaves unknown.
+fn unknown_as_any(types: &TypeArena, ty: TypeId) -> TypeId {
+    match &*types.get(ty) {
+        Type::Unknown => types.intern(Type::KeywordType(TsKeywordTypeKind::Any)),
+        Type::Union(_)
+        | Type::Intersection(_)
+        | Type::Tuple(_)
+        | Type::TypeRef(_)
+        | Type::Function(_)
+        | Type::Literal(TsLiteralTypeKind::Object(_)) => {
+            types.map(ty, |child| unknown_as_any(types, child))
+        }
+        _ => ty,
+    }
+}
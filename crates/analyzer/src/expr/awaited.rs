@@ -0,0 +1,26 @@
+use biome_js_syntax::JsAwaitExpression;
+use type_info::{TsKeywordTypeKind, Type, TypeArena, TypeId};
+
+use crate::{TResult, TypeAnalyzer};
+
+impl TypeAnalyzer {
+    pub fn analyze_js_await_expression(&self, node: &JsAwaitExpression) -> TResult<TypeId> {
+        let argument = self.analyze_any_js_expression(&node.argument()?)?;
+        Ok(awaited_type(&self.types, argument))
+    }
+}
+
+/// Mirrors TypeScript's `Awaited<T>`: unwraps (nested) promises, leaving
+/// every other type untouched.
+pub fn awaited_type(types: &TypeArena, ty: TypeId) -> TypeId {
+    match &*types.get(ty) {
+        Type::TypeRef(type_ref) if type_ref.name == "Promise" || type_ref.name == "PromiseLike" => {
+            match type_ref.type_params.first() {
+                Some(inner) => awaited_type(types, *inner),
+                None => types.intern(Type::KeywordType(TsKeywordTypeKind::Any)),
+            }
+        }
+        Type::Union(_) => types.map(ty, |t| awaited_type(types, t)),
+        _ => ty,
+    }
+}
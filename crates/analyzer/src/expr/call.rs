@@ -0,0 +1,139 @@
+use std::fmt;
+
+use biome_js_syntax::{AnyJsCallArgument, JsCallExpression, JsNewExpression, TextRange};
+use biome_rowan::AstNode;
+use type_info::{TsFunctionSignature, TsTypeRef, Type, TypeId};
+
+use crate::{TResult, TypeAnalyzer};
+
+/// A call with more or fewer arguments than its callee accepts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArityMismatch {
+    /// The call expression.
+    pub range: TextRange,
+    /// The minimum and maximum number of arguments, see
+    /// [TsFunctionSignature::arity].
+    pub expected: (usize, Option<usize>),
+    pub found: usize,
+}
+
+/// Worded like tsc's errors.
+impl fmt::Display for ArityMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.expected {
+            (min, Some(max)) if min == max => write!(f, "Expected {} arguments", min)?,
+            (min, Some(max)) => write!(f, "Expected {}-{} arguments", min, max)?,
+            (min, None) => write!(f, "Expected at least {} arguments", min)?,
+        }
+        write!(f, ", but got {}.", self.found)
+    }
+}
+
+impl TypeAnalyzer {
+    /// With overloads, the return type of the first one accepting the
+    /// arguments, like tsc picks the first matching overload.
+    pub fn analyze_js_call_expression(&self, node: &JsCallExpression) -> TResult<TypeId> {
+        let callee = self.analyze_any_js_expression(&node.callee()?)?;
+        let signatures = self.signatures_of(callee, "__call");
+        let count = argument_count(node);
+        let func = signatures
+            .iter()
+            .find(|func| count.is_some_and(|count| func.accepts_argument_count(&self.types, count)))
+            .or(signatures.first());
+        let ty = match func {
+            Some(func) => self.return_type_of(func),
+            None => self.intern(Type::Unknown),
+        };
+        Ok(ty)
+    }
+
+    /// `new Promise(...)` resolves through the `__new` signature of the
+    /// constructor's type, e.g. `PromiseConstructor`.
+    pub fn analyze_js_new_expression(&self, node: &JsNewExpression) -> TResult<TypeId> {
+        let callee = self.analyze_any_js_expression(&node.callee()?)?;
+        let ty = match self.signatures_of(callee, "__new").first() {
+            Some(func) => func.return_type,
+            None => self.intern(Type::Unknown),
+        };
+        Ok(ty)
+    }
+
+    /// Checks the number of arguments at a call site against the callee's
+    /// signatures, passing if any overload accepts them. Calls to unknown
+    /// callees or with spread arguments pass.
+    pub fn check_call_arity(&self, node: &JsCallExpression) -> TResult<Option<ArityMismatch>> {
+        let callee = self.analyze_any_js_expression(&node.callee()?)?;
+        let signatures = self.signatures_of(callee, "__call");
+        let Some(count) = argument_count(node) else {
+            return Ok(None);
+        };
+        if signatures.is_empty()
+            || signatures
+                .iter()
+                .any(|func| func.accepts_argument_count(&self.types, count))
+        {
+            return Ok(None);
+        }
+        // The fewest and most arguments any overload takes.
+        let expected = signatures
+            .iter()
+            .map(|func| func.arity(&self.types))
+            .reduce(|(min, max), (other_min, other_max)| {
+                (
+                    min.min(other_min),
+                    max.zip(other_max).map(|(a, b)| a.max(b)),
+                )
+            })
+            .unwrap();
+        Ok(Some(ArityMismatch {
+            range: node.syntax().text_trimmed_range(),
+            expected,
+            found: count,
+        }))
+    }
+
+    /// Returns the call (`__call`) or construct (`__new`) signatures of a
+    /// callee, several for overloads. Plain function types only have a call
+    /// signature.
+    fn signatures_of(&self, callee: TypeId, kind: &str) -> Vec<TsFunctionSignature> {
+        match &*self.get_type(callee) {
+            Type::Function(func) if kind == "__call" => vec![func.clone()],
+            Type::Intersection(types) => types
+                .iter()
+                .flat_map(|ty| self.signatures_of(*ty, kind))
+                .collect(),
+            Type::Function(_) | Type::Unknown => vec![],
+            _ => {
+                let member = self.resolve_member_type(callee, kind, &self.current_path);
+                match &*self.get_type(member) {
+                    Type::Function(func) => vec![func.clone()],
+                    Type::Intersection(_) => self.signatures_of(member, "__call"),
+                    _ => vec![],
+                }
+            }
+        }
+    }
+
+    /// Unannotated async functions return a `Promise` of an unknown type.
+    fn return_type_of(&self, func: &TsFunctionSignature) -> TypeId {
+        match *self.get_type(func.return_type) {
+            Type::Unknown if func.is_async => self.intern(Type::TypeRef(TsTypeRef {
+                name: "Promise".to_string(),
+                type_params: vec![func.return_type],
+            })),
+            _ => func.return_type,
+        }
+    }
+}
+
+/// The number of arguments of a call, `None` with spread arguments.
+fn argument_count(node: &JsCallExpression) -> Option<usize> {
+    let mut count = 0;
+    for arg in node.arguments().ok()?.args().into_iter().flatten() {
+        if matches!(arg, AnyJsCallArgument::JsSpread(_)) {
+            return None;
+        }
+        count += 1;
+    }
+    Some(count)
+}
@@ -1,12 +1,14 @@
 use biome_js_syntax::{AnyJsLiteralExpression, AnyJsObjectMember, JsObjectExpression};
+use biome_rowan::AstNode;
 use type_info::{
-    BoolLiteral, ObjectLiteral, ObjectPropertyType, TsKeywordTypeKind, TsLiteralTypeKind, Type,
+    BoolLiteral, ObjectLiteral, ObjectPropertyType, TsKeywordTypeKind, TsLiteralTypeKind,
+    TsTypeRef, Type, TypeArena, TypeId,
 };
 
 use crate::{TResult, TypeAnalyzer};
 
 impl TypeAnalyzer {
-    pub fn analyze_js_literal_expression(&self, node: &AnyJsLiteralExpression) -> TResult<Type> {
+    pub fn analyze_js_literal_expression(&self, node: &AnyJsLiteralExpression) -> TResult<TypeId> {
         let ty = match node {
             AnyJsLiteralExpression::JsBooleanLiteralExpression(node) => {
                 let value = node.value_token()?;
@@ -18,7 +20,11 @@ impl TypeAnalyzer {
             }
             AnyJsLiteralExpression::JsNumberLiteralExpression(lit) => {
                 let value = lit.value_token()?;
-                Type::Literal(TsLiteralTypeKind::Number(value.text().parse().unwrap()))
+                // Only integers are representable as literal types for now.
+                match value.text().parse() {
+                    Ok(value) => Type::Literal(TsLiteralTypeKind::Number(value)),
+                    Err(_) => Type::KeywordType(TsKeywordTypeKind::Number),
+                }
             }
             AnyJsLiteralExpression::JsStringLiteralExpression(lit) => {
                 let value = lit.value_token()?.text().to_string().replace("\'", "");
@@ -28,18 +34,27 @@ impl TypeAnalyzer {
             AnyJsLiteralExpression::JsNullLiteralExpression(_) => {
                 Type::KeywordType(TsKeywordTypeKind::Null)
             }
-            _ => todo!("{:?}", node),
+            AnyJsLiteralExpression::JsBigintLiteralExpression(_) => {
+                Type::KeywordType(TsKeywordTypeKind::BigInt)
+            }
+            AnyJsLiteralExpression::JsRegexLiteralExpression(_) => Type::TypeRef(TsTypeRef {
+                name: "RegExp".to_string(),
+                type_params: vec![],
+            }),
         };
-        Ok(ty)
+        Ok(self.intern(ty))
     }
 
-    pub fn analyze_js_object_expression(&self, node: &JsObjectExpression) -> TResult<Type> {
+    pub fn analyze_js_object_expression(&self, node: &JsObjectExpression) -> TResult<TypeId> {
         let mut properties = vec![];
         for prop in node.members() {
             let prop = prop?;
             match prop {
                 AnyJsObjectMember::JsPropertyObjectMember(member) => {
-                    let key = member.name()?.name().unwrap().to_string();
+                    let Some(key) = member.name()?.name() else {
+                        continue;
+                    };
+                    let key = key.text().to_string();
                     let value = member.value()?;
                     let value_ty = self.analyze_any_js_expression(&value)?;
                     properties.push(ObjectPropertyType {
@@ -47,11 +62,34 @@ impl TypeAnalyzer {
                         type_info: value_ty,
                     });
                 }
-                node => todo!("{:?}", node),
+                AnyJsObjectMember::JsShorthandPropertyObjectMember(member) => {
+                    let name = member.name()?.text();
+                    let type_info = self.lookup_value_at(&name, member.syntax()).map_or_else(
+                        || self.intern(Type::Unknown),
+                        |symbol| self.flow_type_at(symbol, member.syntax()),
+                    );
+                    properties.push(ObjectPropertyType { name, type_info });
+                }
+                // Not modelled yet: methods, accessors and spreads.
+                _ => {}
             }
         }
-        Ok(Type::Literal(TsLiteralTypeKind::Object(ObjectLiteral {
-            properties,
-        })))
+        Ok(
+            self.intern(Type::Literal(TsLiteralTypeKind::Object(ObjectLiteral {
+                properties,
+            }))),
+        )
     }
 }
+
+/// Widens a literal type the way TypeScript does for mutable locations,
+/// e.g. a parameter default of `1` gives the parameter type `number`.
+pub fn widen_literal_type(types: &TypeArena, ty: TypeId) -> TypeId {
+    let keyword = match &*types.get(ty) {
+        Type::Literal(TsLiteralTypeKind::Number(_)) => TsKeywordTypeKind::Number,
+        Type::Literal(TsLiteralTypeKind::String(_)) => TsKeywordTypeKind::String,
+        Type::Literal(TsLiteralTypeKind::Boolean(_)) => TsKeywordTypeKind::Boolean,
+        _ => return ty,
+    };
+    types.intern(Type::KeywordType(keyword))
+}
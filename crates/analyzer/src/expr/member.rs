@@ -0,0 +1,28 @@
+use biome_js_syntax::{JsIdentifierExpression, JsStaticMemberExpression};
+use biome_rowan::AstNode;
+use type_info::{Type, TypeId};
+
+use crate::{TResult, TypeAnalyzer};
+
+impl TypeAnalyzer {
+    pub fn analyze_js_identifier_expression(
+        &self,
+        node: &JsIdentifierExpression,
+    ) -> TResult<TypeId> {
+        let name = node.name()?.text();
+        let ty = self.lookup_value_at(&name, node.syntax()).map_or_else(
+            || self.intern(Type::Unknown),
+            |symbol| self.flow_type_at(symbol, node.syntax()),
+        );
+        Ok(ty)
+    }
+
+    pub fn analyze_js_static_member_expression(
+        &self,
+        node: &JsStaticMemberExpression,
+    ) -> TResult<TypeId> {
+        let object = self.analyze_any_js_expression(&node.object()?)?;
+        let member = node.member()?.text();
+        Ok(self.resolve_member_type(object, &member, &self.current_path))
+    }
+}
@@ -1,20 +1,27 @@
 use biome_js_syntax::AnyJsExpression;
-use type_info::Type;
+use type_info::{Type, TypeId};
 
 use crate::{TResult, TypeAnalyzer};
 
+mod awaited;
+mod call;
 mod literal;
+mod member;
+mod this;
+
+pub use call::ArityMismatch;
+pub(crate) use literal::widen_literal_type;
 
 impl TypeAnalyzer {
-    pub fn analyze_expression(&self, node: &AnyJsExpression) -> Type {
+    pub fn analyze_expression(&self, node: &AnyJsExpression) -> TypeId {
         let ty = self.analyze_any_js_expression(node);
         match ty {
             Ok(ty) => ty,
-            Err(_) => Type::Unknown,
+            Err(_) => self.intern(Type::Unknown),
         }
     }
 
-    pub fn analyze_any_js_expression(&self, node: &AnyJsExpression) -> TResult<Type> {
+    pub fn analyze_any_js_expression(&self, node: &AnyJsExpression) -> TResult<TypeId> {
         let ty = match node {
             AnyJsExpression::AnyJsLiteralExpression(expr) => {
                 self.analyze_js_literal_expression(expr)?
@@ -23,11 +30,24 @@ impl TypeAnalyzer {
             AnyJsExpression::JsArrowFunctionExpression(node) => {
                 self.analyze_js_arrow_function_expression(node)?
             }
-            AnyJsExpression::JsFunctionExpression(node) => {
+            AnyJsExpression::JsIdentifierExpression(node) => {
+                self.analyze_js_identifier_expression(node)?
+            }
+            AnyJsExpression::JsStaticMemberExpression(node) => {
+                self.analyze_js_static_member_expression(node)?
+            }
+            AnyJsExpression::JsThisExpression(node) => self.analyze_js_this_expression(node)?,
+            AnyJsExpression::JsCallExpression(node) => self.analyze_js_call_expression(node)?,
+            AnyJsExpression::JsNewExpression(node) => self.analyze_js_new_expression(node)?,
+            AnyJsExpression::JsAwaitExpression(node) => self.analyze_js_await_expression(node)?,
+            AnyJsExpression::JsFunctionExpression(_) => {
                 // temporarily ignore
-                Type::Unknown
+                self.intern(Type::Unknown)
+            }
+            AnyJsExpression::JsParenthesizedExpression(node) => {
+                self.analyze_any_js_expression(&node.expression()?)?
             }
-            _ => todo!("{:?}", node),
+            _ => self.intern(Type::Unknown),
         };
         Ok(ty)
     }
@@ -0,0 +1,50 @@
+use biome_js_syntax::{
+    JsConstructorClassMember, JsFunctionDeclaration, JsFunctionExpression, JsGetterClassMember,
+    JsGetterObjectMember, JsMethodClassMember, JsMethodObjectMember, JsObjectExpression,
+    JsPropertyClassMember, JsSetterClassMember, JsSetterObjectMember, JsThisExpression,
+};
+use biome_rowan::AstNode;
+use type_info::{Type, TypeId};
+
+use crate::{TResult, TypeAnalyzer};
+
+impl TypeAnalyzer {
+    /// Types `this` from the closest enclosing function that binds it.
+    /// Arrow functions don't bind `this`, so they are skipped. In the methods
+    /// and accessors of an object literal, `this` is the object.
+    pub fn analyze_js_this_expression(&self, node: &JsThisExpression) -> TResult<TypeId> {
+        for ancestor in node.syntax().ancestors().skip(1) {
+            if let Some(func) = JsFunctionDeclaration::cast_ref(&ancestor) {
+                let params = func.parameters()?;
+                return Ok(self.this_param_or_unknown(self.analyze_this_parameter(&params)?));
+            }
+            if let Some(func) = JsFunctionExpression::cast_ref(&ancestor) {
+                let params = func.parameters()?;
+                return Ok(self.this_param_or_unknown(self.analyze_this_parameter(&params)?));
+            }
+            if JsMethodObjectMember::can_cast(ancestor.kind())
+                || JsGetterObjectMember::can_cast(ancestor.kind())
+                || JsSetterObjectMember::can_cast(ancestor.kind())
+            {
+                return match ancestor.ancestors().find_map(JsObjectExpression::cast) {
+                    Some(object) => self.analyze_js_object_expression(&object),
+                    None => Ok(self.intern(Type::Unknown)),
+                };
+            }
+            // Classes aren't analyzed, see `visit_unsupported_declaration`.
+            if JsMethodClassMember::can_cast(ancestor.kind())
+                || JsConstructorClassMember::can_cast(ancestor.kind())
+                || JsGetterClassMember::can_cast(ancestor.kind())
+                || JsSetterClassMember::can_cast(ancestor.kind())
+                || JsPropertyClassMember::can_cast(ancestor.kind())
+            {
+                return Ok(self.intern(Type::Unknown));
+            }
+        }
+        Ok(self.intern(Type::Unknown))
+    }
+
+    fn this_param_or_unknown(&self, this_param: Option<TypeId>) -> TypeId {
+        this_param.unwrap_or_else(|| self.intern(Type::Unknown))
+    }
+}
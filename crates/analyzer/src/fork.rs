@@ -0,0 +1,139 @@
+use std::{path::PathBuf, sync::Arc};
+
+use type_info::symbol::{Namespace, Symbol};
+
+use crate::{module::ambient_module_path, TypeAnalyzer};
+
+/// What analyzing a file declared, in order: symbols and exports with the
+/// scope they were declared in, and the names of its `declare module` blocks.
+#[derive(Debug, Clone, Default)]
+pub struct Declarations {
+    pub symbols: Vec<(PathBuf, Namespace, Symbol)>,
+    pub exports: Vec<(PathBuf, Namespace, Symbol)>,
+    pub ambient_modules: Vec<String>,
+}
+
+impl Declarations {
+    /// Whether other files may see these declarations without importing the
+    /// file, i.e. it declares globals or ambient modules.
+    pub fn is_shared(&self) -> bool {
+        !self.symbols.is_empty() || !self.exports.is_empty() || !self.ambient_modules.is_empty()
+    }
+}
+
+impl TypeAnalyzer {
+    /// A copy of the analyzer to analyze one file with, possibly on another
+    /// thread. It shares the symbols and types of this analyzer, and what it
+    /// declares is brought back with [TypeAnalyzer::merge].
+    pub fn fork(&self) -> Self {
+        Self {
+            current_path: self.current_path.clone(),
+            current_file: self.current_file.clone(),
+            symbol_table: self.symbol_table.fork(),
+            builtin_symbol_table: self.builtin_symbol_table.clone(),
+            types: self.types.clone(),
+            resolved_refs: self.resolved_refs.clone(),
+            resolved_modules: self.resolved_modules.clone(),
+            ambient_modules: self.ambient_modules.clone(),
+            declarations: Some(Declarations::default()),
+            bindings: Arc::default(),
+            current_scope: None,
+            exporting: false,
+            arity_mismatches: vec![],
+            unresolved_imports: vec![],
+            unsupported_declarations: vec![],
+        }
+    }
+
+    /// Stops sharing the resolved references with the forks, and the analyzer
+    /// forked from, after the symbols changed.
+    pub(crate) fn invalidate_resolved_refs(&mut self) {
+        self.resolved_refs = Arc::default();
+    }
+
+    /// Makes forking cheap again after symbols were added or merged.
+    pub fn freeze(&mut self) {
+        self.symbol_table.freeze();
+    }
+
+    /// Brings back what `fork` declared while analyzing the file with scope
+    /// `scope`. A module's own scope is taken as is. Declarations in shared
+    /// scopes (the global scope, ambient modules) are merged in the order
+    /// they were made, and returned so that they can be replayed with
+    /// [TypeAnalyzer::declare].
+    pub fn merge(&mut self, mut fork: TypeAnalyzer, scope: &PathBuf) -> Declarations {
+        let is_module = !scope.as_os_str().is_empty();
+        if is_module {
+            if let Some(symbols) = fork.symbol_table.take_local(scope) {
+                self.symbol_table.replace(scope.clone(), symbols);
+            }
+            if let Some(modules) = fork.resolved_modules.get(scope) {
+                Arc::make_mut(&mut self.resolved_modules).insert(scope.clone(), modules.clone());
+            }
+        }
+
+        let mut declarations = fork.declarations.take().unwrap_or_default();
+        declarations.symbols = declarations
+            .symbols
+            .into_iter()
+            .filter(|(path, _, _)| !is_module || path != scope)
+            .map(|(path, namespace, symbol)| {
+                // Assignments after the declaration narrowed the fork's copy.
+                let flow_ty = fork
+                    .symbol_table
+                    .get(&path, namespace, &symbol.name)
+                    .filter(|_| symbol.is_mutable)
+                    .map_or(symbol.flow_ty, |declared| declared.flow_ty);
+                (path, namespace, Symbol { flow_ty, ..symbol })
+            })
+            .collect();
+        declarations
+            .exports
+            .retain(|(path, _, _)| !is_module || path != scope);
+        self.declare(&declarations);
+        declarations
+    }
+
+    /// Adds `declarations` to their scopes, merged with the symbols already
+    /// there.
+    pub fn declare(&mut self, declarations: &Declarations) {
+        let previous = std::mem::take(&mut self.current_path);
+        for (path, namespace, symbol) in &declarations.symbols {
+            self.current_path = path.clone();
+            self.insert_new_symbol(*namespace, symbol.clone());
+        }
+        for (path, namespace, symbol) in &declarations.exports {
+            self.current_path = path.clone();
+            self.export_symbol(*namespace, symbol.clone());
+        }
+        self.current_path = previous;
+
+        for name in &declarations.ambient_modules {
+            if !self.ambient_modules.contains(name) {
+                self.ambient_modules.push(name.clone());
+            }
+        }
+        self.invalidate_resolved_refs();
+    }
+
+    /// Drops the scope of the module at `scope` and the imports resolved
+    /// from it, so that the module can be analyzed again.
+    pub fn remove_scope(&mut self, scope: &PathBuf) {
+        self.symbol_table.remove(scope);
+        if self.resolved_modules.contains_key(scope) {
+            Arc::make_mut(&mut self.resolved_modules).remove(scope);
+        }
+        self.invalidate_resolved_refs();
+    }
+
+    /// Resets the global scope to the lib declarations and drops every
+    /// ambient module. Built-in tables are kept as they are.
+    pub fn reset_shared_scopes(&mut self) {
+        for name in std::mem::take(&mut self.ambient_modules) {
+            self.symbol_table.remove(&ambient_module_path(&name));
+        }
+        self.symbol_table
+            .replace(PathBuf::new(), self.builtin_symbol_table.symbols().clone());
+        self.invalidate_resolved_refs();
+    }
+}
@@ -1,15 +1,13 @@
-use biome_js_syntax::{
-    AnyJsArrowFunctionParameters, AnyJsBinding, AnyTsReturnType, JsArrowFunctionExpression,
-};
-use type_info::{FunctionParam, TsFunctionSignature, Type};
+use biome_js_syntax::{AnyJsArrowFunctionParameters, JsArrowFunctionExpression};
+use type_info::{FunctionParam, TsFunctionSignature, Type, TypeId};
 
-use crate::{TResult, TypeAnalyzer};
+use crate::{binding::binding_name, TResult, TypeAnalyzer};
 
 impl TypeAnalyzer {
     pub fn analyze_js_arrow_function_expression(
         &self,
         node: &JsArrowFunctionExpression,
-    ) -> TResult<Type> {
+    ) -> TResult<TypeId> {
         let is_async = node.async_token().is_some();
         let mut type_params = vec![];
         if let Some(params) = node.type_parameters() {
@@ -23,17 +21,16 @@ impl TypeAnalyzer {
 
         if let Ok(parameters) = node.parameters() {
             match parameters {
-                AnyJsArrowFunctionParameters::AnyJsBinding(node) => match node {
-                    AnyJsBinding::JsIdentifierBinding(bind) => {
-                        let name = bind.name_token().unwrap().text_trimmed().to_string();
+                AnyJsArrowFunctionParameters::AnyJsBinding(node) => {
+                    if let Some(name) = binding_name(&node) {
                         params.push(FunctionParam {
                             name,
                             is_optional: false,
-                            param_type: Type::Unknown,
+                            param_type: self.intern(Type::Unknown),
+                            is_rest: false,
                         });
                     }
-                    _ => todo!("{:?}", node),
-                },
+                }
                 AnyJsArrowFunctionParameters::JsParameters(param) => {
                     params = self.analyze_js_parameters(&param)?;
                 }
@@ -42,23 +39,17 @@ impl TypeAnalyzer {
 
         let return_type = if let Some(ty) = node.return_type_annotation() {
             let ty = ty.ty()?;
-            match ty {
-                AnyTsReturnType::AnyTsType(any_ts_type) => {
-                    let ty = self.analyze_any_ts_types(&any_ts_type)?;
-                    Box::new(ty)
-                }
-                node => todo!("{:?}", node),
-            }
+            self.analyze_any_ts_return_type(&ty)?
         } else {
-            Box::new(Type::Unknown)
+            self.intern(Type::Unknown)
         };
 
-        Ok(Type::Function(TsFunctionSignature {
+        Ok(self.intern(Type::Function(TsFunctionSignature {
             type_params,
             this_param: None,
             params,
             return_type,
             is_async,
-        }))
+        })))
     }
 }
@@ -1,43 +1,43 @@
 use biome_js_syntax::{
-    AnyJsFormalParameter, AnyJsParameter, AnyTsReturnType, JsFunctionDeclaration, JsParameters,
+    AnyJsBindingPattern, AnyJsConstructorParameter, AnyJsFormalParameter, AnyJsFunctionBody,
+    AnyJsParameter, JsArrowFunctionExpression, JsConstructorParameters, JsFunctionDeclaration,
+    JsParameters, JsRestParameter, JsReturnStatement, JsSyntaxKind, JsSyntaxNode,
+};
+use biome_rowan::AstNode;
+use type_info::{
+    symbol::Symbol, FunctionParam, TsFunctionSignature, TsKeywordTypeKind, TsTypeRef, Type, TypeId,
 };
-use type_info::{FunctionParam, TsFunctionSignature, Type};
 
-use crate::{TResult, TypeAnalyzer};
+use crate::{binding::binding_name, expr::widen_literal_type, TResult, TypeAnalyzer};
 
 mod arrow;
 
 impl TypeAnalyzer {
-    pub fn analyze_js_function_declaration(
-        &self,
-        node: &JsFunctionDeclaration,
-    ) -> TResult<Type> {
+    pub fn analyze_js_function_declaration(&self, node: &JsFunctionDeclaration) -> TResult<TypeId> {
         let is_async = node.async_token().is_some();
 
         let mut params = vec![];
+        let mut this_param = None;
 
         if let Ok(param) = node.parameters() {
             params = self.analyze_js_parameters(&param)?;
+            this_param = self.analyze_this_parameter(&param)?;
         }
 
         let return_type = if let Some(ret_ty) = node.return_type_annotation() {
-            let ty = ret_ty.ty()?;
-            match ty {
-                AnyTsReturnType::AnyTsType(ty) => self.analyze_any_ts_types(&ty)?,
-                _ => Type::Unknown,
-            }
+            self.analyze_any_ts_return_type(&ret_ty.ty()?)?
         } else {
-            Type::Unknown
+            self.intern(Type::Unknown)
         };
 
-        Ok(Type::Function(TsFunctionSignature {
+        Ok(self.intern(Type::Function(TsFunctionSignature {
             //todo
             type_params: vec![],
-            this_param: None,
+            this_param,
             params,
-            return_type: Box::new(return_type),
+            return_type,
             is_async,
-        }))
+        })))
     }
 
     pub fn analyze_js_parameters(&self, params: &JsParameters) -> TResult<Vec<FunctionParam>> {
@@ -45,32 +45,211 @@ impl TypeAnalyzer {
         for p in params.items().into_iter().flatten() {
             match p {
                 AnyJsParameter::AnyJsFormalParameter(p) => {
-                    match p {
-                        AnyJsFormalParameter::JsFormalParameter(p) => {
-                            let name = p.binding()?;
-                            let is_optional = p.question_mark_token().is_some();
-                            let param_type = if let Some(ann) = p.type_annotation() {
-                                self.analyze_type_annotation(ann)
-                            } else {
-                                Type::Unknown
-                            };
-
-                            result.push(FunctionParam {
-                                name: name.to_string(),
-                                is_optional,
-                                param_type,
-                            });
-                        }
-                        AnyJsFormalParameter::JsBogusParameter(_)
-                        | AnyJsFormalParameter::JsMetavariable(_) => {
-                            unreachable!()
-                        }
-                    };
+                    result.extend(self.analyze_formal_parameter(&p, result.len())?);
+                }
+                AnyJsParameter::JsRestParameter(p) => {
+                    result.push(self.analyze_rest_parameter(&p, result.len())?);
                 }
-                AnyJsParameter::JsRestParameter(_) => todo!(),
-                AnyJsParameter::TsThisParameter(_) => todo!(),
+                // `this` is not a real parameter; see `analyze_this_parameter`.
+                AnyJsParameter::TsThisParameter(_) => {}
             }
         }
         Ok(result)
     }
+
+    /// Like [TypeAnalyzer::analyze_js_parameters], for a constructor, whose
+    /// parameter properties are typed like plain parameters.
+    pub fn analyze_js_constructor_parameters(
+        &self,
+        params: &JsConstructorParameters,
+    ) -> TResult<Vec<FunctionParam>> {
+        let mut result = vec![];
+        for p in params.parameters().into_iter().flatten() {
+            let param = match p {
+                AnyJsConstructorParameter::AnyJsFormalParameter(p) => {
+                    self.analyze_formal_parameter(&p, result.len())?
+                }
+                AnyJsConstructorParameter::JsRestParameter(p) => {
+                    Some(self.analyze_rest_parameter(&p, result.len())?)
+                }
+                AnyJsConstructorParameter::TsPropertyParameter(p) => {
+                    self.analyze_formal_parameter(&p.formal_parameter()?, result.len())?
+                }
+            };
+            result.extend(param);
+        }
+        Ok(result)
+    }
+
+    /// The parameter, or `None` for what the parser couldn't make sense of.
+    pub fn analyze_formal_parameter(
+        &self,
+        param: &AnyJsFormalParameter,
+        index: usize,
+    ) -> TResult<Option<FunctionParam>> {
+        match param {
+            AnyJsFormalParameter::JsFormalParameter(p) => {
+                let binding = p.binding()?;
+                let initializer = p.initializer();
+                let is_optional = p.question_mark_token().is_some() || initializer.is_some();
+                let param_type = if let Some(ann) = p.type_annotation() {
+                    self.analyze_type_annotation(ann)
+                } else if let Some(init) = initializer {
+                    widen_literal_type(&self.types, self.analyze_expression(&init.expression()?))
+                } else {
+                    self.intern(Type::Unknown)
+                };
+
+                Ok(Some(FunctionParam {
+                    name: parameter_name(&binding, index),
+                    is_optional,
+                    param_type,
+                    is_rest: false,
+                }))
+            }
+            AnyJsFormalParameter::JsBogusParameter(_) | AnyJsFormalParameter::JsMetavariable(_) => {
+                Ok(None)
+            }
+        }
+    }
+
+    fn analyze_rest_parameter(
+        &self,
+        param: &JsRestParameter,
+        index: usize,
+    ) -> TResult<FunctionParam> {
+        let binding = param.binding()?;
+        let param_type = if let Some(ann) = param.type_annotation() {
+            self.analyze_type_annotation(ann)
+        } else {
+            self.array_of(self.intern(Type::KeywordType(TsKeywordTypeKind::Any)))
+        };
+
+        Ok(FunctionParam {
+            name: parameter_name(&binding, index),
+            is_optional: false,
+            param_type,
+            is_rest: true,
+        })
+    }
+
+    /// Infers what an unannotated function returns from its `return`
+    /// statements, or from the body of an arrow function. Returned literals
+    /// are widened, and async functions return a `Promise` of the result.
+    /// Generators aren't modelled.
+    pub fn infer_return_type(&self, function: &JsSyntaxNode, is_async: bool) -> TypeId {
+        if function
+            .children_with_tokens()
+            .any(|child| child.kind() == JsSyntaxKind::STAR)
+        {
+            return self.intern(Type::Unknown);
+        }
+        let expression_body =
+            JsArrowFunctionExpression::cast_ref(function).and_then(|arrow| match arrow.body() {
+                Ok(AnyJsFunctionBody::AnyJsExpression(body)) => Some(body),
+                _ => None,
+            });
+        let mut types = vec![];
+        if let Some(body) = expression_body {
+            types.push(widen_literal_type(
+                &self.types,
+                self.analyze_expression(&body),
+            ));
+        }
+        for statement in function.descendants().filter_map(JsReturnStatement::cast) {
+            let owner = statement
+                .syntax()
+                .ancestors()
+                .skip(1)
+                .find(|node| is_function_like(node.kind()));
+            if owner.as_ref() != Some(function) {
+                continue;
+            }
+            let ty = match statement.argument() {
+                Some(argument) => {
+                    widen_literal_type(&self.types, self.analyze_expression(&argument))
+                }
+                None => self.intern(Type::KeywordType(TsKeywordTypeKind::Void)),
+            };
+            if !types.contains(&ty) {
+                types.push(ty);
+            }
+        }
+        let ty = match types.len() {
+            0 => self.intern(Type::KeywordType(TsKeywordTypeKind::Void)),
+            1 => types.remove(0),
+            _ => self.intern(Type::Union(types)),
+        };
+        match &*self.get_type(ty) {
+            Type::TypeRef(promise) if is_async && promise.name == "Promise" => ty,
+            _ if is_async => self.intern(Type::TypeRef(TsTypeRef {
+                name: "Promise".to_string(),
+                type_params: vec![ty],
+            })),
+            _ => ty,
+        }
+    }
+
+    /// Types every name bound by the parameter list, including the ones
+    /// introduced by destructuring patterns.
+    pub fn analyze_parameter_bindings(&self, params: &JsParameters) -> TResult<Vec<Symbol>> {
+        let signature = self.analyze_js_parameters(params)?;
+        let mut bindings = vec![];
+        let patterns = params
+            .items()
+            .into_iter()
+            .flatten()
+            .filter_map(|p| match p {
+                AnyJsParameter::AnyJsFormalParameter(AnyJsFormalParameter::JsFormalParameter(
+                    p,
+                )) => p.binding().ok(),
+                AnyJsParameter::JsRestParameter(p) => p.binding().ok(),
+                _ => None,
+            });
+        for (pattern, param) in patterns.zip(signature.iter()) {
+            bindings.extend(self.analyze_binding_pattern(&pattern, param.param_type)?);
+        }
+        Ok(bindings)
+    }
+
+    pub fn analyze_this_parameter(&self, params: &JsParameters) -> TResult<Option<TypeId>> {
+        for p in params.items().into_iter().flatten() {
+            if let AnyJsParameter::TsThisParameter(p) = p {
+                let ty = if let Some(ann) = p.type_annotation() {
+                    self.analyze_type_annotation(ann)
+                } else {
+                    self.intern(Type::Unknown)
+                };
+                return Ok(Some(ty));
+            }
+        }
+        Ok(None)
+    }
+}
+
+pub(crate) fn is_function_like(kind: JsSyntaxKind) -> bool {
+    matches!(
+        kind,
+        JsSyntaxKind::JS_FUNCTION_DECLARATION
+            | JsSyntaxKind::JS_FUNCTION_EXPRESSION
+            | JsSyntaxKind::JS_FUNCTION_EXPORT_DEFAULT_DECLARATION
+            | JsSyntaxKind::JS_ARROW_FUNCTION_EXPRESSION
+            | JsSyntaxKind::JS_METHOD_CLASS_MEMBER
+            | JsSyntaxKind::JS_METHOD_OBJECT_MEMBER
+            | JsSyntaxKind::JS_GETTER_CLASS_MEMBER
+            | JsSyntaxKind::JS_GETTER_OBJECT_MEMBER
+            | JsSyntaxKind::JS_SETTER_CLASS_MEMBER
+            | JsSyntaxKind::JS_SETTER_OBJECT_MEMBER
+            | JsSyntaxKind::JS_CONSTRUCTOR_CLASS_MEMBER
+    )
+}
+
+/// Destructured parameters have no name of their own; like tsc, call them `__N`.
+fn parameter_name(binding: &AnyJsBindingPattern, index: usize) -> String {
+    match binding {
+        AnyJsBindingPattern::AnyJsBinding(binding) => {
+            binding_name(binding).unwrap_or_else(|| format!("__{}", index))
+        }
+        _ => format!("__{}", index),
+    }
 }
@@ -1,96 +1,389 @@
-use std::path::PathBuf;
+use std::{
+    path::PathBuf,
+    sync::{Arc, RwLock},
+};
 
+use binding::binding_name;
 use biome_js_parser::parse;
 use biome_js_syntax::*;
-use biome_rowan::SyntaxError;
-use symbol::{BuiltinTable, Symbol, SymbolTable};
+use biome_rowan::{AstNode, SyntaxError};
+use file_system::{FileSystem, OsFileSystem, ReadError};
+use function::is_function_like;
+use module::has_export_statements;
+use rustc_hash::FxHashMap;
+use stmt::is_mutable_declarator;
+use symbol::{BuiltinTable, Location, Namespace, Symbol, SymbolTable};
+use ts::identifier_binding_name;
 use type_info::*;
 use visitor::Visitor;
 
+mod binding;
+mod emit;
 mod expr;
+mod fork;
 mod function;
+mod module;
+mod promise;
+mod reference;
 mod resolver;
+mod scope;
+mod snapshot;
 mod stmt;
 mod ts;
 
+pub use expr::ArityMismatch;
+pub use fork::Declarations;
+pub use module::UnresolvedImport;
+pub use promise::FloatingPromise;
+pub use reference::Reference;
+pub use scope::FileBindings;
+pub use snapshot::DeclarationSnapshot;
+pub use stmt::{DeclarationKind, UnsupportedDeclaration};
+
 type TResult<T> = Result<T, SyntaxError>;
 
 #[derive(Debug, Default)]
 pub struct TypeAnalyzer {
+    /// The file being analyzed, or the empty path for the global scope
+    /// shared by scripts and lib files.
     current_path: PathBuf,
+    /// The file being analyzed, where its declarations are located. Unlike
+    /// `current_path`, never a shared scope.
+    current_file: PathBuf,
     symbol_table: SymbolTable,
-    builtin_symbol_table: BuiltinTable,
+    builtin_symbol_table: Arc<BuiltinTable>,
+    /// Every type the symbols refer to, interned. Shared with forks.
+    types: Arc<TypeArena>,
+    /// Type references already resolved, by scope. Shared with forks, and
+    /// replaced with an empty one whenever a declaration or import changes
+    /// what a name refers to, so that it's only shared by analyzers seeing
+    /// the same symbols.
+    resolved_refs: Arc<RwLock<FxHashMap<(PathBuf, TypeId), TypeId>>>,
+    /// Import specifiers of each module and the files they resolve to.
+    resolved_modules: Arc<FxHashMap<PathBuf, FxHashMap<String, PathBuf>>>,
+    /// Names of the `declare module "name"` blocks seen so far, including
+    /// wildcard patterns such as `"*.svg"`.
+    ambient_modules: Vec<String>,
+    /// What a fork declared, for [TypeAnalyzer::merge]. `None` outside forks.
+    declarations: Option<Declarations>,
+    /// The local names of the current file and the assignments to its
+    /// bindings.
+    bindings: Arc<FileBindings>,
+    /// The range of the function whose body is being visited.
+    current_scope: Option<TextRange>,
+    /// Whether the declarations being visited are exported too, as in
+    /// `export` declarations and ambient modules.
+    exporting: bool,
+    /// Calls of the file last visited with the wrong number of arguments.
+    arity_mismatches: Vec<ArityMismatch>,
+    /// Imports of the file last visited that don't bind anything.
+    unresolved_imports: Vec<UnresolvedImport>,
+    unsupported_declarations: Vec<UnsupportedDeclaration>,
 }
 
 impl TypeAnalyzer {
-    pub fn new(builtin_path: Vec<PathBuf>) -> Self {
-        let mut analyzer = Self {
-            current_path: PathBuf::new(),
-            symbol_table: SymbolTable::new(),
-            builtin_symbol_table: BuiltinTable::new(),
-        };
+    /// Fails if a lib file at `builtin_path` can't be read.
+    pub fn new(builtin_path: Vec<PathBuf>) -> Result<Self, ReadError> {
+        Self::with_file_system(&OsFileSystem, builtin_path)
+    }
 
-        analyzer.init_builtin_types(builtin_path);
-        analyzer
+    /// Like [TypeAnalyzer::new], reading the lib files from `fs`.
+    pub fn with_file_system(
+        fs: &dyn FileSystem,
+        builtin_path: Vec<PathBuf>,
+    ) -> Result<Self, ReadError> {
+        let mut analyzer = Self::default();
+        analyzer.init_builtin_types(fs, builtin_path)?;
+        Ok(analyzer)
     }
 
     pub fn current_path(&self) -> &PathBuf {
         &self.current_path
     }
 
+    pub fn set_current_path(&mut self, path: PathBuf) {
+        self.current_path = path;
+    }
+
+    /// Sets the file the next declarations are located in, which defaults
+    /// to none.
+    pub fn set_current_file(&mut self, path: PathBuf) {
+        self.current_file = path;
+    }
+
+    /// Where `node` is in the current file.
+    pub(crate) fn location(&self, node: &JsSyntaxNode) -> Location {
+        Location {
+            path: self.current_file.clone(),
+            range: node.text_trimmed_range(),
+        }
+    }
+
+    pub fn intern(&self, ty: Type) -> TypeId {
+        self.types.intern(ty)
+    }
+
+    pub fn get_type(&self, id: TypeId) -> Arc<Type> {
+        self.types.get(id)
+    }
+
+    pub fn types(&self) -> &TypeArena {
+        &self.types
+    }
+
+    /// Prints `ty`, expanding aliases it refers to in the current scope when
+    /// `options` asks for it.
+    pub fn print_type(&self, ty: TypeId, options: &PrintOptions) -> String {
+        self.types.print_with(ty, options, &|id| {
+            self.resolve_reference(id, &self.current_path)
+        })
+    }
+
+    /// The calls of the file last visited with the wrong number of arguments.
+    pub fn arity_mismatches(&self) -> &[ArityMismatch] {
+        &self.arity_mismatches
+    }
+
+    /// The imports of the file last visited whose module or name isn't found.
+    pub fn unresolved_imports(&self) -> &[UnresolvedImport] {
+        &self.unresolved_imports
+    }
+
+    /// The classes, enums and namespaces of the file last visited, which
+    /// aren't analyzed.
+    pub fn unsupported_declarations(&self) -> &[UnsupportedDeclaration] {
+        &self.unsupported_declarations
+    }
+
+    /// Records that `specifier` imported from the module at `from` refers to
+    /// the file at `resolved`.
+    pub fn add_resolved_module(&mut self, from: PathBuf, specifier: String, resolved: PathBuf) {
+        Arc::make_mut(&mut self.resolved_modules)
+            .entry(from)
+            .or_default()
+            .insert(specifier, resolved);
+        self.invalidate_resolved_refs();
+    }
+
     pub fn print_symbol_table(&self) {
         for (path, symbol_table) in self.symbol_table.iter() {
             println!("Path: {:?}", path);
-            for (name, symbol) in symbol_table.iter() {
-                println!("  \x1b[32m{}\x1b[0m: {:?}\n", name, symbol);
+            for (namespace, symbol) in symbol_table.iter() {
+                println!(
+                    "  \x1b[32m{}\x1b[0m ({:?}): {}\n",
+                    symbol.name,
+                    namespace,
+                    self.types
+                        .print(symbol.current_type(), &PrintOptions::default())
+                );
             }
         }
     }
 
     pub fn print_global_symbol_table(&self) {
-        for (name, symbol) in self.builtin_symbol_table.iter() {
-            println!("  \x1b[32m{}\x1b[0m: {:?}\n", name, symbol);
+        for (namespace, symbol) in self.builtin_symbol_table.iter() {
+            println!(
+                "  \x1b[32m{}\x1b[0m ({:?}): {}\n",
+                symbol.name,
+                namespace,
+                self.types.print(symbol.ty, &PrintOptions::default())
+            );
         }
     }
 
-    fn init_builtin_types(&mut self, path: Vec<PathBuf>) {
+    fn init_builtin_types(
+        &mut self,
+        fs: &dyn FileSystem,
+        path: Vec<PathBuf>,
+    ) -> Result<(), ReadError> {
         let src_type = JsFileSource::d_ts();
 
         for p in path {
-            let src = std::fs::read_to_string(&p).unwrap();
-            let parsed = parse(&src, src_type, Default::default());
-            if parsed.has_errors() {
-                panic!("Failed to parse source code: {:?}", parsed.diagnostics());
-            }
-            let root = parsed.tree();
+            let src = fs.read_to_string(&p).map_err(|error| ReadError {
+                path: p.clone(),
+                error,
+            })?;
+            self.current_file = p;
+            // A lib file with syntax errors is used as far as it parses.
+            let root = parse(&src, src_type, Default::default()).tree();
             self.visit(&root);
-            for (_, symbol_table) in self.symbol_table.iter() {
-                for (_, symbol) in symbol_table.iter() {
-                    self.builtin_symbol_table.insert(symbol.clone());
-                }
+        }
+        self.current_file = PathBuf::new();
+        let mut builtins = BuiltinTable::new();
+        for (_, symbol_table) in self.symbol_table.iter() {
+            for (namespace, symbol) in symbol_table.iter() {
+                builtins.insert(namespace, symbol.clone());
             }
         }
+        self.builtin_symbol_table = Arc::new(builtins);
+        self.symbol_table.freeze();
+        Ok(())
+    }
+
+    /// Inserts `symbol`, merging it with an earlier interface declaration of
+    /// the same name (e.g. `Promise` is extended by several lib files). The
+    /// locations of earlier declarations are kept either way. In `export`
+    /// declarations, the symbol is exported too.
+    pub fn insert_new_symbol(&mut self, namespace: Namespace, symbol: Symbol) {
+        if let Some(scope) = self.current_scope {
+            self.insert_local_symbol(scope, namespace, symbol);
+            return;
+        }
+        if self.exporting {
+            self.export_symbol(namespace, symbol.clone());
+        }
+        if let Some(declarations) = &mut self.declarations {
+            declarations
+                .symbols
+                .push((self.current_path.clone(), namespace, symbol.clone()));
+        }
+        let symbol = match self
+            .symbol_table
+            .get(&self.current_path, namespace, &symbol.name)
+        {
+            Some(existing) => merge_declarations(&self.types, existing, symbol),
+            None => symbol,
+        };
+        self.symbol_table
+            .insert(self.current_path.clone(), namespace, symbol);
+        self.invalidate_resolved_refs();
     }
 
-    pub fn insert_new_symbol(&mut self, symbol: Symbol) {
-        self.symbol_table.insert(self.current_path.clone(), symbol);
+    /// Adds `symbol` to what the current scope exports, merged with an
+    /// export of the same name.
+    pub fn export_symbol(&mut self, namespace: Namespace, symbol: Symbol) {
+        if let Some(declarations) = &mut self.declarations {
+            declarations
+                .exports
+                .push((self.current_path.clone(), namespace, symbol.clone()));
+        }
+        let symbol = match self
+            .symbol_table
+            .get_export(&self.current_path, namespace, &symbol.name)
+        {
+            Some(existing) => merge_declarations(&self.types, existing, symbol),
+            None => symbol,
+        };
+        self.symbol_table
+            .export(self.current_path.clone(), namespace, symbol);
+        self.invalidate_resolved_refs();
     }
 
+    /// Looks `name` up from the current file, preferring the value namespace.
     pub fn get_symbol(&self, name: &str) -> Option<&Symbol> {
-        self.symbol_table.get(&self.current_path, name)
+        self.lookup_in_scope(&self.current_path, Namespace::Value, name)
+            .or_else(|| self.lookup_in_scope(&self.current_path, Namespace::Type, name))
+    }
+
+    /// Looks `name` up in the file at `path`, then in the global scope.
+    /// Types declared in the modules the file imports are visible too, so
+    /// that the types of imported symbols resolve.
+    pub fn lookup_in_scope(
+        &self,
+        path: &PathBuf,
+        namespace: Namespace,
+        name: &str,
+    ) -> Option<&Symbol> {
+        if let Some(symbol) = self.symbol_table.get(path, namespace, name) {
+            return Some(symbol);
+        }
+        if namespace == Namespace::Type {
+            let imported = self
+                .resolved_modules
+                .get(path)
+                .into_iter()
+                .flat_map(|modules| modules.values())
+                .find_map(|module| self.symbol_table.get(module, namespace, name));
+            if imported.is_some() {
+                return imported;
+            }
+        }
+        self.symbol_table.get(&PathBuf::new(), namespace, name)
     }
 
     pub fn get_builtin_symbol(&self, name: &str) -> Option<&Symbol> {
-        self.builtin_symbol_table.get(name)
+        self.builtin_symbol_table
+            .get(Namespace::Value, name)
+            .or_else(|| self.builtin_symbol_table.get(Namespace::Type, name))
+    }
+
+    /// Looks up the value bound to `name`, falling back to lib globals such
+    /// as `Promise` or `JSON`.
+    pub fn lookup_value(&self, name: &str) -> Option<&Symbol> {
+        self.lookup_in_scope(&self.current_path, Namespace::Value, name)
+            .or_else(|| self.builtin_symbol_table.get(Namespace::Value, name))
+    }
+}
+
+impl TypeAnalyzer {
+    fn visit_declaration_clause(&mut self, node: &AnyJsDeclarationClause) {
+        match node {
+            AnyJsDeclarationClause::JsVariableDeclarationClause(node) => {
+                self.visit_js_variable_declaration_clause(node);
+            }
+            AnyJsDeclarationClause::TsDeclareFunctionDeclaration(node) => {
+                self.visit_ts_declare_function_declaration(node);
+            }
+            AnyJsDeclarationClause::JsFunctionDeclaration(node) => {
+                self.visit_js_function_declaration(node);
+            }
+            AnyJsDeclarationClause::TsInterfaceDeclaration(node) => {
+                self.visit_ts_interface_declaration(node);
+            }
+            AnyJsDeclarationClause::TsTypeAliasDeclaration(node) => {
+                self.visit_ts_type_alias_declaration(node);
+            }
+            AnyJsDeclarationClause::TsExternalModuleDeclaration(node) => {
+                self.visit_ts_external_module_declaration(node);
+            }
+            _ => self.visit_unsupported_declaration(node.syntax()),
+        }
+    }
+
+    fn visit_ts_type_alias_declaration(&mut self, node: &TsTypeAliasDeclaration) {
+        let Ok(id) = node.binding_identifier() else {
+            return;
+        };
+        let Some(name) = identifier_binding_name(&id) else {
+            return;
+        };
+        if let Ok(ty) = self.analyze_ts_type_alias_declaration(node) {
+            let symbol = Symbol::new(name, ty).declared_at(self.location(id.syntax()));
+            self.insert_new_symbol(Namespace::Type, symbol);
+        }
     }
 }
 
 impl Visitor for TypeAnalyzer {
     fn visit(&mut self, node: &AnyJsRoot) {
+        self.bindings = Arc::default();
+        self.arity_mismatches.clear();
+        self.unresolved_imports.clear();
+        self.unsupported_declarations.clear();
         match node {
             AnyJsRoot::JsModule(node) => self.visit_module(node),
             AnyJsRoot::TsDeclarationModule(node) => self.visit_ts_declaration_module(node),
-            node => todo!("{:?}", node),
+            AnyJsRoot::JsScript(node) => {
+                for statement in node.statements() {
+                    self.visit_statement(&statement);
+                }
+            }
+            _ => {}
+        }
+        // Functions that aren't declarations, such as callbacks and methods.
+        for function in node
+            .syntax()
+            .descendants()
+            .filter(|node| is_function_like(node.kind()))
+        {
+            self.declare_locals(&function);
+        }
+        for call in node
+            .syntax()
+            .descendants()
+            .filter_map(JsCallExpression::cast)
+        {
+            self.visit_js_call_expression(&call);
         }
     }
 
@@ -100,16 +393,27 @@ impl Visitor for TypeAnalyzer {
         }
     }
 
+    /// A module's declarations are exported even without `export`, unless
+    /// it has export statements.
     fn visit_ts_declaration_module(&mut self, node: &TsDeclarationModule) {
+        let exporting =
+            !self.current_path.as_os_str().is_empty() && !has_export_statements(&node.items());
+        let previous = std::mem::replace(&mut self.exporting, exporting);
         for item in node.items() {
             self.visit_module_item(&item);
         }
+        self.exporting = previous;
     }
 
     fn visit_module_item(&mut self, node: &AnyJsModuleItem) {
         match node {
             AnyJsModuleItem::AnyJsStatement(node) => self.visit_statement(node),
-            node => todo!("{:?}", node),
+            AnyJsModuleItem::JsExport(node) => {
+                if let Ok(clause) = node.export_clause() {
+                    self.visit_js_export_clause(&clause);
+                }
+            }
+            AnyJsModuleItem::JsImport(node) => self.visit_js_import(node),
         }
     }
 
@@ -130,16 +434,77 @@ impl Visitor for TypeAnalyzer {
             AnyJsStatement::JsFunctionDeclaration(node) => {
                 self.visit_js_function_declaration(node);
             }
-            node => todo!("{:?}", node),
+            AnyJsStatement::TsDeclareFunctionDeclaration(node) => {
+                self.visit_ts_declare_function_declaration(node);
+            }
+            AnyJsStatement::TsTypeAliasDeclaration(node) => {
+                self.visit_ts_type_alias_declaration(node);
+            }
+            AnyJsStatement::TsExternalModuleDeclaration(node) => {
+                self.visit_ts_external_module_declaration(node);
+            }
+            AnyJsStatement::JsBlockStatement(_)
+            | AnyJsStatement::JsIfStatement(_)
+            | AnyJsStatement::JsForStatement(_)
+            | AnyJsStatement::JsForInStatement(_)
+            | AnyJsStatement::JsForOfStatement(_)
+            | AnyJsStatement::JsWhileStatement(_)
+            | AnyJsStatement::JsDoWhileStatement(_)
+            | AnyJsStatement::JsTryStatement(_)
+            | AnyJsStatement::JsTryFinallyStatement(_)
+            | AnyJsStatement::JsSwitchStatement(_)
+            | AnyJsStatement::JsLabeledStatement(_) => {
+                self.hoist_var_declarations(node.syntax());
+                self.analyze_assignments(node.syntax());
+            }
+            _ => self.visit_unsupported_declaration(node.syntax()),
         }
     }
 
-    fn visit_js_expression_statement(&mut self, _node: &JsExpressionStatement) {}
+    fn visit_js_expression_statement(&mut self, node: &JsExpressionStatement) {
+        self.analyze_assignments(node.syntax());
+    }
 
     fn visit_js_function_declaration(&mut self, node: &JsFunctionDeclaration) {
-        if let Ok(ty) = self.analyze_js_function_declaration(node) {
-            let symbol = Symbol::new(node.id().unwrap().to_string(), ty);
-            self.insert_new_symbol(symbol);
+        let Ok(id) = node.id() else {
+            return;
+        };
+        let Some(name) = binding_name(&id) else {
+            return;
+        };
+        // The body may refer to the parameters.
+        self.declare_locals(node.syntax());
+        let ty = match self
+            .symbol_table
+            .get(&self.current_path, Namespace::Value, &name)
+        {
+            // Callers only see the overload signatures.
+            Some(overloads) if is_overload_implementation(node, &name) => Ok(overloads.ty),
+            _ => self.analyze_js_function_declaration(node),
+        };
+        if let Ok(ty) = ty {
+            let symbol = Symbol::new(name, ty).declared_at(self.location(id.syntax()));
+            self.insert_new_symbol(Namespace::Value, symbol);
+        }
+    }
+
+    /// An overload signature, or a function declared with `declare`.
+    fn visit_ts_declare_function_declaration(&mut self, node: &TsDeclareFunctionDeclaration) {
+        let Ok(id) = node.id() else {
+            return;
+        };
+        let Some(name) = binding_name(&id) else {
+            return;
+        };
+        if let Ok(ty) = self.analyze_ts_declare_function_declaration(node) {
+            let symbol = Symbol::new(name, ty).declared_at(self.location(id.syntax()));
+            self.insert_new_symbol(Namespace::Value, symbol);
+        }
+    }
+
+    fn visit_js_call_expression(&mut self, node: &JsCallExpression) {
+        if let Ok(Some(mismatch)) = self.check_call_arity(node) {
+            self.arity_mismatches.push(mismatch);
         }
     }
 
@@ -153,32 +518,21 @@ impl Visitor for TypeAnalyzer {
 
     fn visit_ts_declare_statement(&mut self, node: &TsDeclareStatement) {
         if let Ok(n) = node.declaration() {
-            match n {
-                AnyJsDeclarationClause::JsVariableDeclarationClause(node) => {
-                    self.visit_js_variable_declaration_clause(&node);
-                }
-                AnyJsDeclarationClause::TsDeclareFunctionDeclaration(node) => {
-                    if let Ok(ty) = self.analyze_ts_declare_function_declaration(&node) {
-                        let symbol = Symbol::new(node.id().unwrap().to_string(), ty);
-                        self.insert_new_symbol(symbol);
-                    }
-                }
-                _ => todo!("{:?}", n),
-            }
+            self.visit_declaration_clause(&n);
         }
     }
 
     fn visit_ts_interface_declaration(&mut self, node: &TsInterfaceDeclaration) {
-        let interface_name = match node.id().unwrap() {
-            AnyTsIdentifierBinding::TsIdentifierBinding(bind) => {
-                let name_token = bind.name_token().unwrap();
-                name_token.text_trimmed().to_string()
-            }
-            _ => todo!(),
+        let Ok(id) = node.id() else {
+            return;
         };
-        let ty = self.analyze_ts_interface_declaration(node).unwrap();
-        let symbol = Symbol::new(interface_name.to_string(), ty);
-        self.insert_new_symbol(symbol);
+        let Some(interface_name) = identifier_binding_name(&id) else {
+            return;
+        };
+        if let Ok(ty) = self.analyze_ts_interface_declaration(node) {
+            let symbol = Symbol::new(interface_name, ty).declared_at(self.location(id.syntax()));
+            self.insert_new_symbol(Namespace::Type, symbol);
+        }
     }
 
     fn visit_js_variable_declaration_clause(&mut self, node: &JsVariableDeclarationClause) {
@@ -190,25 +544,108 @@ impl Visitor for TypeAnalyzer {
     }
 
     fn visit_js_variable_declarator(&mut self, node: &JsVariableDeclarator) {
-        let id = match node.id() {
-            Ok(node) => match node {
-                AnyJsBindingPattern::AnyJsBinding(node) => match node {
-                    AnyJsBinding::JsIdentifierBinding(bind) => {
-                        bind.name_token().unwrap().text_trimmed().to_string()
-                    }
-                    _ => todo!(),
-                },
-                AnyJsBindingPattern::JsArrayBindingPattern(node) => {
-                    todo!("array binding pattern {:?}", node)
-                }
-                AnyJsBindingPattern::JsObjectBindingPattern(node) => {
-                    todo!("object binding pattern {:?}", node)
-                }
-            },
-            Err(_) => todo!(),
+        let Ok(pattern) = node.id() else {
+            return;
         };
-        let ty = self.analyze_js_variable_declarator(node).unwrap();
-        let symbol = Symbol::new(id.to_string(), ty);
-        self.insert_new_symbol(symbol);
+        if let Some(function) = node
+            .initializer()
+            .and_then(|init| init.expression().ok())
+            .filter(|expr| is_function_like(expr.syntax().kind()))
+        {
+            self.declare_locals(function.syntax());
+        }
+        let ty = self
+            .analyze_js_variable_declarator(node)
+            .unwrap_or_else(|_| self.intern(Type::Unknown));
+        let is_mutable = is_mutable_declarator(node);
+        let flow_ty = self.analyze_js_variable_flow_type(node);
+        let symbols = self
+            .analyze_binding_pattern(&pattern, ty)
+            .unwrap_or_default();
+        for symbol in symbols {
+            let symbol = if is_mutable {
+                Symbol {
+                    is_mutable: true,
+                    flow_ty,
+                    ..symbol
+                }
+            } else {
+                symbol
+            };
+            if let Some(flow_ty) = flow_ty {
+                self.narrow(&symbol, node.syntax(), flow_ty);
+            }
+            self.insert_new_symbol(Namespace::Value, symbol);
+        }
+    }
+}
+
+/// Whether `node` implements the overload signatures declared right before
+/// it, e.g. `function f(a: string): void;`.
+fn is_overload_implementation(node: &JsFunctionDeclaration, name: &str) -> bool {
+    let item = node
+        .syntax()
+        .parent()
+        .filter(|parent| JsExport::can_cast(parent.kind()))
+        .unwrap_or_else(|| node.syntax().clone());
+    let previous = item
+        .prev_sibling()
+        .and_then(|previous| match JsExport::cast_ref(&previous) {
+            Some(export) => export
+                .export_clause()
+                .ok()
+                .map(|clause| clause.into_syntax()),
+            None => Some(previous),
+        });
+    previous
+        .and_then(TsDeclareFunctionDeclaration::cast)
+        .and_then(|signature| signature.id().ok())
+        .and_then(|id| binding_name(&id))
+        .is_some_and(|signature| signature == name)
+}
+
+fn merge_declarations(types: &TypeArena, existing: &Symbol, symbol: Symbol) -> Symbol {
+    let mut declarations = existing.declarations.clone();
+    for location in &symbol.declarations {
+        if !declarations.contains(location) {
+            declarations.push(location.clone());
+        }
+    }
+    let merged = match (&*types.get(existing.ty), &*types.get(symbol.ty)) {
+        (Type::Interface(existing_interface), Type::Interface(interface)) => {
+            // Like tsc, members of later declarations take precedence.
+            let mut merged = interface.clone();
+            merged
+                .properties
+                .extend(existing_interface.properties.iter().cloned());
+            merged
+                .extends
+                .extend(existing_interface.extends.iter().cloned());
+            if merged.type_params.is_empty() {
+                merged.type_params = existing_interface.type_params.clone();
+            }
+            Symbol::new(symbol.name, types.intern(Type::Interface(merged)))
+        }
+        // Functions declared more than once are overloads, in order.
+        (Type::Function(_), Type::Function(_)) if existing.ty != symbol.ty => {
+            let overloads = vec![existing.ty, symbol.ty];
+            Symbol::new(symbol.name, types.intern(Type::Intersection(overloads)))
+        }
+        (Type::Intersection(overloads), Type::Function(_))
+            if overloads
+                .iter()
+                .all(|ty| matches!(*types.get(*ty), Type::Function(_))) =>
+        {
+            let mut overloads = overloads.clone();
+            if !overloads.contains(&symbol.ty) {
+                overloads.push(symbol.ty);
+            }
+            Symbol::new(symbol.name, types.intern(Type::Intersection(overloads)))
+        }
+        _ => symbol,
+    };
+    Symbol {
+        declarations,
+        ..merged
     }
 }
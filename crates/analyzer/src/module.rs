@@ -0,0 +1,485 @@
+use std::{fmt, path::PathBuf};
+
+use biome_js_syntax::{
+    AnyJsBinding, AnyJsCombinedSpecifier, AnyJsExportClause, AnyJsExportNamedSpecifier,
+    AnyJsExpression, AnyJsImportClause, AnyJsModuleItem, AnyJsNamedImportSpecifier,
+    AnyTsExternalModuleDeclarationBody, JsImport, JsLiteralExportName, JsModuleItemList,
+    JsModuleSource, JsNamedImportSpecifiers, JsSyntaxNode, TextRange, TsExternalModuleDeclaration,
+};
+use biome_rowan::{AstNode, SyntaxResult};
+use type_info::{
+    symbol::{Namespace, Symbol},
+    ObjectLiteral, ObjectPropertyType, TsLiteralTypeKind, Type, TypeId,
+};
+
+use visitor::Visitor;
+
+use crate::{binding::binding_name, TypeAnalyzer};
+
+/// An import or re-export that binds nothing: its module isn't found, or
+/// doesn't export the name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnresolvedImport {
+    /// The module specifier, or the imported name.
+    pub range: TextRange,
+    pub specifier: String,
+    /// `None` when the module isn't found.
+    pub name: Option<String>,
+}
+
+/// Worded like tsc's errors.
+impl fmt::Display for UnresolvedImport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.name {
+            None => write!(
+                f,
+                "Cannot find module '{}' or its corresponding type declarations.",
+                self.specifier
+            ),
+            Some(name) if name == "default" => {
+                write!(f, "Module '\"{}\"' has no default export.", self.specifier)
+            }
+            Some(name) => write!(
+                f,
+                "Module '\"{}\"' has no exported member '{}'.",
+                self.specifier, name
+            ),
+        }
+    }
+}
+
+/// A module an import refers to.
+struct ImportedModule {
+    path: PathBuf,
+    specifier: String,
+}
+
+impl TypeAnalyzer {
+    /// Binds the names imported from a module resolved with
+    /// [TypeAnalyzer::add_resolved_module], or else declared with
+    /// `declare module`, to what the module exports. Imports that bind
+    /// nothing are recorded, see [TypeAnalyzer::unresolved_imports].
+    pub fn visit_js_import(&mut self, node: &JsImport) {
+        let Ok(clause) = node.import_clause() else {
+            return;
+        };
+        match clause {
+            AnyJsImportClause::JsImportBareClause(_) => {}
+            AnyJsImportClause::JsImportDefaultClause(clause) => {
+                let Some(module) = self.imported_module(clause.source()) else {
+                    return;
+                };
+                if let Ok(local) = clause
+                    .default_specifier()
+                    .and_then(|specifier| specifier.local_name())
+                {
+                    self.import_default(&module, &local);
+                }
+            }
+            AnyJsImportClause::JsImportNamedClause(clause) => {
+                let Some(module) = self.imported_module(clause.source()) else {
+                    return;
+                };
+                if let Ok(specifiers) = clause.named_specifiers() {
+                    self.import_named_specifiers(&module, &specifiers);
+                }
+            }
+            AnyJsImportClause::JsImportNamespaceClause(clause) => {
+                let Some(module) = self.imported_module(clause.source()) else {
+                    return;
+                };
+                if let Ok(local) = clause
+                    .namespace_specifier()
+                    .and_then(|specifier| specifier.local_name())
+                {
+                    self.import_namespace(&module, &local);
+                }
+            }
+            AnyJsImportClause::JsImportCombinedClause(clause) => {
+                let Some(module) = self.imported_module(clause.source()) else {
+                    return;
+                };
+                if let Ok(local) = clause
+                    .default_specifier()
+                    .and_then(|specifier| specifier.local_name())
+                {
+                    self.import_default(&module, &local);
+                }
+                match clause.specifier() {
+                    Ok(AnyJsCombinedSpecifier::JsNamedImportSpecifiers(specifiers)) => {
+                        self.import_named_specifiers(&module, &specifiers);
+                    }
+                    Ok(AnyJsCombinedSpecifier::JsNamespaceImportSpecifier(specifier)) => {
+                        if let Ok(local) = specifier.local_name() {
+                            self.import_namespace(&module, &local);
+                        }
+                    }
+                    Err(_) => {}
+                }
+            }
+        }
+    }
+
+    /// Records what the current module exports: exported declarations,
+    /// which are visited like local ones, `export default x`, `export = x`,
+    /// renamed exports and re-exports.
+    pub fn visit_js_export_clause(&mut self, node: &AnyJsExportClause) {
+        match node {
+            AnyJsExportClause::AnyJsDeclarationClause(clause) => {
+                let previous = std::mem::replace(&mut self.exporting, true);
+                self.visit_declaration_clause(clause);
+                self.exporting = previous;
+            }
+            AnyJsExportClause::JsExportDefaultExpressionClause(clause) => {
+                if let Ok(expr) = clause.expression() {
+                    self.export_expression("default", &expr);
+                }
+            }
+            AnyJsExportClause::TsExportAssignmentClause(clause) => {
+                // Imported like a default export, as with `esModuleInterop`.
+                if let Ok(expr) = clause.expression() {
+                    self.export_expression("default", &expr);
+                }
+            }
+            AnyJsExportClause::JsExportNamedClause(clause) => {
+                for specifier in clause.specifiers().into_iter().flatten() {
+                    if let AnyJsExportNamedSpecifier::JsExportNamedSpecifier(specifier) = specifier
+                    {
+                        let (Ok(local), Some(exported)) = (
+                            specifier.local_name(),
+                            export_name(specifier.exported_name()),
+                        ) else {
+                            continue;
+                        };
+                        if let Ok(local) = local.value_token() {
+                            self.export_local(local.text_trimmed(), exported);
+                        }
+                    }
+                }
+            }
+            AnyJsExportClause::JsExportFromClause(clause) => {
+                let Some(module) = self.imported_module(clause.source()) else {
+                    return;
+                };
+                match clause.export_as() {
+                    Some(export_as) => {
+                        if let Ok(exported) = export_as.exported_name() {
+                            if let Some(name) = export_name(Ok(exported.clone())) {
+                                let ty = self.namespace_type(&module.path);
+                                let symbol = Symbol::new(name, ty)
+                                    .declared_at(self.location(exported.syntax()));
+                                self.export_symbol(Namespace::Value, symbol);
+                            }
+                        }
+                    }
+                    None => self.reexport_all(&module.path),
+                }
+            }
+            AnyJsExportClause::JsExportNamedFromClause(clause) => {
+                let Some(module) = self.imported_module(clause.source()) else {
+                    return;
+                };
+                for specifier in clause.specifiers().into_iter().flatten() {
+                    let Ok(source_name) = specifier.source_name() else {
+                        continue;
+                    };
+                    let Some(name) = export_name(Ok(source_name.clone())) else {
+                        continue;
+                    };
+                    let exported = specifier
+                        .export_as()
+                        .and_then(|export_as| export_name(export_as.exported_name()))
+                        .unwrap_or_else(|| name.clone());
+                    for (namespace, symbol) in
+                        self.module_exports(&module, &name, source_name.syntax())
+                    {
+                        let symbol = Symbol {
+                            name: exported.clone(),
+                            ..symbol
+                        };
+                        self.export_symbol(namespace, symbol);
+                    }
+                }
+            }
+            // Not modelled yet: default declarations, `export as namespace`, ...
+            _ => {}
+        }
+    }
+
+    /// Registers `declare module "name" { ... }`. Its body gets a scope of its
+    /// own, and repeated declarations of a module are merged. Like in a
+    /// `.d.ts` module, its declarations are exported without `export` unless
+    /// it has export statements.
+    pub fn visit_ts_external_module_declaration(&mut self, node: &TsExternalModuleDeclaration) {
+        let Some(name) = node
+            .source()
+            .ok()
+            .and_then(|source| source.inner_string_text().ok())
+        else {
+            return;
+        };
+        let name = name.text().to_string();
+        self.register_ambient_module(name.clone());
+        // `declare module "foo";` has no body: its imports stay unbound.
+        let Some(AnyTsExternalModuleDeclarationBody::TsModuleBlock(block)) = node.body() else {
+            return;
+        };
+        let previous_path = std::mem::replace(&mut self.current_path, ambient_module_path(&name));
+        let previous_exporting =
+            std::mem::replace(&mut self.exporting, !has_export_statements(&block.items()));
+        for item in block.items() {
+            self.visit_module_item(&item);
+        }
+        self.exporting = previous_exporting;
+        self.current_path = previous_path;
+    }
+
+    pub(crate) fn register_ambient_module(&mut self, name: String) {
+        if let Some(declarations) = &mut self.declarations {
+            declarations.ambient_modules.push(name.clone());
+        }
+        if !self.ambient_modules.contains(&name) {
+            self.ambient_modules.push(name);
+        }
+    }
+
+    /// The ambient module matching `specifier`. Like tsc, an exact name wins
+    /// over patterns, and the pattern with the longest prefix before `*`
+    /// over other patterns.
+    pub fn ambient_module(&self, specifier: &str) -> Option<PathBuf> {
+        if self.ambient_modules.iter().any(|name| name == specifier) {
+            return Some(ambient_module_path(specifier));
+        }
+        self.ambient_modules
+            .iter()
+            .filter_map(|pattern| {
+                let (prefix, suffix) = pattern.split_once('*')?;
+                let matches = specifier.len() >= prefix.len() + suffix.len()
+                    && specifier.starts_with(prefix)
+                    && specifier.ends_with(suffix);
+                matches.then_some((prefix.len(), pattern))
+            })
+            .max_by_key(|(len, _)| *len)
+            .map(|(_, pattern)| ambient_module_path(pattern))
+    }
+
+    /// Files found by the resolver take precedence over ambient modules. The
+    /// ambient module is recorded as resolved so that its types are visible
+    /// from the importing file. A module that isn't found is recorded.
+    fn imported_module(&mut self, source: SyntaxResult<JsModuleSource>) -> Option<ImportedModule> {
+        let source = source.ok()?;
+        let specifier = source.inner_string_text().ok()?.text().to_string();
+        if let Some(resolved) = self
+            .resolved_modules
+            .get(&self.current_path)
+            .and_then(|modules| modules.get(&specifier))
+        {
+            return Some(ImportedModule {
+                path: resolved.clone(),
+                specifier,
+            });
+        }
+        let Some(path) = self.ambient_module(&specifier) else {
+            self.unresolved_imports.push(UnresolvedImport {
+                range: source.syntax().text_trimmed_range(),
+                specifier,
+                name: None,
+            });
+            return None;
+        };
+        self.add_resolved_module(self.current_path.clone(), specifier.clone(), path.clone());
+        Some(ImportedModule { path, specifier })
+    }
+
+    fn import_default(&mut self, module: &ImportedModule, local: &AnyJsBinding) {
+        if let Some(local_name) = binding_name(local) {
+            self.import_symbol(module, "default", local_name, local.syntax());
+        }
+    }
+
+    fn import_named_specifiers(
+        &mut self,
+        module: &ImportedModule,
+        specifiers: &JsNamedImportSpecifiers,
+    ) {
+        for specifier in specifiers.specifiers().into_iter().flatten() {
+            match specifier {
+                AnyJsNamedImportSpecifier::JsShorthandNamedImportSpecifier(specifier) => {
+                    let Ok(local) = specifier.local_name() else {
+                        continue;
+                    };
+                    if let Some(name) = binding_name(&local) {
+                        self.import_symbol(module, &name, name.clone(), local.syntax());
+                    }
+                }
+                AnyJsNamedImportSpecifier::JsNamedImportSpecifier(specifier) => {
+                    let Ok(imported) = specifier.name() else {
+                        continue;
+                    };
+                    let Some(name) = export_name(Ok(imported.clone())) else {
+                        continue;
+                    };
+                    if let Some(local) = specifier.local_name().ok().as_ref().and_then(binding_name)
+                    {
+                        self.import_symbol(module, &name, local, imported.syntax());
+                    }
+                }
+                AnyJsNamedImportSpecifier::JsBogusNamedImportSpecifier(_) => {}
+            }
+        }
+    }
+
+    /// `import * as ns` binds an object with the module's exported values.
+    fn import_namespace(&mut self, module: &ImportedModule, local: &AnyJsBinding) {
+        if let Some(name) = binding_name(local) {
+            let ty = self.namespace_type(&module.path);
+            let symbol = Symbol::new(name, ty).declared_at(self.location(local.syntax()));
+            self.insert_new_symbol(Namespace::Value, symbol);
+        }
+    }
+
+    fn namespace_type(&self, module: &PathBuf) -> TypeId {
+        let properties = self
+            .symbol_table
+            .symbols(module)
+            .into_iter()
+            .flat_map(|symbols| symbols.exports())
+            .filter(|(namespace, _)| *namespace == Namespace::Value)
+            .map(|(_, symbol)| ObjectPropertyType {
+                name: symbol.name.clone(),
+                type_info: symbol.ty,
+            })
+            .collect();
+        self.intern(Type::Literal(TsLiteralTypeKind::Object(ObjectLiteral {
+            properties,
+        })))
+    }
+
+    /// `export * from "module"` exports what the module does, except its
+    /// default export.
+    fn reexport_all(&mut self, module: &PathBuf) {
+        let symbols: Vec<_> = self
+            .symbol_table
+            .symbols(module)
+            .into_iter()
+            .flat_map(|symbols| symbols.exports())
+            .filter(|(_, symbol)| symbol.name != "default")
+            .map(|(namespace, symbol)| (namespace, symbol.clone()))
+            .collect();
+        for (namespace, symbol) in symbols {
+            self.export_symbol(namespace, symbol);
+        }
+    }
+
+    fn export_expression(&mut self, name: &str, expr: &AnyJsExpression) {
+        if let AnyJsExpression::JsIdentifierExpression(ident) = expr {
+            if let Ok(local) = ident.name().and_then(|name| name.value_token()) {
+                if self.export_local(local.text_trimmed(), name.to_string()) {
+                    return;
+                }
+            }
+        }
+        let ty = self.analyze_expression(expr);
+        let symbol = Symbol::new(name.to_string(), ty).declared_at(self.location(expr.syntax()));
+        self.export_symbol(Namespace::Value, symbol);
+    }
+
+    /// Exports both meanings of the name `local` of the current scope as
+    /// `exported`. Returns whether the name is declared.
+    fn export_local(&mut self, local: &str, exported: String) -> bool {
+        let symbols: Vec<_> = [Namespace::Value, Namespace::Type]
+            .into_iter()
+            .filter_map(|namespace| {
+                let symbol = self
+                    .symbol_table
+                    .get(&self.current_path, namespace, local)?;
+                Some((namespace, symbol.clone()))
+            })
+            .collect();
+        let found = !symbols.is_empty();
+        for (namespace, symbol) in symbols {
+            let symbol = Symbol {
+                name: exported.clone(),
+                ..symbol
+            };
+            self.export_symbol(namespace, symbol);
+        }
+        found
+    }
+
+    /// Declares `local` in the current file for both meanings of the export
+    /// `name` of `module`.
+    fn import_symbol(
+        &mut self,
+        module: &ImportedModule,
+        name: &str,
+        local: String,
+        node: &JsSyntaxNode,
+    ) {
+        for (namespace, symbol) in self.module_exports(module, name, node) {
+            let symbol = Symbol {
+                name: local.clone(),
+                ..symbol
+            };
+            self.insert_new_symbol(namespace, symbol);
+        }
+    }
+
+    /// Both meanings of the export `name` of `module`. If it has none, the
+    /// import at `node` is recorded, unless nothing is known about the
+    /// module, as for `declare module "name";` or files that weren't
+    /// analyzed.
+    fn module_exports(
+        &mut self,
+        module: &ImportedModule,
+        name: &str,
+        node: &JsSyntaxNode,
+    ) -> Vec<(Namespace, Symbol)> {
+        let Some(symbols) = self.symbol_table.symbols(&module.path) else {
+            return vec![];
+        };
+        let exports: Vec<_> = [Namespace::Value, Namespace::Type]
+            .into_iter()
+            .filter_map(|namespace| Some((namespace, symbols.get_export(namespace, name)?.clone())))
+            .collect();
+        if exports.is_empty() {
+            self.unresolved_imports.push(UnresolvedImport {
+                range: node.text_trimmed_range(),
+                specifier: module.specifier.clone(),
+                name: Some(name.to_string()),
+            });
+        }
+        exports
+    }
+}
+
+/// The scope of the body of `declare module "name"`. Quotes keep it apart
+/// from file paths.
+pub(crate) fn ambient_module_path(name: &str) -> PathBuf {
+    PathBuf::from(format!("\"{}\"", name))
+}
+
+/// Whether `items` has `export { ... }`, `export * from`, `export default x`
+/// or `export =`, which make a `.d.ts` file or an ambient module export only
+/// what is marked with `export`.
+pub(crate) fn has_export_statements(items: &JsModuleItemList) -> bool {
+    items.iter().any(|item| {
+        let AnyJsModuleItem::JsExport(export) = item else {
+            return false;
+        };
+        matches!(
+            export.export_clause(),
+            Ok(AnyJsExportClause::JsExportNamedClause(_)
+                | AnyJsExportClause::JsExportFromClause(_)
+                | AnyJsExportClause::JsExportNamedFromClause(_)
+                | AnyJsExportClause::JsExportDefaultExpressionClause(_)
+                | AnyJsExportClause::TsExportAssignmentClause(_))
+        )
+    })
+}
+
+fn export_name(name: SyntaxResult<JsLiteralExportName>) -> Option<String> {
+    let token = name.ok()?.value().ok()?;
+    let text = token.text_trimmed();
+    Some(text.trim_matches(['"', '\'']).to_string())
+}
@@ -0,0 +1,202 @@
+use biome_js_syntax::*;
+use biome_rowan::AstNode;
+use type_info::{Type, TypeId};
+
+use crate::TypeAnalyzer;
+
+const BUILTIN_PROMISE: &str = "Promise";
+
+/// An expression statement whose Promise is neither awaited nor handled.
+pub struct FloatingPromise {
+    /// The expression of the statement, where `await` or `void` would go.
+    pub expression: AnyJsExpression,
+    /// Whether it ends with a `.then` or `.catch` call given something other
+    /// than a rejection handler.
+    pub non_function_handler: bool,
+    /// The arguments of that call.
+    pub handler_arguments: Option<TextRange>,
+    /// Whether the statement is in an async function, so that it can be
+    /// awaited.
+    pub can_await: bool,
+    /// Whether the Promise is passed to `void`, which rules may take as
+    /// deliberately not awaiting it.
+    pub is_void: bool,
+}
+
+impl TypeAnalyzer {
+    /// Checks `expr_stmt`, which is in the file of the current scope. How
+    /// the Promise is reported is left to the rules.
+    pub fn find_floating_promise(
+        &self,
+        expr_stmt: &JsExpressionStatement,
+    ) -> Option<FloatingPromise> {
+        let expr = expr_stmt.expression().ok()?;
+        let (expr, is_void) = match &expr {
+            AnyJsExpression::JsUnaryExpression(unary_expr)
+                if unary_expr
+                    .operator()
+                    .map_or(false, |op| op == JsUnaryOperator::Void) =>
+            {
+                (unary_expr.argument().ok()?, true)
+            }
+            _ => (expr.clone(), false),
+        };
+        let (is_unhandled, non_function_handler) = is_unhandled_promise(self, &expr);
+        if !is_unhandled && !non_function_handler {
+            return None;
+        }
+        let handler_arguments = match &expr {
+            AnyJsExpression::JsCallExpression(call_expr) if non_function_handler => call_expr
+                .arguments()
+                .ok()
+                .map(|arguments| arguments.syntax().text_trimmed_range()),
+            _ => None,
+        };
+        Some(FloatingPromise {
+            expression: expr,
+            non_function_handler,
+            handler_arguments,
+            can_await: is_in_async_function(expr_stmt.syntax()),
+            is_void,
+        })
+    }
+}
+
+fn is_unhandled_promise(types: &TypeAnalyzer, expr: &AnyJsExpression) -> (bool, bool) {
+    match expr {
+        AnyJsExpression::JsCallExpression(call_expr) => {
+            if let Ok(AnyJsExpression::JsStaticMemberExpression(member_expr)) = call_expr.callee() {
+                if let (Ok(object), Ok(prop)) = (member_expr.object(), member_expr.member()) {
+                    let method_name = prop.text();
+                    let Ok(arguments) = call_expr.arguments() else {
+                        return (false, false);
+                    };
+
+                    if method_name == "catch" {
+                        if let Some(Ok(AnyJsCallArgument::AnyJsExpression(
+                            catch_rejection_handler,
+                        ))) = arguments.args().into_iter().next()
+                        {
+                            let is_valid =
+                                is_valid_rejection_handler(types, &catch_rejection_handler);
+                            return (!is_valid, !is_valid);
+                        }
+                    }
+
+                    if method_name == "then" {
+                        let mut args = arguments.args().into_iter();
+                        let is_first_arg_valid = args
+                            .next()
+                            .map(|arg| {
+                                if let Ok(AnyJsCallArgument::AnyJsExpression(expr)) = arg {
+                                    is_valid_rejection_handler(types, &expr)
+                                } else {
+                                    false
+                                }
+                            })
+                            .unwrap_or(true);
+
+                        let is_second_arg_valid = args
+                            .next()
+                            .map(|arg| {
+                                if let Ok(AnyJsCallArgument::AnyJsExpression(expr)) = arg {
+                                    is_valid_rejection_handler(types, &expr)
+                                } else {
+                                    false
+                                }
+                            })
+                            .unwrap_or(true);
+
+                        if !is_first_arg_valid || !is_second_arg_valid {
+                            return (true, true);
+                        }
+                    }
+
+                    let object_type = types.analyze_expression(&object);
+                    if is_promise_type(types, object_type) {
+                        return (true, false);
+                    }
+                }
+            }
+            let type_info = types.analyze_expression(expr);
+            (is_promise_type(types, type_info), false)
+        }
+        AnyJsExpression::JsAwaitExpression(_) => (false, false),
+        AnyJsExpression::JsUnaryExpression(unary_expr) => {
+            if let Ok(op) = unary_expr.operator() {
+                if op == JsUnaryOperator::Void {
+                    return (false, false);
+                }
+            }
+            let type_info = types.analyze_expression(expr);
+            (is_promise_type(types, type_info), false)
+        }
+        _ => {
+            let type_info = types.analyze_expression(expr);
+            (is_promise_type(types, type_info), false)
+        }
+    }
+}
+
+fn is_valid_rejection_handler(types: &TypeAnalyzer, handler: &AnyJsExpression) -> bool {
+    match handler {
+        AnyJsExpression::JsArrowFunctionExpression(_)
+        | AnyJsExpression::JsFunctionExpression(_)
+        | AnyJsExpression::AnyJsLiteralExpression(
+            AnyJsLiteralExpression::JsNullLiteralExpression(_),
+        ) => true,
+        AnyJsExpression::JsIdentifierExpression(ident)
+            if ident.name().is_ok_and(|name| name.text() == "undefined") =>
+        {
+            true
+        }
+        // Parameters and locals are typed where the handler is passed.
+        _ => is_call_signature(types, types.analyze_expression(handler)),
+    }
+}
+
+/// Whether `await` can be used where `node` is: in an async function and
+/// not in a nested function.
+fn is_in_async_function(node: &JsSyntaxNode) -> bool {
+    for ancestor in node.ancestors().skip(1) {
+        if let Some(func) = JsFunctionDeclaration::cast_ref(&ancestor) {
+            return func.async_token().is_some();
+        }
+        if let Some(func) = JsFunctionExpression::cast_ref(&ancestor) {
+            return func.async_token().is_some();
+        }
+        if let Some(func) = JsArrowFunctionExpression::cast_ref(&ancestor) {
+            return func.async_token().is_some();
+        }
+        if let Some(method) = JsMethodClassMember::cast_ref(&ancestor) {
+            return method.async_token().is_some();
+        }
+        if let Some(method) = JsMethodObjectMember::cast_ref(&ancestor) {
+            return method.async_token().is_some();
+        }
+    }
+    false
+}
+
+fn is_call_signature(types: &TypeAnalyzer, type_info: TypeId) -> bool {
+    match &*types.get_type(type_info) {
+        Type::Function(_) => true,
+        Type::Union(members) => members.iter().all(|ty| is_call_signature(types, *ty)),
+        // Overloads.
+        Type::Intersection(members) => members.iter().any(|ty| is_call_signature(types, *ty)),
+        Type::TypeRef(_) => {
+            let resolved = types.resolve_reference(type_info, &types.current_path);
+            resolved != type_info && is_call_signature(types, resolved)
+        }
+        _ => false,
+    }
+}
+
+fn is_promise_type(types: &TypeAnalyzer, type_info: TypeId) -> bool {
+    match &*types.get_type(type_info) {
+        Type::Interface(interface) if interface.name == BUILTIN_PROMISE => true,
+        Type::TypeRef(type_ref) if type_ref.name == BUILTIN_PROMISE => true,
+        Type::Union(members) => members.iter().any(|ty| is_promise_type(types, *ty)),
+        _ => false,
+    }
+}
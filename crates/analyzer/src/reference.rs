@@ -0,0 +1,55 @@
+use biome_js_semantic::{semantic_model, SemanticModelOptions};
+use biome_js_syntax::{AnyJsRoot, JsReferenceIdentifier, JsSyntaxKind, TextRange};
+use biome_rowan::AstNode;
+use type_info::symbol::{Namespace, Symbol};
+
+use crate::TypeAnalyzer;
+
+/// A name in a file referring to a symbol of the analyzer.
+#[derive(Debug, Clone)]
+pub struct Reference {
+    pub range: TextRange,
+    pub symbol: Symbol,
+}
+
+impl TypeAnalyzer {
+    /// The references of `root`, the file of the current scope, to its
+    /// top-level declarations, its imports and globals. Names bound inside
+    /// functions or blocks aren't symbols, so references to them are left
+    /// out.
+    pub fn references(&self, root: &AnyJsRoot) -> Vec<Reference> {
+        let model = semantic_model(root, SemanticModelOptions::default());
+        root.syntax()
+            .descendants()
+            .filter_map(JsReferenceIdentifier::cast)
+            .filter(|reference| {
+                model
+                    .binding(reference)
+                    .map_or(true, |binding| binding.scope().parent().is_none())
+            })
+            .filter_map(|reference| {
+                let name = reference.value_token().ok()?;
+                let symbol = self.lookup_reference(&reference, name.text_trimmed())?;
+                Some(Reference {
+                    range: reference.syntax().text_trimmed_range(),
+                    symbol: symbol.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// Names in type references are looked up as types first.
+    fn lookup_reference(&self, reference: &JsReferenceIdentifier, name: &str) -> Option<&Symbol> {
+        let is_type = reference
+            .syntax()
+            .parent()
+            .is_some_and(|parent| parent.kind() == JsSyntaxKind::TS_REFERENCE_TYPE);
+        let value = || self.lookup_value(name);
+        let ty = || self.lookup_in_scope(&self.current_path, Namespace::Type, name);
+        if is_type {
+            ty().or_else(value)
+        } else {
+            value().or_else(ty)
+        }
+    }
+}
@@ -1,184 +1,571 @@
-use std::path::PathBuf;
+use std::{cell::RefCell, path::PathBuf};
 
-use rustc_hash::FxHashMap;
-use type_info::{symbol::Symbol, *};
+use rustc_hash::{FxHashMap, FxHashSet};
+use type_info::{
+    symbol::{Namespace, Symbol},
+    *,
+};
 
 use crate::TypeAnalyzer;
 
+thread_local! {
+    /// The references [TypeAnalyzer::resolve_reference] is resolving on this
+    /// thread. They are kept out of the shared memo, so that other threads
+    /// don't see a reference as circular while it's being resolved.
+    static RESOLVING: RefCell<FxHashSet<(PathBuf, TypeId)>> = RefCell::default();
+}
+
 impl TypeAnalyzer {
-    pub fn resolve_type_info(&self, symbol: &Symbol, path: &PathBuf) -> Type {
-        match &symbol.ty {
-            Type::Interface(interface) => {
-                let mut resolved_interface = interface.clone();
+    /// Resolves the references in the declaration of `symbol` one level deep.
+    /// The members of the types they resolve to are only resolved when
+    /// accessed, see [TypeAnalyzer::resolve_member_type].
+    pub fn resolve_type_info(&self, symbol: &Symbol, path: &PathBuf) -> TypeId {
+        match &*self.get_type(symbol.ty) {
+            Type::Interface(_) | Type::Function(_) => self
+                .types
+                .map(symbol.ty, |ty| self.resolve_type_info_inner(ty, path)),
+            _ => symbol.ty,
+        }
+    }
 
-                for type_param in &mut resolved_interface.type_params {
-                    if let Some(constraint) = &type_param.constraint {
-                        type_param.constraint =
-                            Some(self.resolve_type_info_inner(constraint, path));
-                    }
-                    if let Some(default) = &type_param.default {
-                        type_param.default = Some(self.resolve_type_info_inner(default, path));
-                    }
-                }
+    /// Looks up `member` on `object`, replacing the polymorphic `this` type
+    /// with the receiver the member was accessed through.
+    pub fn resolve_member_type(&self, object: TypeId, member: &str, path: &PathBuf) -> TypeId {
+        self.lookup_member(object, member, object, path, &mut vec![])
+    }
 
-                for property in &mut resolved_interface.properties {
-                    property.type_info = self.resolve_type_info_inner(&property.type_info, path);
+    /// `visiting` holds the references being expanded. Reaching one of them
+    /// again, e.g. through `type A = B; type B = A;`, gives [Type::Circular].
+    fn lookup_member(
+        &self,
+        ty: TypeId,
+        member: &str,
+        receiver: TypeId,
+        path: &PathBuf,
+        visiting: &mut Vec<TypeId>,
+    ) -> TypeId {
+        let is_reference = match &*self.get_type(ty) {
+            Type::TypeRef(_) => true,
+            deferred if deferred.is_deferred() => false,
+            _ => return self.lookup_resolved_member(ty, member, receiver, path, visiting),
+        };
+        if visiting.contains(&ty) {
+            return self.intern(Type::Circular);
+        }
+        visiting.push(ty);
+        let resolved = if is_reference {
+            self.resolve_reference(ty, path)
+        } else {
+            self.evaluate_type(ty, path)
+        };
+        // An alias resolves to another reference, e.g. `type P = Promise<string>`.
+        // Deferred types that can't be computed have no members.
+        let member_ty = if resolved == ty {
+            self.intern(Type::Unknown)
+        } else {
+            self.lookup_member(resolved, member, receiver, path, visiting)
+        };
+        visiting.pop();
+        member_ty
+    }
+
+    fn lookup_resolved_member(
+        &self,
+        ty: TypeId,
+        member: &str,
+        receiver: TypeId,
+        path: &PathBuf,
+        visiting: &mut Vec<TypeId>,
+    ) -> TypeId {
+        match &*self.get_type(ty) {
+            Type::Interface(interface) => {
+                let mut props = interface
+                    .properties
+                    .iter()
+                    .filter(|prop| prop.name == member);
+                match props.next() {
+                    // Methods declared more than once are overloads, kept in
+                    // order as an intersection of their signatures.
+                    Some(prop) if matches!(*self.get_type(prop.type_info), Type::Function(_)) => {
+                        let mut overloads = vec![prop.type_info];
+                        overloads.extend(
+                            props
+                                .map(|prop| prop.type_info)
+                                .filter(|ty| matches!(*self.get_type(*ty), Type::Function(_))),
+                        );
+                        let ty = match overloads[..] {
+                            [ty] => ty,
+                            _ => self.intern(Type::Intersection(overloads)),
+                        };
+                        substitute_this(&self.types, ty, receiver)
+                    }
+                    Some(prop) => substitute_this(&self.types, prop.type_info, receiver),
+                    None => interface
+                        .extends
+                        .iter()
+                        .map(|ext| self.lookup_member(*ext, member, receiver, path, visiting))
+                        .find(|ty| !matches!(*self.get_type(*ty), Type::Unknown))
+                        .unwrap_or_else(|| self.intern(Type::Unknown)),
                 }
+            }
+            Type::Literal(TsLiteralTypeKind::Object(obj)) => obj
+                .properties
+                .iter()
+                .find(|prop| prop.name == member)
+                .map_or_else(|| self.intern(Type::Unknown), |prop| prop.type_info),
+            Type::Union(types) => {
+                let types = types
+                    .iter()
+                    .map(|t| self.lookup_member(*t, member, *t, path, visiting))
+                    .collect();
+                self.intern(Type::Union(types))
+            }
+            _ => self.intern(Type::Unknown),
+        }
+    }
 
-                resolved_interface.extends = resolved_interface
-                    .extends
+    /// Type of the element at `index` when destructuring an array or tuple.
+    pub fn resolve_element_type(&self, ty: TypeId, index: usize) -> TypeId {
+        match &*self.get_type(ty) {
+            Type::Tuple(elements) => match elements.iter().position(|element| element.is_rest) {
+                // Elements from a rest element on come from its array type.
+                Some(rest) if index >= rest => self.resolve_element_type(elements[rest].ty, 0),
+                _ => match elements.get(index) {
+                    Some(element) if element.is_optional => self.intern(Type::Union(vec![
+                        element.ty,
+                        self.intern(Type::KeywordType(TsKeywordTypeKind::Undefined)),
+                    ])),
+                    Some(element) => element.ty,
+                    None => self.intern(Type::KeywordType(TsKeywordTypeKind::Undefined)),
+                },
+            },
+            Type::TypeRef(type_ref) if is_array_name(&type_ref.name) => type_ref
+                .type_params
+                .first()
+                .copied()
+                .unwrap_or_else(|| self.intern(Type::Unknown)),
+            Type::Union(types) => {
+                let types = types
                     .iter()
-                    .map(|ext| self.resolve_type_info_inner(ext, path))
+                    .map(|t| self.resolve_element_type(*t, index))
                     .collect();
+                self.intern(Type::Union(types))
+            }
+            _ => self.intern(Type::Unknown),
+        }
+    }
 
-                Type::Interface(resolved_interface)
+    /// Type of a `...rest` element starting at `index` in an array pattern.
+    pub fn resolve_array_rest_type(&self, ty: TypeId, index: usize) -> TypeId {
+        match &*self.get_type(ty) {
+            Type::Tuple(elements) => {
+                // A rest element is kept whatever the index, since it may be empty.
+                let skipped = elements
+                    .iter()
+                    .position(|element| element.is_rest)
+                    .map_or(index, |rest| rest.min(index));
+                self.intern(Type::Tuple(
+                    elements[skipped.min(elements.len())..].to_vec(),
+                ))
             }
-            Type::Function(func) => {
-                let mut resolved_func = func.clone();
-                resolved_func.params = resolved_func
-                    .params
-                    .into_iter()
-                    .map(|param| FunctionParam {
-                        name: param.name,
-                        param_type: self.resolve_type_info_inner(&param.param_type, path),
-                        is_optional: param.is_optional,
-                    })
+            Type::TypeRef(type_ref) if is_array_name(&type_ref.name) => ty,
+            Type::Union(types) => {
+                let types = types
+                    .iter()
+                    .map(|t| self.resolve_array_rest_type(*t, index))
                     .collect();
+                self.intern(Type::Union(types))
+            }
+            _ => self.intern(Type::Unknown),
+        }
+    }
+
+    /// Type of a `...rest` binding in an object pattern: the remaining properties.
+    pub fn resolve_object_rest_type(
+        &self,
+        ty: TypeId,
+        excluded: &[String],
+        path: &PathBuf,
+    ) -> TypeId {
+        let resolved = self.resolve_type_info_inner(ty, path);
+        let properties = match &*self.get_type(resolved) {
+            Type::Interface(interface) => interface
+                .properties
+                .iter()
+                .filter(|prop| !excluded.contains(&prop.name))
+                .map(|prop| ObjectPropertyType {
+                    name: prop.name.clone(),
+                    type_info: prop.type_info,
+                })
+                .collect(),
+            Type::Literal(TsLiteralTypeKind::Object(obj)) => obj
+                .properties
+                .iter()
+                .filter(|prop| !excluded.contains(&prop.name))
+                .cloned()
+                .collect(),
+            _ => return self.intern(Type::Unknown),
+        };
+        self.intern(Type::Literal(TsLiteralTypeKind::Object(ObjectLiteral {
+            properties,
+        })))
+    }
+
+    fn resolve_type_info_inner(&self, ty: TypeId, path: &PathBuf) -> TypeId {
+        match &*self.get_type(ty) {
+            Type::TypeRef(_) => self.resolve_reference(ty, path),
+            deferred if deferred.is_deferred() => self.evaluate_type(ty, path),
+            Type::Union(_) | Type::Intersection(_) | Type::Function(_) => self
+                .types
+                .map(ty, |child| self.resolve_type_info_inner(child, path)),
+            _ => ty,
+        }
+    }
 
-                resolved_func.return_type =
-                    Box::new(self.resolve_type_info_inner(&func.return_type, path));
+    /// Resolves the reference `ty` one step, to the instantiated interface or
+    /// alias target it names. Other types are returned as they are. Results
+    /// are memoized per scope until the symbol table changes.
+    pub fn resolve_reference(&self, ty: TypeId, path: &PathBuf) -> TypeId {
+        let Type::TypeRef(type_ref) = &*self.get_type(ty) else {
+            return ty;
+        };
+        let key = (path.clone(), ty);
+        if let Some(id) = self.resolved_refs.read().unwrap().get(&key) {
+            return *id;
+        }
+        let symbol = self
+            .lookup_in_scope(path, Namespace::Type, &type_ref.name)
+            .or_else(|| {
+                self.builtin_symbol_table
+                    .get(Namespace::Type, &type_ref.name)
+            });
+        let Some(generic) = symbol.map(|symbol| symbol.ty) else {
+            let unknown = self.intern(Type::Unknown);
+            self.resolved_refs.write().unwrap().insert(key, unknown);
+            return unknown;
+        };
+        // Evaluating `type A = { [P in keyof A]: ... }` refers back to `A`.
+        if !RESOLVING.with(|resolving| resolving.borrow_mut().insert(key.clone())) {
+            return self.intern(Type::Circular);
+        }
+        let instantiated = self.apply_type_arguments(generic, &type_ref.type_params);
+        let resolved = self.evaluate_type(instantiated, path);
+        RESOLVING.with(|resolving| resolving.borrow_mut().remove(&key));
+        self.resolved_refs.write().unwrap().insert(key, resolved);
+        resolved
+    }
 
-                Type::Function(resolved_func)
+    /// Computes a deferred type, see [Type::is_deferred], in the scope of
+    /// `path`. Types that aren't deferred, or whose operands don't resolve to
+    /// something the analyzer models, are returned as they are.
+    pub fn evaluate_type(&self, ty: TypeId, path: &PathBuf) -> TypeId {
+        match &*self.get_type(ty) {
+            Type::KeyOf(object) => self.resolve_keys(*object, path).unwrap_or(ty),
+            Type::IndexedAccess(access) => self
+                .resolve_indexed_access(access.object, access.index, path)
+                .unwrap_or(ty),
+            Type::Mapped(mapped) => self.resolve_mapped_type(mapped, path).unwrap_or(ty),
+            Type::TypeQuery(name) => self.resolve_type_query(name, path).unwrap_or(ty),
+            _ => ty,
+        }
+    }
+
+    /// Resolves references and deferred types until `ty` is neither.
+    fn resolve_fully(&self, ty: TypeId, path: &PathBuf) -> TypeId {
+        let mut visiting = vec![];
+        let mut ty = ty;
+        while !visiting.contains(&ty) {
+            visiting.push(ty);
+            let next = match &*self.get_type(ty) {
+                Type::TypeRef(_) => self.resolve_reference(ty, path),
+                _ => self.evaluate_type(ty, path),
+            };
+            if next == ty {
+                return ty;
             }
-            _ => symbol.ty.clone(),
+            ty = next;
         }
+        self.intern(Type::Circular)
     }
 
-    fn resolve_type_info_inner(&self, ty: &Type, path: &PathBuf) -> Type {
-        match ty {
-            Type::TypeRef(type_ref) => {
-                if let Some(symbol) = self.symbol_table.get(path, &type_ref.name) {
-                    let ty = self.apply_type_arguments(&symbol.ty, &type_ref.type_params);
-                    return ty;
-                }
-                if let Some(referred_symbol) = self.builtin_symbol_table.get(&type_ref.name) {
-                    let mut resolved_type = self.resolve_type_info(referred_symbol, path);
+    /// `keyof object`: the union of the names of its properties.
+    fn resolve_keys(&self, object: TypeId, path: &PathBuf) -> Option<TypeId> {
+        let object = self.resolve_fully(object, path);
+        let names = match &*self.get_type(object) {
+            // `keyof any`, e.g. the constraint of `Record`.
+            Type::KeywordType(TsKeywordTypeKind::Any) => {
+                return Some(self.intern(Type::Union(vec![
+                    self.intern(Type::KeywordType(TsKeywordTypeKind::String)),
+                    self.intern(Type::KeywordType(TsKeywordTypeKind::Number)),
+                    self.intern(Type::KeywordType(TsKeywordTypeKind::Symbol)),
+                ])));
+            }
+            Type::Interface(_) | Type::Literal(TsLiteralTypeKind::Object(_)) => {
+                self.property_names(object, path)
+            }
+            _ => return None,
+        };
+        let keys = names
+            .into_iter()
+            .map(|name| self.intern(Type::Literal(TsLiteralTypeKind::String(name))))
+            .collect::<Vec<_>>();
+        Some(match keys.as_slice() {
+            [] => self.intern(Type::KeywordType(TsKeywordTypeKind::Never)),
+            [key] => *key,
+            _ => self.intern(Type::Union(keys)),
+        })
+    }
 
-                    if !type_ref.type_params.is_empty() {
-                        resolved_type =
-                            self.apply_type_arguments(&resolved_type, &type_ref.type_params);
+    /// The names of the properties of an object type, inherited ones after
+    /// its own.
+    fn property_names(&self, object: TypeId, path: &PathBuf) -> Vec<String> {
+        let mut names = vec![];
+        match &*self.get_type(object) {
+            Type::Interface(interface) => {
+                names.extend(interface.properties.iter().map(|prop| prop.name.clone()));
+                for ext in &interface.extends {
+                    let ext = self.resolve_fully(*ext, path);
+                    for name in self.property_names(ext, path) {
+                        if !names.contains(&name) {
+                            names.push(name);
+                        }
                     }
-
-                    resolved_type
-                } else {
-                    Type::Unknown
                 }
             }
-            Type::Union(types) => Type::Union(
-                types
-                    .iter()
-                    .map(|t| self.resolve_type_info_inner(t, path))
-                    .collect(),
-            ),
-            Type::Intersection(types) => Type::Intersection(
-                types
-                    .iter()
-                    .map(|t| self.resolve_type_info_inner(t, path))
-                    .collect(),
-            ),
-            Type::Function(func) => {
-                let mut resolved_func = func.clone();
-                resolved_func.params = resolved_func
-                    .params
-                    .into_iter()
-                    .map(|param| FunctionParam {
-                        name: param.name,
-                        param_type: self.resolve_type_info_inner(&param.param_type, path),
-                        is_optional: param.is_optional,
-                    })
-                    .collect();
-                resolved_func.return_type =
-                    Box::new(self.resolve_type_info_inner(&func.return_type, path));
-                Type::Function(resolved_func)
+            Type::Literal(TsLiteralTypeKind::Object(obj)) => {
+                names.extend(obj.properties.iter().map(|prop| prop.name.clone()));
             }
-            _ => ty.clone(),
+            _ => {}
         }
+        names
     }
 
-    fn apply_type_arguments(&self, base_type: &Type, type_args: &[Type]) -> Type {
-        match base_type {
-            Type::Interface(interface) => {
-                if interface.type_params.len() != type_args.len() {
-                    return Type::Unknown;
+    /// `object[index]`, for literal indexes, unions of them and `number` on
+    /// arrays and tuples.
+    fn resolve_indexed_access(
+        &self,
+        object: TypeId,
+        index: TypeId,
+        path: &PathBuf,
+    ) -> Option<TypeId> {
+        let index = self.resolve_fully(index, path);
+        let ty = match &*self.get_type(index) {
+            Type::Literal(TsLiteralTypeKind::String(key)) => {
+                let member = self.resolve_member_type(object, literal_key(key), path);
+                if matches!(*self.get_type(member), Type::Unknown) {
+                    return None;
                 }
+                member
+            }
+            Type::Literal(TsLiteralTypeKind::Number(position)) => {
+                let position = usize::try_from(*position).ok()?;
+                self.resolve_element_type(self.resolve_fully(object, path), position)
+            }
+            Type::KeywordType(TsKeywordTypeKind::Number) => {
+                self.resolve_element_type(self.resolve_fully(object, path), 0)
+            }
+            Type::Union(indexes) => {
+                let types = indexes
+                    .iter()
+                    .map(|index| self.resolve_indexed_access(object, *index, path))
+                    .collect::<Option<Vec<_>>>()?;
+                self.intern(Type::Union(types))
+            }
+            _ => return None,
+        };
+        Some(ty)
+    }
 
-                let mut type_map = FxHashMap::default();
-                for (param, arg) in interface.type_params.iter().zip(type_args.iter()) {
-                    type_map.insert(param.name.clone(), arg.clone());
-                }
-                let resolved_properties = interface
+    /// A mapped type over known keys, as an object type.
+    fn resolve_mapped_type(&self, mapped: &MappedType, path: &PathBuf) -> Option<TypeId> {
+        let keys = self.resolve_fully(mapped.keys, path);
+        let keys = match &*self.get_type(keys) {
+            Type::Union(keys) => keys.clone(),
+            Type::KeywordType(TsKeywordTypeKind::Never) => vec![],
+            _ => vec![keys],
+        };
+        // `{ [P in keyof T]: ... }` keeps the modifiers of the properties of `T`.
+        let source = match &*self.get_type(mapped.keys) {
+            Type::KeyOf(source) => Some(self.resolve_fully(*source, path)),
+            _ => None,
+        };
+        let mut properties = vec![];
+        for key in keys {
+            let name = match &*self.get_type(key) {
+                Type::Literal(TsLiteralTypeKind::String(key)) => literal_key(key).to_string(),
+                Type::Literal(TsLiteralTypeKind::Number(key)) => key.to_string(),
+                // Index signatures aren't modelled.
+                _ => return None,
+            };
+            let type_map = FxHashMap::from_iter([(mapped.param.clone(), key)]);
+            let value = substitute_type(&self.types, mapped.value, &type_map);
+            let source_property = source.and_then(|source| match &*self.get_type(source) {
+                Type::Interface(interface) => interface
                     .properties
                     .iter()
-                    .map(|prop| {
-                        let resolved_type = substitute_type(&prop.type_info, &type_map);
-                        TsInterfaceProperty {
-                            name: prop.name.clone(),
-                            type_info: resolved_type,
-                            is_optional: prop.is_optional,
-                            is_readonly: prop.is_readonly,
-                        }
-                    })
-                    .collect();
-                Type::Interface(TsInterface {
-                    name: interface.name.clone(),
-                    extends: interface.extends.clone(),
-                    properties: resolved_properties,
-                    type_params: interface.type_params.clone(),
-                })
-            }
-            _ => base_type.clone(),
+                    .find(|prop| prop.name == name)
+                    .map(|prop| (prop.is_optional, prop.is_readonly)),
+                _ => None,
+            });
+            let (was_optional, was_readonly) = source_property.unwrap_or_default();
+            properties.push(TsInterfaceProperty {
+                type_info: self.evaluate_type(value, path),
+                is_optional: apply_modifier(mapped.optional, was_optional),
+                is_readonly: apply_modifier(mapped.readonly, was_readonly),
+                name,
+            });
         }
+        Some(self.intern(Type::Interface(TsInterface {
+            name: "__type".to_string(),
+            extends: vec![],
+            properties,
+            type_params: vec![],
+            type_args: vec![],
+        })))
+    }
+
+    /// `typeof a.b`: the declared type of the value `a`, then of its members.
+    fn resolve_type_query(&self, name: &str, path: &PathBuf) -> Option<TypeId> {
+        let mut parts = name.split('.');
+        let value = parts.next()?;
+        let symbol = self
+            .lookup_in_scope(path, Namespace::Value, value)
+            .or_else(|| self.builtin_symbol_table.get(Namespace::Value, value))?;
+        Some(parts.fold(symbol.ty, |ty, member| {
+            self.resolve_member_type(ty, member, path)
+        }))
+    }
+
+    /// Instantiates the generic `generic` with `type_args`. Instantiations
+    /// are cached by the arena.
+    fn apply_type_arguments(&self, generic: TypeId, type_args: &[TypeId]) -> TypeId {
+        self.types
+            .instantiate(generic, type_args.to_vec(), |base_type| {
+                instantiate(&self.types, base_type, type_args)
+            })
     }
 }
 
-pub fn substitute_type(ty: &Type, type_map: &FxHashMap<String, Type>) -> Type {
-    match ty {
-        Type::TypeRef(ref_type) => {
-            if let Some(ty) = type_map.get(&ref_type.name) {
-                return ty.clone();
-            }
-            if !ref_type.type_params.is_empty() {
-                let mut resolved_type = vec![];
-                for r in ref_type.type_params.iter() {
-                    let ty = substitute_type(r, type_map);
-                    resolved_type.push(ty);
-                }
-                Type::TypeRef(TsTypeRef {
-                    name: ref_type.name.clone(),
-                    type_params: resolved_type,
-                })
-            } else {
-                ty.clone()
-            }
-        }
-        Type::Union(types) => {
-            let resolved_types = types.iter().map(|t| substitute_type(t, type_map)).collect();
-            Type::Union(resolved_types)
-        }
-        Type::Function(func) => {
-            let mut resolved_func = func.clone();
-            resolved_func.params = resolved_func
-                .params
+fn instantiate(types: &TypeArena, base_type: &Type, type_args: &[TypeId]) -> TypeId {
+    match base_type {
+        Type::Interface(interface) => {
+            let Some(type_map) = type_argument_map(types, &interface.type_params, type_args) else {
+                return types.intern(Type::Unknown);
+            };
+            let resolved_properties = interface
+                .properties
                 .iter()
-                .map(|param| FunctionParam {
-                    name: param.name.clone(),
-                    param_type: substitute_type(&param.param_type, type_map),
-                    is_optional: param.is_optional,
+                .map(|prop| TsInterfaceProperty {
+                    type_info: substitute_type(types, prop.type_info, &type_map),
+                    ..prop.clone()
                 })
                 .collect();
-            resolved_func.return_type = Box::new(substitute_type(&func.return_type, type_map));
-            Type::Function(resolved_func)
+            types.intern(Type::Interface(TsInterface {
+                name: interface.name.clone(),
+                extends: interface
+                    .extends
+                    .iter()
+                    .map(|ext| substitute_type(types, *ext, &type_map))
+                    .collect(),
+                properties: resolved_properties,
+                type_params: interface.type_params.clone(),
+                type_args: interface
+                    .type_params
+                    .iter()
+                    .map(|param| type_map[&param.name])
+                    .collect(),
+            }))
+        }
+        Type::Alias(alias) => {
+            let Some(type_map) = type_argument_map(types, &alias.type_params, type_args) else {
+                return types.intern(Type::Unknown);
+            };
+            substitute_type(types, alias.aliased_type, &type_map)
+        }
+        _ => types.intern(base_type.clone()),
+    }
+}
+
+/// Maps type parameters to the given arguments, filling in defaults for
+/// omitted ones. Returns `None` when the arguments don't fit the parameters.
+fn type_argument_map(
+    types: &TypeArena,
+    params: &[TypeParam],
+    args: &[TypeId],
+) -> Option<FxHashMap<String, TypeId>> {
+    if args.len() > params.len() {
+        return None;
+    }
+    let mut type_map = FxHashMap::default();
+    for (index, param) in params.iter().enumerate() {
+        let arg = match args.get(index) {
+            Some(arg) => *arg,
+            None => substitute_type(types, param.default?, &type_map),
+        };
+        type_map.insert(param.name.clone(), arg);
+    }
+    Some(type_map)
+}
+
+/// Replaces references to the type parameters in `type_map`. Types without
+/// any are returned as they are, without interning.
+pub fn substitute_type(
+    types: &TypeArena,
+    ty: TypeId,
+    type_map: &FxHashMap<String, TypeId>,
+) -> TypeId {
+    match &*types.get(ty) {
+        Type::TypeRef(ref_type) => match type_map.get(&ref_type.name) {
+            Some(arg) => *arg,
+            None => types.map(ty, |t| substitute_type(types, t, type_map)),
+        },
+        // The parameter of a mapped type shadows the outer ones.
+        Type::Mapped(mapped) if type_map.contains_key(&mapped.param) => {
+            let mut type_map = type_map.clone();
+            type_map.remove(&mapped.param);
+            types.map(ty, |t| substitute_type(types, t, &type_map))
+        }
+        Type::Union(_)
+        | Type::KeyOf(_)
+        | Type::IndexedAccess(_)
+        | Type::Mapped(_)
+        | Type::Tuple(_)
+        | Type::Intersection(_)
+        | Type::Interface(_)
+        | Type::Function(_)
+        | Type::Literal(TsLiteralTypeKind::Object(_)) => {
+            types.map(ty, |t| substitute_type(types, t, type_map))
+        }
+        _ => ty,
+    }
+}
+
+/// String literal types may hold their source text, quotes included.
+fn literal_key(key: &str) -> &str {
+    ['"', '\'']
+        .into_iter()
+        .find_map(|quote| key.strip_prefix(quote)?.strip_suffix(quote))
+        .unwrap_or(key)
+}
+
+fn apply_modifier(modifier: MappedModifier, declared: bool) -> bool {
+    match modifier {
+        MappedModifier::Add => true,
+        MappedModifier::Remove => false,
+        MappedModifier::Preserve => declared,
+    }
+}
+
+fn is_array_name(name: &str) -> bool {
+    name == "Array" || name == "ReadonlyArray"
+}
+
+pub fn substitute_this(types: &TypeArena, ty: TypeId, receiver: TypeId) -> TypeId {
+    match &*types.get(ty) {
+        Type::This => receiver,
+        Type::TypeRef(_) | Type::Union(_) | Type::Intersection(_) | Type::Function(_) => {
+            types.map(ty, |t| substitute_this(types, t, receiver))
         }
-        _ => ty.clone(),
+        _ => ty,
     }
 }
@@ -0,0 +1,199 @@
+use std::sync::Arc;
+
+use biome_js_syntax::{
+    AnyJsArrowFunctionParameters, JsArrowFunctionExpression, JsFunctionBody, JsParameters,
+    JsSyntaxNode, TextRange, TextSize,
+};
+use biome_rowan::AstNode;
+use rustc_hash::FxHashMap;
+use type_info::{
+    symbol::{Namespace, Symbol},
+    Type, TypeId,
+};
+use visitor::Visitor;
+
+use crate::{binding::binding_name, function::is_function_like, TypeAnalyzer};
+
+/// What a file binds besides its symbols: the names declared inside its
+/// functions and the types its assignments narrow bindings to.
+#[derive(Debug, Clone, Default)]
+pub struct FileBindings {
+    /// Parameters included, by the range of the function binding them.
+    locals: FxHashMap<TextRange, Vec<Symbol>>,
+    /// In source order.
+    narrowings: Vec<Narrowing>,
+}
+
+/// The type of a `let`/`var` binding after an initializer or assignment.
+#[derive(Debug, Clone)]
+struct Narrowing {
+    /// Where the binding is declared.
+    binding: TextRange,
+    /// The function the assignment is in, `None` at the top level.
+    function: Option<TextRange>,
+    /// Where the assignment ends.
+    end: TextSize,
+    ty: TypeId,
+}
+
+impl FileBindings {
+    fn local(&self, function: TextRange, name: &str) -> Option<&Symbol> {
+        self.locals
+            .get(&function)?
+            .iter()
+            .rev()
+            .find(|symbol| symbol.name == name)
+    }
+}
+
+impl TypeAnalyzer {
+    /// The bindings of the file last visited.
+    pub fn bindings(&self) -> &Arc<FileBindings> {
+        &self.bindings
+    }
+
+    /// Makes lookups see the bindings of the current file, e.g. from a fork
+    /// that visited it.
+    pub fn set_bindings(&mut self, bindings: Arc<FileBindings>) {
+        self.bindings = bindings;
+    }
+
+    /// Binds the parameters of `function`, a function-like node, and the
+    /// names its body declares, in a scope of its own. Type declarations in
+    /// function bodies aren't modelled.
+    pub(crate) fn declare_locals(&mut self, function: &JsSyntaxNode) {
+        let range = function.text_trimmed_range();
+        if self.bindings.locals.contains_key(&range) {
+            return;
+        }
+        let parameters = if let Some(params) = function.children().find_map(JsParameters::cast) {
+            self.analyze_parameter_bindings(&params).unwrap_or_default()
+        } else if let Some(Ok(AnyJsArrowFunctionParameters::AnyJsBinding(binding))) =
+            JsArrowFunctionExpression::cast_ref(function).map(|arrow| arrow.parameters())
+        {
+            binding_name(&binding)
+                .map(|name| {
+                    Symbol::new(name, self.intern(Type::Unknown))
+                        .declared_at(self.location(binding.syntax()))
+                })
+                .into_iter()
+                .collect()
+        } else {
+            vec![]
+        };
+        Arc::make_mut(&mut self.bindings)
+            .locals
+            .insert(range, parameters);
+
+        let Some(body) = function.children().find_map(JsFunctionBody::cast) else {
+            return;
+        };
+        let previous = self.current_scope.replace(range);
+        for statement in body.statements() {
+            self.visit_statement(&statement);
+        }
+        self.current_scope = previous;
+    }
+
+    /// Adds `symbol` to the scope of the function at `scope`.
+    pub(crate) fn insert_local_symbol(
+        &mut self,
+        scope: TextRange,
+        namespace: Namespace,
+        symbol: Symbol,
+    ) {
+        if namespace == Namespace::Value {
+            Arc::make_mut(&mut self.bindings)
+                .locals
+                .entry(scope)
+                .or_default()
+                .push(symbol);
+        }
+    }
+
+    /// Looks up the value `name` refers to at `node`, in the functions
+    /// around it from the innermost, then like [TypeAnalyzer::lookup_value].
+    pub fn lookup_value_at(&self, name: &str, node: &JsSyntaxNode) -> Option<&Symbol> {
+        node.ancestors()
+            .filter(|ancestor| is_function_like(ancestor.kind()))
+            .find_map(|function| self.bindings.local(function.text_trimmed_range(), name))
+            .or_else(|| self.lookup_value(name))
+    }
+
+    /// The value `binding`, a name in a declaration of the current file,
+    /// declares: a local of the function around it, or else a symbol of the
+    /// file. Unlike a lookup by name, this tells apart bindings shadowing
+    /// each other.
+    pub fn binding_symbol(&self, binding: &JsSyntaxNode) -> Option<&Symbol> {
+        let range = binding.text_trimmed_range();
+        let declares = |symbol: &&Symbol| {
+            symbol
+                .declarations
+                .iter()
+                .any(|location| location.path == self.current_file && location.range == range)
+        };
+        // A function's name is declared in the scope around the function.
+        let declaration = binding
+            .parent()
+            .filter(|parent| is_function_like(parent.kind()))
+            .unwrap_or_else(|| binding.clone());
+        match enclosing_function(&declaration) {
+            Some(function) => self.bindings.locals.get(&function)?.iter().find(declares),
+            None => self
+                .lookup_value(binding.text_trimmed().to_string().as_str())
+                .filter(declares),
+        }
+    }
+
+    /// Records that the binding of `symbol` has type `ty` after `node`, an
+    /// initializer or assignment.
+    pub(crate) fn narrow(&mut self, symbol: &Symbol, node: &JsSyntaxNode, ty: TypeId) {
+        let Some(binding) = self.declared_range(symbol) else {
+            return;
+        };
+        let narrowing = Narrowing {
+            binding,
+            function: enclosing_function(node),
+            end: node.text_trimmed_range().end(),
+            ty,
+        };
+        Arc::make_mut(&mut self.bindings).narrowings.push(narrowing);
+    }
+
+    /// The type of `symbol` where `node` refers to it: the type of the last
+    /// assignment to it before `node` in the same function, else its
+    /// declared type. Assignments in branches count as if they always ran.
+    pub fn flow_type_at(&self, symbol: &Symbol, node: &JsSyntaxNode) -> TypeId {
+        let Some(binding) = self.declared_range(symbol).filter(|_| symbol.is_mutable) else {
+            return symbol.ty;
+        };
+        let function = enclosing_function(node);
+        let start = node.text_trimmed_range().start();
+        self.bindings
+            .narrowings
+            .iter()
+            .rev()
+            .find(|narrowing| {
+                narrowing.binding == binding
+                    && narrowing.function == function
+                    && narrowing.end <= start
+            })
+            .map_or(symbol.ty, |narrowing| narrowing.ty)
+    }
+
+    /// Where `symbol` is declared in the current file.
+    fn declared_range(&self, symbol: &Symbol) -> Option<TextRange> {
+        symbol
+            .declarations
+            .iter()
+            .find(|location| location.path == self.current_file)
+            .map(|location| location.range)
+    }
+}
+
+fn enclosing_function(node: &JsSyntaxNode) -> Option<TextRange> {
+    node.ancestors()
+        .skip(1)
+        .find(|ancestor| is_function_like(ancestor.kind()))
+        .map(|function| function.text_trimmed_range())
+}
@@ -0,0 +1,199 @@
+use std::{path::PathBuf, sync::Arc};
+
+use biome_rowan::TextRange;
+use serde::{Deserialize, Serialize};
+use type_info::{
+    symbol::{BuiltinTable, Location, Namespace, Symbol},
+    Type, TypeExport, TypeId,
+};
+
+use crate::{module::ambient_module_path, TypeAnalyzer};
+
+/// What analyzing a file declared, with the types copied out of the arena,
+/// so that it can be stored and loaded into another analyzer.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeclarationSnapshot {
+    /// The types the symbols refer to, by their index here.
+    types: Vec<Type>,
+    symbols: Vec<SymbolSnapshot>,
+    exports: Vec<SymbolSnapshot>,
+    ambient_modules: Vec<String>,
+    /// Imports of the file bound to ambient modules.
+    ambient_imports: Vec<(String, PathBuf)>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SymbolSnapshot {
+    /// `None` for the scope of the file itself, which depends on its path.
+    scope: Option<PathBuf>,
+    namespace: Namespace,
+    name: String,
+    ty: TypeId,
+    is_mutable: bool,
+    flow_ty: Option<TypeId>,
+    declarations: Vec<LocationSnapshot>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LocationSnapshot {
+    /// `None` for the file itself.
+    path: Option<PathBuf>,
+    start: u32,
+    end: u32,
+}
+
+impl TypeAnalyzer {
+    /// Sets up an analyzer with built-in types saved with
+    /// [TypeAnalyzer::builtin_snapshot] instead of analyzing the lib files.
+    pub fn from_builtin_snapshot(snapshot: &DeclarationSnapshot) -> Self {
+        let mut analyzer = Self::default();
+        analyzer.restore(snapshot);
+        let mut builtins = BuiltinTable::new();
+        for (_, symbol_table) in analyzer.symbol_table.iter() {
+            for (namespace, symbol) in symbol_table.iter() {
+                builtins.insert(namespace, symbol.clone());
+            }
+        }
+        analyzer.builtin_symbol_table = Arc::new(builtins);
+        analyzer.symbol_table.freeze();
+        analyzer
+    }
+
+    /// The built-in types, in the global scope.
+    pub fn builtin_snapshot(&self) -> DeclarationSnapshot {
+        let mut types = TypeExport::default();
+        let symbols = self
+            .builtin_symbol_table
+            .iter()
+            .map(|(namespace, symbol)| self.symbol_snapshot(None, namespace, symbol, &mut types))
+            .collect();
+        DeclarationSnapshot {
+            types: types.types,
+            symbols,
+            ..Default::default()
+        }
+    }
+
+    /// What this fork declared while analyzing the file of the current scope.
+    pub fn snapshot(&self) -> DeclarationSnapshot {
+        let Some(declarations) = &self.declarations else {
+            return DeclarationSnapshot::default();
+        };
+        let mut types = TypeExport::default();
+        let symbols = declarations
+            .symbols
+            .iter()
+            .map(|(path, namespace, symbol)| {
+                // Like in a merge, assignments may have narrowed the symbol.
+                let flow_ty = self
+                    .symbol_table
+                    .get(path, *namespace, &symbol.name)
+                    .filter(|_| symbol.is_mutable)
+                    .map_or(symbol.flow_ty, |declared| declared.flow_ty);
+                let scope = (path != &self.current_path).then(|| path.clone());
+                let symbol = Symbol {
+                    flow_ty,
+                    ..symbol.clone()
+                };
+                self.symbol_snapshot(scope, *namespace, &symbol, &mut types)
+            })
+            .collect();
+        let exports = declarations
+            .exports
+            .iter()
+            .map(|(path, namespace, symbol)| {
+                let scope = (path != &self.current_path).then(|| path.clone());
+                self.symbol_snapshot(scope, *namespace, symbol, &mut types)
+            })
+            .collect();
+        let ambient_imports = self
+            .resolved_modules
+            .get(&self.current_path)
+            .into_iter()
+            .flatten()
+            .filter(|(_, module)| {
+                self.ambient_modules
+                    .iter()
+                    .any(|name| ambient_module_path(name) == **module)
+            })
+            .map(|(specifier, module)| (specifier.clone(), module.clone()))
+            .collect();
+        DeclarationSnapshot {
+            types: types.types,
+            symbols,
+            exports,
+            ambient_modules: declarations.ambient_modules.clone(),
+            ambient_imports,
+        }
+    }
+
+    /// Declares what `snapshot` holds as if the file of the current scope
+    /// had been analyzed.
+    pub fn restore(&mut self, snapshot: &DeclarationSnapshot) {
+        let current = self.current_path.clone();
+        for name in &snapshot.ambient_modules {
+            self.register_ambient_module(name.clone());
+        }
+        for (specifier, module) in &snapshot.ambient_imports {
+            self.add_resolved_module(current.clone(), specifier.clone(), module.clone());
+        }
+        let types = self.types.import(&snapshot.types);
+        for symbol in &snapshot.symbols {
+            self.current_path = symbol.scope.clone().unwrap_or_else(|| current.clone());
+            let restored = self.restore_symbol(symbol, &types);
+            self.insert_new_symbol(symbol.namespace, restored);
+        }
+        for symbol in &snapshot.exports {
+            self.current_path = symbol.scope.clone().unwrap_or_else(|| current.clone());
+            let restored = self.restore_symbol(symbol, &types);
+            self.export_symbol(symbol.namespace, restored);
+        }
+        self.current_path = current;
+    }
+
+    fn restore_symbol(&self, symbol: &SymbolSnapshot, types: &[TypeId]) -> Symbol {
+        Symbol {
+            name: symbol.name.clone(),
+            ty: types[symbol.ty.index()],
+            is_mutable: symbol.is_mutable,
+            flow_ty: symbol.flow_ty.map(|ty| types[ty.index()]),
+            declarations: symbol
+                .declarations
+                .iter()
+                .map(|location| Location {
+                    path: location
+                        .path
+                        .clone()
+                        .unwrap_or_else(|| self.current_file.clone()),
+                    range: TextRange::new(location.start.into(), location.end.into()),
+                })
+                .collect(),
+        }
+    }
+
+    fn symbol_snapshot(
+        &self,
+        scope: Option<PathBuf>,
+        namespace: Namespace,
+        symbol: &Symbol,
+        types: &mut TypeExport,
+    ) -> SymbolSnapshot {
+        SymbolSnapshot {
+            scope,
+            namespace,
+            name: symbol.name.clone(),
+            ty: self.types.export(symbol.ty, types),
+            is_mutable: symbol.is_mutable,
+            flow_ty: symbol.flow_ty.map(|ty| self.types.export(ty, types)),
+            declarations: symbol
+                .declarations
+                .iter()
+                .map(|location| LocationSnapshot {
+                    path: (location.path != self.current_file).then(|| location.path.clone()),
+                    start: location.range.start().into(),
+                    end: location.range.end().into(),
+                })
+                .collect(),
+        }
+    }
+}
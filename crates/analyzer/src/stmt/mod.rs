@@ -1 +1,5 @@
+mod unsupported;
 mod variable;
+
+pub use unsupported::{DeclarationKind, UnsupportedDeclaration};
+pub(crate) use variable::is_mutable_declarator;
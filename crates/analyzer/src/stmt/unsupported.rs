@@ -0,0 +1,80 @@
+use std::fmt;
+
+use biome_js_syntax::{
+    JsClassDeclaration, JsSyntaxNode, TextRange, TsEnumDeclaration, TsModuleDeclaration,
+};
+use biome_rowan::AstNode;
+
+use crate::{binding::binding_name, TypeAnalyzer};
+
+/// A declaration the analyzer doesn't model. Its names are left unbound.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsupportedDeclaration {
+    /// The declared name.
+    pub range: TextRange,
+    pub kind: DeclarationKind,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeclarationKind {
+    Class,
+    Enum,
+    Namespace,
+}
+
+impl fmt::Display for UnsupportedDeclaration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            DeclarationKind::Class => "Class",
+            DeclarationKind::Enum => "Enum",
+            DeclarationKind::Namespace => "Namespace",
+        };
+        write!(
+            f,
+            "{} '{}' isn't analyzed yet; its type is unknown.",
+            kind, self.name
+        )
+    }
+}
+
+impl TypeAnalyzer {
+    /// Records classes, enums and namespaces, see
+    /// [TypeAnalyzer::unsupported_declarations]. Other nodes are ignored.
+    pub(crate) fn visit_unsupported_declaration(&mut self, node: &JsSyntaxNode) {
+        let declaration = if let Some(class) = JsClassDeclaration::cast_ref(node) {
+            class.id().ok().and_then(|id| {
+                let name = binding_name(&id)?;
+                Some((
+                    id.syntax().text_trimmed_range(),
+                    DeclarationKind::Class,
+                    name,
+                ))
+            })
+        } else if let Some(enumeration) = TsEnumDeclaration::cast_ref(node) {
+            enumeration.id().ok().and_then(|id| {
+                let name = binding_name(&id)?;
+                Some((
+                    id.syntax().text_trimmed_range(),
+                    DeclarationKind::Enum,
+                    name,
+                ))
+            })
+        } else if let Some(namespace) = TsModuleDeclaration::cast_ref(node) {
+            namespace.name().ok().map(|name| {
+                let text = name.syntax().text_trimmed().to_string();
+                (
+                    name.syntax().text_trimmed_range(),
+                    DeclarationKind::Namespace,
+                    text,
+                )
+            })
+        } else {
+            None
+        };
+        if let Some((range, kind, name)) = declaration {
+            self.unsupported_declarations
+                .push(UnsupportedDeclaration { range, kind, name });
+        }
+    }
+}
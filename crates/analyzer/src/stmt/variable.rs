@@ -1,16 +1,24 @@
-use biome_js_syntax::{AnyTsVariableAnnotation, JsVariableDeclarator};
-use type_info::Type;
+use biome_js_syntax::{
+    AnyJsAssignment, AnyJsAssignmentPattern, AnyJsBindingPattern, AnyTsVariableAnnotation,
+    JsAssignmentExpression, JsAssignmentOperator, JsSyntaxNode, JsVariableDeclaration,
+    JsVariableDeclarator, JsVariableKind,
+};
+use biome_rowan::{AstNode, WalkEvent};
+use type_info::{symbol::Namespace, TsKeywordTypeKind, Type, TypeArena, TypeId};
+use visitor::Visitor;
 
-use crate::{TResult, TypeAnalyzer};
+use crate::{expr::widen_literal_type, function::is_function_like, TResult, TypeAnalyzer};
 
 impl TypeAnalyzer {
-    pub fn analyze_js_variable_declarator(&self, node: &JsVariableDeclarator) -> TResult<Type> {
+    /// Returns the declared type of the declarator. Unannotated `let`/`var`
+    /// bindings get the widened type of their initializer.
+    pub fn analyze_js_variable_declarator(&self, node: &JsVariableDeclarator) -> TResult<TypeId> {
         let ann = node.variable_annotation();
 
         let ty = if let Some(ann) = ann {
             match ann {
                 AnyTsVariableAnnotation::TsDefiniteVariableAnnotation(node) => {
-                    todo!("definite assignment assertion {:?}", node)
+                    self.analyze_type_annotation(node.type_annotation()?)
                 }
                 AnyTsVariableAnnotation::TsTypeAnnotation(node) => {
                     self.analyze_type_annotation(node)
@@ -18,13 +26,148 @@ impl TypeAnalyzer {
             }
         } else if let Some(init) = node.initializer() {
             if let Ok(expr) = init.expression() {
-                self.analyze_expression(&expr)
+                let ty = self.analyze_expression(&expr);
+                if is_mutable_declarator(node) {
+                    widen_literal_type(&self.types, ty)
+                } else {
+                    ty
+                }
             } else {
-                Type::Unknown
+                self.intern(Type::Unknown)
             }
         } else {
-            Type::Unknown
+            self.intern(Type::Unknown)
         };
         Ok(ty)
     }
+
+    /// Returns the flow type of an annotated `let`/`var` binding right after
+    /// its initializer, e.g. `string` for `let x: string | number = "a"`.
+    pub fn analyze_js_variable_flow_type(&self, node: &JsVariableDeclarator) -> Option<TypeId> {
+        if !is_mutable_declarator(node) || node.variable_annotation().is_none() {
+            return None;
+        }
+        if !matches!(node.id(), Ok(AnyJsBindingPattern::AnyJsBinding(_))) {
+            return None;
+        }
+        let declared = self.analyze_js_variable_declarator(node).ok()?;
+        let assigned = self.analyze_expression(&node.initializer()?.expression().ok()?);
+        narrow_by_assignment(&self.types, declared, assigned)
+    }
+
+    /// Records the type the binding assigned by `node` has after it. Top-level
+    /// assignments to a symbol of the file also update its flow type.
+    pub fn analyze_js_assignment_expression(&mut self, node: &JsAssignmentExpression) {
+        let Ok(AnyJsAssignmentPattern::AnyJsAssignment(AnyJsAssignment::JsIdentifierAssignment(
+            ident,
+        ))) = node.left()
+        else {
+            return;
+        };
+        let Ok(name) = ident.name_token() else {
+            return;
+        };
+        let assigned = match (node.operator(), node.right()) {
+            (Ok(JsAssignmentOperator::Assign), Ok(right)) => self.analyze_expression(&right),
+            // Compound assignments fall back to the declared type.
+            _ => self.intern(Type::Unknown),
+        };
+        let Some(symbol) = self
+            .lookup_value_at(name.text_trimmed(), node.syntax())
+            .filter(|symbol| symbol.is_mutable)
+            .cloned()
+        else {
+            return;
+        };
+        let flow_ty = narrow_by_assignment(&self.types, symbol.ty, assigned);
+        self.narrow(&symbol, node.syntax(), flow_ty.unwrap_or(symbol.ty));
+
+        let in_function = node
+            .syntax()
+            .ancestors()
+            .any(|ancestor| is_function_like(ancestor.kind()));
+        if in_function {
+            return;
+        }
+        let path = self.current_path.clone();
+        if let Some(symbol) = self
+            .symbol_table
+            .get_mut(&path, Namespace::Value, name.text_trimmed())
+            .filter(|declared| declared.declarations == symbol.declarations)
+        {
+            symbol.flow_ty = flow_ty;
+        }
+    }
+
+    /// Analyzes the assignments in `node` in source order, leaving nested
+    /// functions alone.
+    pub fn analyze_assignments(&mut self, node: &JsSyntaxNode) {
+        let mut preorder = node.preorder();
+        while let Some(event) = preorder.next() {
+            let WalkEvent::Enter(node) = event else {
+                continue;
+            };
+            if is_function_like(node.kind()) {
+                preorder.skip_subtree();
+                continue;
+            }
+            if let Some(assignment) = JsAssignmentExpression::cast(node) {
+                self.analyze_js_assignment_expression(&assignment);
+            }
+        }
+    }
+
+    /// `var` declarations nested in blocks, loops, etc. belong to the
+    /// enclosing function scope, which at the top level is the module.
+    /// Block-scoped `let`/`const` and nested functions are left alone.
+    pub fn hoist_var_declarations(&mut self, node: &JsSyntaxNode) {
+        let mut preorder = node.preorder();
+        while let Some(event) = preorder.next() {
+            let WalkEvent::Enter(node) = event else {
+                continue;
+            };
+            if is_function_like(node.kind()) {
+                preorder.skip_subtree();
+                continue;
+            }
+            if let Some(decl) = JsVariableDeclaration::cast(node) {
+                if matches!(decl.variable_kind(), Ok(JsVariableKind::Var)) {
+                    for declarator in decl.declarators().into_iter().flatten() {
+                        self.visit_js_variable_declarator(&declarator);
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn is_mutable_declarator(node: &JsVariableDeclarator) -> bool {
+    node.syntax()
+        .ancestors()
+        .find_map(JsVariableDeclaration::cast)
+        .is_some_and(|decl| {
+            matches!(
+                decl.variable_kind(),
+                Ok(JsVariableKind::Let | JsVariableKind::Var)
+            )
+        })
+}
+
+/// Like tsc, assignments only narrow bindings declared with a union type
+/// (or `unknown`). Literals are widened unless the union has them, as in
+/// `let x: "a" | "b"`.
+fn narrow_by_assignment(types: &TypeArena, declared: TypeId, assigned: TypeId) -> Option<TypeId> {
+    if let Type::Union(members) = &*types.get(declared) {
+        if members.contains(&assigned) {
+            return Some(assigned);
+        }
+    }
+    let assigned = widen_literal_type(types, assigned);
+    if *types.get(assigned) == Type::Unknown || assigned == declared {
+        return None;
+    }
+    match &*types.get(declared) {
+        Type::Union(_) | Type::KeywordType(TsKeywordTypeKind::Unknown) => Some(assigned),
+        _ => None,
+    }
 }
@@ -0,0 +1,34 @@
+use biome_js_syntax::TsTypeAliasDeclaration;
+use type_info::{TsTypeAlias, Type, TypeId};
+
+use crate::{TResult, TypeAnalyzer};
+
+use super::identifier_binding_name;
+
+impl TypeAnalyzer {
+    pub fn analyze_ts_type_alias_declaration(
+        &self,
+        node: &TsTypeAliasDeclaration,
+    ) -> TResult<TypeId> {
+        let Some(name) = identifier_binding_name(&node.binding_identifier()?) else {
+            return Ok(self.intern(Type::Unknown));
+        };
+
+        let mut type_params = vec![];
+        if let Some(params) = node.type_parameters() {
+            for param in params.items().into_iter().flatten() {
+                if let Ok(param) = self.analyze_type_param(&param) {
+                    type_params.push(param);
+                }
+            }
+        }
+
+        let aliased_type = self.analyze_any_ts_types(&node.ty()?)?;
+
+        Ok(self.intern(Type::Alias(TsTypeAlias {
+            name,
+            type_params,
+            aliased_type,
+        })))
+    }
+}
@@ -1,23 +1,42 @@
-use biome_js_syntax::{AnyTsType, TsTypeAnnotation};
-use type_info::{BoolLiteral, TsKeywordTypeKind, TsLiteralTypeKind, Type};
+use biome_js_syntax::{
+    AnyTsReturnType, AnyTsTupleTypeElement, AnyTsType, JsSyntaxToken, TsMappedType,
+    TsTypeAnnotation,
+};
+use biome_rowan::AstNode;
+use type_info::{
+    BoolLiteral, IndexedAccessType, MappedModifier, MappedType, TsInterface, TsKeywordTypeKind,
+    TsLiteralTypeKind, TsTypeRef, TupleElement, Type, TypeId,
+};
 
 use crate::{TResult, TypeAnalyzer};
 
 impl TypeAnalyzer {
-    pub fn analyze_type_annotation(&self, node: TsTypeAnnotation) -> Type {
+    pub fn analyze_type_annotation(&self, node: TsTypeAnnotation) -> TypeId {
         match node.ty() {
             Ok(ty) => {
                 let ty = self.analyze_any_ts_types(&ty);
                 match ty {
                     Ok(ty) => ty,
-                    Err(_) => Type::Unknown,
+                    Err(_) => self.intern(Type::Unknown),
                 }
             }
-            Err(_) => Type::Unknown,
+            Err(_) => self.intern(Type::Unknown),
         }
     }
 
-    pub fn analyze_any_ts_types(&self, node: &AnyTsType) -> TResult<Type> {
+    pub fn analyze_any_ts_return_type(&self, node: &AnyTsReturnType) -> TResult<TypeId> {
+        let ty = match node {
+            AnyTsReturnType::AnyTsType(ty) => return self.analyze_any_ts_types(ty),
+            // `x is T` narrows the argument, the call itself returns a boolean.
+            AnyTsReturnType::TsPredicateReturnType(_) => {
+                Type::KeywordType(TsKeywordTypeKind::Boolean)
+            }
+            AnyTsReturnType::TsAssertsReturnType(_) => Type::KeywordType(TsKeywordTypeKind::Void),
+        };
+        Ok(self.intern(ty))
+    }
+
+    pub fn analyze_any_ts_types(&self, node: &AnyTsType) -> TResult<TypeId> {
         let ty = match node {
             AnyTsType::TsAnyType(_) => Type::KeywordType(TsKeywordTypeKind::Any),
             AnyTsType::TsBigintType(_) => Type::KeywordType(TsKeywordTypeKind::BigInt),
@@ -41,16 +60,23 @@ impl TypeAnalyzer {
             }
             AnyTsType::TsNumberLiteralType(lit) => {
                 let value = lit.literal_token()?.text_trimmed().to_string();
-                dbg!(&value);
-                Type::Literal(TsLiteralTypeKind::Number(value.parse().unwrap()))
+                let sign = if lit.minus_token().is_some() { -1 } else { 1 };
+                // Only integers are representable as literal types for now.
+                match value.parse::<i64>() {
+                    Ok(value) => Type::Literal(TsLiteralTypeKind::Number(sign * value)),
+                    Err(_) => Type::KeywordType(TsKeywordTypeKind::Number),
+                }
             }
             AnyTsType::TsStringLiteralType(lit) => {
                 let value = lit.literal_token()?.text_trimmed().to_string();
                 Type::Literal(TsLiteralTypeKind::String(value))
             }
             AnyTsType::TsNullLiteralType(_) => Type::KeywordType(TsKeywordTypeKind::Null),
+            AnyTsType::TsBigintLiteralType(_) => Type::KeywordType(TsKeywordTypeKind::BigInt),
+            AnyTsType::TsTemplateLiteralType(_) => Type::KeywordType(TsKeywordTypeKind::String),
+            AnyTsType::TsNonPrimitiveType(_) => Type::KeywordType(TsKeywordTypeKind::Object),
 
-            AnyTsType::TsReferenceType(ref_type) => self.analyze_ts_type_ref(ref_type)?,
+            AnyTsType::TsReferenceType(ref_type) => return self.analyze_ts_type_ref(ref_type),
             AnyTsType::TsUnionType(union) => {
                 let mut types = vec![];
                 for ty in union.types().into_iter().flatten() {
@@ -59,13 +85,137 @@ impl TypeAnalyzer {
                 }
                 Type::Union(types)
             }
+            AnyTsType::TsIntersectionType(intersection) => {
+                let mut types = vec![];
+                for ty in intersection.types().into_iter().flatten() {
+                    let t = self.analyze_any_ts_types(&ty)?;
+                    types.push(t);
+                }
+                Type::Intersection(types)
+            }
+            AnyTsType::TsTypeOperatorType(operator) => {
+                match operator.operator_token()?.text_trimmed() {
+                    "readonly" => return self.analyze_any_ts_types(&operator.ty()?),
+                    "unique" => Type::KeywordType(TsKeywordTypeKind::Symbol),
+                    // keyof
+                    _ => Type::KeyOf(self.analyze_any_ts_types(&operator.ty()?)?),
+                }
+            }
             AnyTsType::TsParenthesizedType(ty) => {
                 let inner = ty.ty()?;
-                self.analyze_any_ts_types(&inner)?
+                return self.analyze_any_ts_types(&inner);
+            }
+            AnyTsType::TsFunctionType(func) => return self.analyze_ts_function_type(func),
+            AnyTsType::TsThisType(_) => Type::This,
+            AnyTsType::TsObjectType(object) => {
+                let mut properties = vec![];
+                for member in object.members() {
+                    if let Some(property) = self.analyze_any_ts_type_member(&member)? {
+                        properties.push(property);
+                    }
+                }
+                // tsc names anonymous object types `__type`.
+                Type::Interface(TsInterface {
+                    name: "__type".to_string(),
+                    extends: vec![],
+                    properties,
+                    type_params: vec![],
+                    type_args: vec![],
+                })
             }
-            AnyTsType::TsFunctionType(func) => self.analyze_ts_function_type(func)?,
-            _ => todo!("{:?}", node),
+            AnyTsType::TsArrayType(array) => {
+                let element = self.analyze_any_ts_types(&array.element_type()?)?;
+                return Ok(self.array_of(element));
+            }
+            AnyTsType::TsTupleType(tuple) => {
+                let mut elements = vec![];
+                for element in tuple.elements().into_iter().flatten() {
+                    let element = match element {
+                        AnyTsTupleTypeElement::AnyTsType(ty) => {
+                            TupleElement::required(self.analyze_any_ts_types(&ty)?)
+                        }
+                        AnyTsTupleTypeElement::TsNamedTupleTypeElement(named) => TupleElement {
+                            ty: self.analyze_any_ts_types(&named.ty()?)?,
+                            is_optional: named.question_mark_token().is_some(),
+                            is_rest: named.dotdotdot_token().is_some(),
+                        },
+                        AnyTsTupleTypeElement::TsOptionalTupleTypeElement(optional) => {
+                            TupleElement {
+                                is_optional: true,
+                                ..TupleElement::required(
+                                    self.analyze_any_ts_types(&optional.ty()?)?,
+                                )
+                            }
+                        }
+                        // The type of a rest element is already the array, `...T[]`.
+                        AnyTsTupleTypeElement::TsRestTupleTypeElement(rest) => TupleElement {
+                            is_rest: true,
+                            ..TupleElement::required(self.analyze_any_ts_types(&rest.ty()?)?)
+                        },
+                    };
+                    elements.push(element);
+                }
+                Type::Tuple(elements)
+            }
+            AnyTsType::TsIndexedAccessType(access) => Type::IndexedAccess(IndexedAccessType {
+                object: self.analyze_any_ts_types(&access.object_type()?)?,
+                index: self.analyze_any_ts_types(&access.index_type()?)?,
+            }),
+            AnyTsType::TsMappedType(mapped) => return self.analyze_ts_mapped_type(mapped),
+            AnyTsType::TsTypeofType(query) => {
+                let name = query.expression_name()?.syntax().text_trimmed().to_string();
+                Type::TypeQuery(name.split_whitespace().collect())
+            }
+            // Not modelled yet: conditional, `infer`, constructor and import types.
+            _ => Type::Unsupported(node.syntax().text_trimmed().to_string()),
+        };
+        Ok(self.intern(ty))
+    }
+
+    fn analyze_ts_mapped_type(&self, node: &TsMappedType) -> TResult<TypeId> {
+        // Remapped keys, `[P in K as N]`, aren't modelled.
+        if node.as_clause().is_some() {
+            return Ok(self.intern(Type::Unsupported(node.syntax().text_trimmed().to_string())));
+        }
+        let value = match node.mapped_type() {
+            Some(annotation) => self.analyze_type_annotation(annotation),
+            None => self.intern(Type::KeywordType(TsKeywordTypeKind::Any)),
+        };
+        let mapped = MappedType {
+            param: node
+                .property_name()?
+                .ident_token()?
+                .text_trimmed()
+                .to_string(),
+            keys: self.analyze_any_ts_types(&node.keys_type()?)?,
+            value,
+            optional: node
+                .optional_modifier()
+                .map_or(MappedModifier::Preserve, |clause| {
+                    mapped_modifier(clause.operator_token())
+                }),
+            readonly: node
+                .readonly_modifier()
+                .map_or(MappedModifier::Preserve, |clause| {
+                    mapped_modifier(clause.operator_token())
+                }),
         };
-        Ok(ty)
+        Ok(self.intern(Type::Mapped(mapped)))
+    }
+
+    /// `T[]` is represented the same way as `Array<T>`.
+    pub(crate) fn array_of(&self, element: TypeId) -> TypeId {
+        self.intern(Type::TypeRef(TsTypeRef {
+            name: "Array".to_string(),
+            type_params: vec![element],
+        }))
+    }
+}
+
+/// `-?` and `-readonly` remove the modifier, `?`, `+?`, ... add it.
+fn mapped_modifier(operator: Option<JsSyntaxToken>) -> MappedModifier {
+    match operator {
+        Some(token) if token.text_trimmed() == "-" => MappedModifier::Remove,
+        _ => MappedModifier::Add,
     }
 }
@@ -1,10 +1,10 @@
-use biome_js_syntax::{AnyTsReturnType, TsDeclareFunctionDeclaration, TsFunctionType};
-use type_info::{TsFunctionSignature, Type};
+use biome_js_syntax::{TsDeclareFunctionDeclaration, TsFunctionType};
+use type_info::{TsFunctionSignature, Type, TypeId};
 
 use crate::{TResult, TypeAnalyzer};
 
 impl TypeAnalyzer {
-    pub fn analyze_ts_function_type(&self, node: &TsFunctionType) -> TResult<Type> {
+    pub fn analyze_ts_function_type(&self, node: &TsFunctionType) -> TResult<TypeId> {
         let mut type_params = vec![];
         if let Some(params) = node.type_parameters() {
             for p in params.items().into_iter().flatten() {
@@ -14,36 +14,32 @@ impl TypeAnalyzer {
         };
 
         let mut params = vec![];
+        let mut this_param = None;
 
         if let Ok(parameters) = node.parameters() {
             params = self.analyze_js_parameters(&parameters)?;
+            this_param = self.analyze_this_parameter(&parameters)?;
         }
 
         let return_type = if let Ok(ty) = node.return_type() {
-            match ty {
-                AnyTsReturnType::AnyTsType(ty) => {
-                    let ty = self.analyze_any_ts_types(&ty)?;
-                    Box::new(ty)
-                }
-                node => todo!("{:?}", node),
-            }
+            self.analyze_any_ts_return_type(&ty)?
         } else {
-            Box::new(Type::Unknown)
+            self.intern(Type::Unknown)
         };
 
-        Ok(Type::Function(TsFunctionSignature {
+        Ok(self.intern(Type::Function(TsFunctionSignature {
             type_params,
-            this_param: None,
+            this_param,
             params,
             return_type,
             is_async: false,
-        }))
+        })))
     }
 
     pub fn analyze_ts_declare_function_declaration(
         &self,
         node: &TsDeclareFunctionDeclaration,
-    ) -> TResult<Type> {
+    ) -> TResult<TypeId> {
         let mut type_params = vec![];
         if let Some(params) = node.type_parameters() {
             for p in params.items().into_iter().flatten() {
@@ -53,29 +49,25 @@ impl TypeAnalyzer {
         };
 
         let mut params = vec![];
+        let mut this_param = None;
 
         if let Ok(parameters) = node.parameters() {
             params = self.analyze_js_parameters(&parameters)?;
+            this_param = self.analyze_this_parameter(&parameters)?;
         }
 
         let return_type = if let Some(ty) = node.return_type_annotation() {
-            match ty.ty()? {
-                AnyTsReturnType::AnyTsType(ty) => {
-                    let ty = self.analyze_any_ts_types(&ty)?;
-                    Box::new(ty)
-                }
-                node => todo!("{:?}", node),
-            }
+            self.analyze_any_ts_return_type(&ty.ty()?)?
         } else {
-            Box::new(Type::Unknown)
+            self.intern(Type::Unknown)
         };
 
-        Ok(Type::Function(TsFunctionSignature {
+        Ok(self.intern(Type::Function(TsFunctionSignature {
             type_params,
-            this_param: None,
+            this_param,
             params,
             return_type,
             is_async: false,
-        }))
+        })))
     }
 }
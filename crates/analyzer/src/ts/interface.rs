@@ -1,15 +1,15 @@
 use biome_js_syntax::{AnyTsIdentifierBinding, TsInterfaceDeclaration};
-use type_info::{TsInterface, Type};
+use type_info::{TsInterface, Type, TypeId};
 
 use crate::{TResult, TypeAnalyzer};
 
 impl TypeAnalyzer {
-    pub fn analyze_ts_interface_declaration(&self, node: &TsInterfaceDeclaration) -> TResult<Type> {
-        let name = match node.id().unwrap() {
-            AnyTsIdentifierBinding::TsIdentifierBinding(bind) => {
-                bind.name_token().unwrap().text_trimmed().to_string()
-            }
-            _ => todo!(),
+    pub fn analyze_ts_interface_declaration(
+        &self,
+        node: &TsInterfaceDeclaration,
+    ) -> TResult<TypeId> {
+        let Some(name) = identifier_binding_name(&node.id()?) else {
+            return Ok(self.intern(Type::Unknown));
         };
 
         let mut type_params = vec![];
@@ -21,21 +21,38 @@ impl TypeAnalyzer {
             }
         }
 
+        let mut extends = vec![];
+        if let Some(clause) = node.extends_clause() {
+            for ty in clause.types().into_iter().flatten() {
+                extends.push(self.analyze_ts_name_with_type_arguments(&ty)?);
+            }
+        }
+
         let members = node.members();
         let mut properties = vec![];
         for m in members {
             let ty = match self.analyze_any_ts_type_member(&m) {
-                Ok(ty) => ty,
-                Err(_) => continue,
+                Ok(Some(ty)) => ty,
+                _ => continue,
             };
             properties.push(ty);
         }
 
-        Ok(Type::Interface(TsInterface {
+        Ok(self.intern(Type::Interface(TsInterface {
             name: name.to_string(),
-            extends: vec![],
+            extends,
             type_params,
+            type_args: vec![],
             properties,
-        }))
+        })))
+    }
+}
+
+pub fn identifier_binding_name(node: &AnyTsIdentifierBinding) -> Option<String> {
+    match node {
+        AnyTsIdentifierBinding::TsIdentifierBinding(bind) => {
+            Some(bind.name_token().ok()?.text_trimmed().to_string())
+        }
+        _ => None,
     }
 }
@@ -1,6 +1,9 @@
+mod alias;
 mod annotation;
 mod function;
 mod interface;
 mod object;
 mod reference;
 mod type_param;
+
+pub(crate) use interface::identifier_binding_name;
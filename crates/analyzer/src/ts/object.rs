@@ -1,86 +1,171 @@
-use biome_js_syntax::{AnyJsObjectMemberName, AnyTsReturnType, AnyTsTypeMember};
-use type_info::{TsFunctionSignature, TsInterfaceProperty, Type};
+use biome_js_syntax::{
+    AnyJsFormalParameter, AnyJsObjectMemberName, AnyTsTypeMember, JsParameters, TsTypeParameters,
+};
+use type_info::{TsFunctionSignature, TsInterfaceProperty, TsKeywordTypeKind, Type, TypeId};
 
 use crate::{TResult, TypeAnalyzer};
 
 impl TypeAnalyzer {
+    /// Returns `None` for members that can't be looked up by name, e.g. the
+    /// computed `[Symbol.iterator]()`. Call, construct and index signatures
+    /// are stored under tsc's internal names `__call`, `__new` and `__index`.
     pub fn analyze_any_ts_type_member(
         &self,
         node: &AnyTsTypeMember,
-    ) -> TResult<TsInterfaceProperty> {
+    ) -> TResult<Option<TsInterfaceProperty>> {
         let ty = match node {
             AnyTsTypeMember::TsPropertySignatureTypeMember(m) => {
-                let name = match m.name().unwrap() {
-                    AnyJsObjectMemberName::JsLiteralMemberName(member) => {
-                        member.value().unwrap().text_trimmed().to_string()
-                    }
-                    _ => todo!("member name {:?}", m.name()),
+                let Some(name) = member_name(&m.name()?) else {
+                    return Ok(None);
                 };
                 let is_optional = m.optional_token().is_some();
                 let is_readonly = m.readonly_token().is_some();
-                if let Some(ann) = m.type_annotation() {
-                    let type_info = self.analyze_type_annotation(ann);
-                    TsInterfaceProperty {
-                        name: name.to_string(),
-                        type_info,
-                        is_optional,
-                        is_readonly,
-                    }
-                } else {
-                    todo!()
+                let type_info = match m.type_annotation() {
+                    Some(ann) => self.analyze_type_annotation(ann),
+                    None => self.intern(Type::KeywordType(TsKeywordTypeKind::Any)),
+                };
+                TsInterfaceProperty {
+                    name,
+                    type_info,
+                    is_optional,
+                    is_readonly,
                 }
             }
             AnyTsTypeMember::TsMethodSignatureTypeMember(member) => {
-                let name = match member.name()? {
-                    AnyJsObjectMemberName::JsLiteralMemberName(literal) => {
-                        literal.value().unwrap().text_trimmed().to_string()
-                    }
-                    node => todo!("{:?}", node),
+                let Some(name) = member_name(&member.name()?) else {
+                    return Ok(None);
                 };
 
                 let is_optional = member.optional_token().is_some();
 
-                let mut type_params = vec![];
-                if let Some(ty_params) = member.type_parameters() {
-                    for param in ty_params.items().into_iter().flatten() {
-                        let param = self.analyze_type_param(&param)?;
-                        type_params.push(param);
-                    }
+                let return_type = match member.return_type_annotation() {
+                    Some(ty) => self.analyze_any_ts_return_type(&ty.ty()?)?,
+                    None => self.intern(Type::Unknown),
                 };
 
-                let mut params = vec![];
-                if let Ok(parameter) = member.parameters() {
-                    params = self.analyze_js_parameters(&parameter)?;
+                TsInterfaceProperty {
+                    name,
+                    type_info: self.analyze_signature(
+                        member.type_parameters(),
+                        member.parameters().ok(),
+                        return_type,
+                    )?,
+                    is_optional,
+                    is_readonly: false,
                 }
-
-                let return_type = if let Some(ty) = member.return_type_annotation() {
-                    let ret_ty = ty.ty()?;
-                    match ret_ty {
-                        AnyTsReturnType::AnyTsType(ty) => {
-                            let ty = self.analyze_any_ts_types(&ty)?;
-                            Box::new(ty)
-                        }
-                        node => todo!("{:?}", node),
-                    }
-                } else {
-                    Box::new(Type::Unknown)
+            }
+            AnyTsTypeMember::TsCallSignatureTypeMember(member) => {
+                let return_type = match member.return_type_annotation() {
+                    Some(ty) => self.analyze_any_ts_return_type(&ty.ty()?)?,
+                    None => self.intern(Type::Unknown),
                 };
-
                 TsInterfaceProperty {
-                    name: name.to_string(),
-                    type_info: Type::Function(TsFunctionSignature {
-                        type_params,
-                        this_param: None,
-                        params,
+                    name: "__call".to_string(),
+                    type_info: self.analyze_signature(
+                        member.type_parameters(),
+                        member.parameters().ok(),
                         return_type,
-                        is_async: false,
-                    }),
-                    is_optional,
+                    )?,
+                    is_optional: false,
+                    is_readonly: true,
+                }
+            }
+            AnyTsTypeMember::TsConstructSignatureTypeMember(member) => {
+                let return_type = match member.type_annotation() {
+                    Some(ann) => self.analyze_type_annotation(ann),
+                    None => self.intern(Type::Unknown),
+                };
+                TsInterfaceProperty {
+                    name: "__new".to_string(),
+                    type_info: self.analyze_signature(
+                        member.type_parameters(),
+                        member.parameters().ok(),
+                        return_type,
+                    )?,
+                    is_optional: false,
+                    is_readonly: true,
+                }
+            }
+            AnyTsTypeMember::TsIndexSignatureTypeMember(member) => TsInterfaceProperty {
+                name: "__index".to_string(),
+                type_info: self.analyze_type_annotation(member.type_annotation()?),
+                is_optional: false,
+                is_readonly: member.readonly_token().is_some(),
+            },
+            AnyTsTypeMember::TsGetterSignatureTypeMember(member) => {
+                let Some(name) = member_name(&member.name()?) else {
+                    return Ok(None);
+                };
+                let type_info = match member.type_annotation() {
+                    Some(ann) => self.analyze_type_annotation(ann),
+                    None => self.intern(Type::Unknown),
+                };
+                TsInterfaceProperty {
+                    name,
+                    type_info,
+                    is_optional: false,
+                    is_readonly: true,
+                }
+            }
+            AnyTsTypeMember::TsSetterSignatureTypeMember(member) => {
+                let Some(name) = member_name(&member.name()?) else {
+                    return Ok(None);
+                };
+                let type_info = match member.parameter()? {
+                    AnyJsFormalParameter::JsFormalParameter(param) => {
+                        param.type_annotation().map_or_else(
+                            || self.intern(Type::Unknown),
+                            |ann| self.analyze_type_annotation(ann),
+                        )
+                    }
+                    _ => self.intern(Type::Unknown),
+                };
+                TsInterfaceProperty {
+                    name,
+                    type_info,
+                    is_optional: false,
                     is_readonly: false,
                 }
             }
-            _ => todo!("{:?}", node),
+            _ => return Ok(None),
         };
-        Ok(ty)
+        Ok(Some(ty))
+    }
+
+    fn analyze_signature(
+        &self,
+        type_parameters: Option<TsTypeParameters>,
+        parameters: Option<JsParameters>,
+        return_type: TypeId,
+    ) -> TResult<TypeId> {
+        let mut type_params = vec![];
+        if let Some(ty_params) = type_parameters {
+            for param in ty_params.items().into_iter().flatten() {
+                let param = self.analyze_type_param(&param)?;
+                type_params.push(param);
+            }
+        };
+
+        let mut params = vec![];
+        let mut this_param = None;
+        if let Some(parameter) = parameters {
+            params = self.analyze_js_parameters(&parameter)?;
+            this_param = self.analyze_this_parameter(&parameter)?;
+        }
+
+        Ok(self.intern(Type::Function(TsFunctionSignature {
+            type_params,
+            this_param,
+            params,
+            return_type,
+            is_async: false,
+        })))
+    }
+}
+
+fn member_name(name: &AnyJsObjectMemberName) -> Option<String> {
+    match name {
+        AnyJsObjectMemberName::JsLiteralMemberName(_) => Some(name.name()?.text().to_string()),
+        _ => None,
     }
 }
@@ -1,23 +1,38 @@
-use biome_js_syntax::{AnyTsName, TsReferenceType};
-use type_info::{TsTypeRef, Type};
+use biome_js_syntax::{AnyTsName, TsNameWithTypeArguments, TsReferenceType, TsTypeArguments};
+use biome_rowan::AstNode;
+use type_info::{TsTypeRef, Type, TypeId};
 
 use crate::{TResult, TypeAnalyzer};
 
 impl TypeAnalyzer {
-    pub fn analyze_ts_type_ref(&self, node: &TsReferenceType) -> TResult<Type> {
-        let name = match node.name()? {
+    pub fn analyze_ts_type_ref(&self, node: &TsReferenceType) -> TResult<TypeId> {
+        self.analyze_type_ref_parts(&node.name()?, node.type_arguments())
+    }
+
+    pub fn analyze_ts_name_with_type_arguments(
+        &self,
+        node: &TsNameWithTypeArguments,
+    ) -> TResult<TypeId> {
+        self.analyze_type_ref_parts(&node.name()?, node.type_arguments())
+    }
+
+    fn analyze_type_ref_parts(
+        &self,
+        name: &AnyTsName,
+        type_arguments: Option<TsTypeArguments>,
+    ) -> TResult<TypeId> {
+        let name = match name {
             AnyTsName::JsReferenceIdentifier(ident) => {
                 let value = ident.value_token()?;
                 value.text_trimmed().to_string()
             }
-            AnyTsName::TsQualifiedName(qual) => {
-                todo!("qualified name {:?}", qual)
-            }
+            // Namespaces aren't modelled, so `Intl.Collator` is looked up by its full text.
+            AnyTsName::TsQualifiedName(qual) => qual.syntax().text_trimmed().to_string(),
         };
 
         let mut type_params = vec![];
 
-        if let Some(args) = node.type_arguments() {
+        if let Some(args) = type_arguments {
             for arg in args.ts_type_argument_list().into_iter().flatten() {
                 let ty = self.analyze_any_ts_types(&arg);
                 match ty {
@@ -26,9 +41,9 @@ impl TypeAnalyzer {
                 }
             }
         }
-        Ok(Type::TypeRef(TsTypeRef {
+        Ok(self.intern(Type::TypeRef(TsTypeRef {
             name: name.to_owned(),
             type_params,
-        }))
+        })))
     }
 }
@@ -5,12 +5,7 @@ use crate::{TResult, TypeAnalyzer};
 
 impl TypeAnalyzer {
     pub fn analyze_type_param(&self, param: &TsTypeParameter) -> TResult<TypeParam> {
-        let name = param
-            .name()
-            .unwrap()
-            .ident_token()?
-            .text_trimmed()
-            .to_string();
+        let name = param.name()?.ident_token()?.text_trimmed().to_string();
 
         let mut constraint = None;
         let mut default = None;
@@ -2,9 +2,13 @@
 mod tests {
     use std::path::PathBuf;
 
-    use analyzer::TypeAnalyzer;
+    use analyzer::{DeclarationKind, TypeAnalyzer};
     use biome_js_parser::parse;
-    use biome_js_syntax::JsFileSource;
+    use biome_js_syntax::{
+        AnyJsExpression, JsCallExpression, JsExpressionStatement, JsFileSource, JsParameters,
+        JsReturnStatement, JsThisExpression, JsVariableDeclarator,
+    };
+    use biome_rowan::AstNode;
     use symbol::Symbol;
     use type_info::Type;
     use type_info::*;
@@ -16,11 +20,15 @@ mod tests {
             panic!("Failed to parse source code: {:?}", parsed.diagnostics());
         }
         let root = parsed.tree();
-        let mut analyzer = TypeAnalyzer::new(vec![]);
+        let mut analyzer = TypeAnalyzer::new(vec![]).unwrap();
         analyzer.visit(&root);
         analyzer
     }
 
+    fn symbol_type(analyzer: &TypeAnalyzer, name: &str) -> TypeId {
+        analyzer.get_symbol(name).unwrap().ty
+    }
+
     #[test]
     fn test_keyword_types() {
         let src = r#"declare const a: number;
@@ -40,7 +48,7 @@ declare const j: any;
             analyzer.get_symbol("a").unwrap(),
             &Symbol::new(
                 "a".to_string(),
-                Type::KeywordType(TsKeywordTypeKind::Number)
+                analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Number))
             )
         );
 
@@ -48,7 +56,7 @@ declare const j: any;
             analyzer.get_symbol("b").unwrap(),
             &Symbol::new(
                 "b".to_string(),
-                Type::KeywordType(TsKeywordTypeKind::String)
+                analyzer.intern(Type::KeywordType(TsKeywordTypeKind::String))
             )
         );
 
@@ -56,7 +64,7 @@ declare const j: any;
             analyzer.get_symbol("c").unwrap(),
             &Symbol::new(
                 "c".to_string(),
-                Type::KeywordType(TsKeywordTypeKind::Boolean)
+                analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Boolean))
             )
         );
 
@@ -64,7 +72,7 @@ declare const j: any;
             analyzer.get_symbol("d").unwrap(),
             &Symbol::new(
                 "d".to_string(),
-                Type::KeywordType(TsKeywordTypeKind::BigInt)
+                analyzer.intern(Type::KeywordType(TsKeywordTypeKind::BigInt))
             )
         );
 
@@ -72,7 +80,7 @@ declare const j: any;
             analyzer.get_symbol("e").unwrap(),
             &Symbol::new(
                 "e".to_string(),
-                Type::KeywordType(TsKeywordTypeKind::Symbol)
+                analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Symbol))
             )
         );
 
@@ -80,7 +88,7 @@ declare const j: any;
             analyzer.get_symbol("f").unwrap(),
             &Symbol::new(
                 "f".to_string(),
-                Type::KeywordType(TsKeywordTypeKind::Null)
+                analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Null))
             )
         );
 
@@ -88,7 +96,7 @@ declare const j: any;
             analyzer.get_symbol("g").unwrap(),
             &Symbol::new(
                 "g".to_string(),
-                Type::KeywordType(TsKeywordTypeKind::Undefined)
+                analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Undefined))
             )
         );
 
@@ -96,7 +104,7 @@ declare const j: any;
             analyzer.get_symbol("h").unwrap(),
             &Symbol::new(
                 "h".to_string(),
-                Type::KeywordType(TsKeywordTypeKind::Never)
+                analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Never))
             )
         );
 
@@ -104,7 +112,7 @@ declare const j: any;
             analyzer.get_symbol("i").unwrap(),
             &Symbol::new(
                 "i".to_string(),
-                Type::KeywordType(TsKeywordTypeKind::Void)
+                analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Void))
             )
         );
 
@@ -112,7 +120,7 @@ declare const j: any;
             analyzer.get_symbol("j").unwrap(),
             &Symbol::new(
                 "j".to_string(),
-                Type::KeywordType(TsKeywordTypeKind::Any)
+                analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Any))
             )
         );
     }
@@ -133,26 +141,26 @@ declare const j: any;
             analyzer.get_symbol("obj").unwrap(),
             &Symbol::new(
                 "obj".to_string(),
-                Type::Literal(TsLiteralTypeKind::Object(ObjectLiteral {
+                analyzer.intern(Type::Literal(TsLiteralTypeKind::Object(ObjectLiteral {
                     properties: vec![
                         ObjectPropertyType {
                             name: "num".to_string(),
-                            type_info: Type::Literal(TsLiteralTypeKind::Number(42))
+                            type_info: analyzer.intern(Type::Literal(TsLiteralTypeKind::Number(42)))
                         },
                         ObjectPropertyType {
                             name: "str".to_string(),
-                            type_info: Type::Literal(TsLiteralTypeKind::String(
+                            type_info: analyzer.intern(Type::Literal(TsLiteralTypeKind::String(
                                 "hello".to_string()
-                            ))
+                            )))
                         },
                         ObjectPropertyType {
                             name: "bool".to_string(),
-                            type_info: Type::Literal(TsLiteralTypeKind::Boolean(
+                            type_info: analyzer.intern(Type::Literal(TsLiteralTypeKind::Boolean(
                                 BoolLiteral::True
-                            ))
+                            )))
                         }
                     ]
-                }))
+                })))
             )
         )
     }
@@ -174,7 +182,7 @@ const h = false;
             analyzer.get_symbol("a").unwrap(),
             &Symbol::new(
                 "a".to_string(),
-                Type::Literal(TsLiteralTypeKind::Number(1))
+                analyzer.intern(Type::Literal(TsLiteralTypeKind::Number(1)))
             )
         );
 
@@ -182,7 +190,7 @@ const h = false;
             analyzer.get_symbol("b").unwrap(),
             &Symbol::new(
                 "b".to_string(),
-                Type::Literal(TsLiteralTypeKind::String("'hello'".to_string()))
+                analyzer.intern(Type::Literal(TsLiteralTypeKind::String("'hello'".to_string())))
             )
         );
 
@@ -190,7 +198,7 @@ const h = false;
             analyzer.get_symbol("c").unwrap(),
             &Symbol::new(
                 "c".to_string(),
-                Type::Literal(TsLiteralTypeKind::Boolean(BoolLiteral::True))
+                analyzer.intern(Type::Literal(TsLiteralTypeKind::Boolean(BoolLiteral::True)))
             )
         );
 
@@ -198,7 +206,7 @@ const h = false;
             analyzer.get_symbol("d").unwrap(),
             &Symbol::new(
                 "d".to_string(),
-                Type::Literal(TsLiteralTypeKind::Boolean(BoolLiteral::False))
+                analyzer.intern(Type::Literal(TsLiteralTypeKind::Boolean(BoolLiteral::False)))
             )
         );
 
@@ -206,7 +214,7 @@ const h = false;
             analyzer.get_symbol("e").unwrap(),
             &Symbol::new(
                 "e".to_string(),
-                Type::Literal(TsLiteralTypeKind::Number(1))
+                analyzer.intern(Type::Literal(TsLiteralTypeKind::Number(1)))
             )
         );
 
@@ -214,7 +222,7 @@ const h = false;
             analyzer.get_symbol("f").unwrap(),
             &Symbol::new(
                 "f".to_string(),
-                Type::Literal(TsLiteralTypeKind::String("hello".to_string()))
+                analyzer.intern(Type::Literal(TsLiteralTypeKind::String("hello".to_string())))
             )
         );
 
@@ -222,7 +230,7 @@ const h = false;
             analyzer.get_symbol("g").unwrap(),
             &Symbol::new(
                 "g".to_string(),
-                Type::Literal(TsLiteralTypeKind::Boolean(BoolLiteral::True))
+                analyzer.intern(Type::Literal(TsLiteralTypeKind::Boolean(BoolLiteral::True)))
             )
         );
 
@@ -230,7 +238,7 @@ const h = false;
             analyzer.get_symbol("h").unwrap(),
             &Symbol::new(
                 "h".to_string(),
-                Type::Literal(TsLiteralTypeKind::Boolean(BoolLiteral::False))
+                analyzer.intern(Type::Literal(TsLiteralTypeKind::Boolean(BoolLiteral::False)))
             )
         );
     }
@@ -260,37 +268,38 @@ const h = false;
             analyzer.get_symbol("Person").unwrap(),
             &Symbol::new(
                 "Person".to_string(),
-                Type::Interface(TsInterface {
+                analyzer.intern(Type::Interface(TsInterface {
                     name: "Person".to_string(),
                     extends: vec![],
+                    type_args: vec![],
                     type_params: vec![],
                     properties: vec![
                         TsInterfaceProperty {
                             name: "name".to_string(),
-                            type_info: Type::KeywordType(TsKeywordTypeKind::String),
+                            type_info: analyzer.intern(Type::KeywordType(TsKeywordTypeKind::String)),
                             is_optional: false,
                             is_readonly: false,
                         },
                         TsInterfaceProperty {
                             name: "age".to_string(),
-                            type_info: Type::KeywordType(TsKeywordTypeKind::Number),
+                            type_info: analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Number)),
                             is_optional: false,
                             is_readonly: false,
                         },
                         TsInterfaceProperty {
                             name: "foo".to_string(),
-                            type_info: Type::KeywordType(TsKeywordTypeKind::String),
+                            type_info: analyzer.intern(Type::KeywordType(TsKeywordTypeKind::String)),
                             is_optional: true,
                             is_readonly: false,
                         },
                         TsInterfaceProperty {
                             name: "bar".to_string(),
-                            type_info: Type::KeywordType(TsKeywordTypeKind::Boolean),
+                            type_info: analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Boolean)),
                             is_optional: false,
                             is_readonly: true,
                         }
                     ]
-                })
+                }))
             )
         );
 
@@ -298,80 +307,84 @@ const h = false;
             analyzer.get_symbol("MethodSignature").unwrap(),
             &Symbol::new(
                 "MethodSignature".to_string(),
-                Type::Interface(TsInterface {
+                analyzer.intern(Type::Interface(TsInterface {
                     name: "MethodSignature".to_string(),
                     extends: vec![],
+                    type_args: vec![],
                     type_params: vec![],
                     properties: vec![
                         TsInterfaceProperty {
                             name: "basic".to_string(),
-                            type_info: Type::Function(TsFunctionSignature {
+                            type_info: analyzer.intern(Type::Function(TsFunctionSignature {
                                 params: vec![],
-                                return_type: Box::new(Type::KeywordType(
+                                return_type: analyzer.intern(Type::KeywordType(
                                     TsKeywordTypeKind::Void
                                 )),
                                 type_params: vec![],
                                 this_param: None,
                                 is_async: false,
-                            }),
+                            })),
                             is_optional: false,
                             is_readonly: false,
                         },
                         TsInterfaceProperty {
                             name: "withParams".to_string(),
-                            type_info: Type::Function(TsFunctionSignature {
+                            type_info: analyzer.intern(Type::Function(TsFunctionSignature {
                                 params: vec![
                                     FunctionParam {
                                         name: "x".to_string(),
-                                        param_type: Type::KeywordType(
+                                        param_type: analyzer.intern(Type::KeywordType(
                                             TsKeywordTypeKind::Number
-                                        ),
-                                        is_optional: false
+                                        )),
+                                        is_optional: false,
+                                        is_rest: false
                                     },
                                     FunctionParam {
                                         name: "y".to_string(),
-                                        param_type: Type::KeywordType(
+                                        param_type: analyzer.intern(Type::KeywordType(
                                             TsKeywordTypeKind::String
-                                        ),
-                                        is_optional: false
+                                        )),
+                                        is_optional: false,
+                                        is_rest: false
                                     }
                                 ],
-                                return_type: Box::new(Type::KeywordType(
+                                return_type: analyzer.intern(Type::KeywordType(
                                     TsKeywordTypeKind::Boolean
                                 )),
                                 type_params: vec![],
                                 this_param: None,
                                 is_async: false,
-                            }),
+                            })),
                             is_optional: false,
                             is_readonly: false,
                         },
                         TsInterfaceProperty {
                             name: "optional".to_string(),
-                            type_info: Type::Function(TsFunctionSignature {
+                            type_info: analyzer.intern(Type::Function(TsFunctionSignature {
                                 params: vec![],
-                                return_type: Box::new(Type::KeywordType(
+                                return_type: analyzer.intern(Type::KeywordType(
                                     TsKeywordTypeKind::Void
                                 )),
                                 type_params: vec![],
                                 this_param: None,
                                 is_async: false
-                            }),
+                            })),
                             is_optional: true,
                             is_readonly: false,
                         },
                         TsInterfaceProperty {
                             name: "generic".to_string(),
-                            type_info: Type::Function(TsFunctionSignature {
+                            type_info: analyzer.intern(Type::Function(TsFunctionSignature {
                                 params: vec![FunctionParam {
                                     name: "value".to_string(),
-                                    param_type: Type::TypeRef(TsTypeRef {
+                                    param_type: analyzer.intern(Type::TypeRef(TsTypeRef {
                                         name: "T".to_string(),
                                         type_params: vec![]
-                                    }),
-                                    is_optional: false
+                                    })),
+                                    is_optional: false,
+                                    is_rest: false
                                 }],
-                                return_type: Box::new(Type::TypeRef(TsTypeRef {
+                                return_type: analyzer.intern(Type::TypeRef(TsTypeRef {
                                     name: "T".to_string(),
                                     type_params: vec![]
                                 })),
@@ -383,32 +396,32 @@ const h = false;
 
                                 this_param: None,
                                 is_async: false,
-                            }),
+                            })),
                             is_optional: false,
                             is_readonly: false,
                         },
                         TsInterfaceProperty {
                             name: "complex".to_string(),
-                            type_info: Type::Function(TsFunctionSignature {
+                            type_info: analyzer.intern(Type::Function(TsFunctionSignature {
                                 params: vec![],
-                                return_type: Box::new(Type::Union(vec![
-                                    Type::KeywordType(TsKeywordTypeKind::String),
-                                    Type::TypeRef(TsTypeRef {
+                                return_type: analyzer.intern(Type::Union(vec![
+                                    analyzer.intern(Type::KeywordType(TsKeywordTypeKind::String)),
+                                    analyzer.intern(Type::TypeRef(TsTypeRef {
                                         name: "Promise".to_string(),
-                                        type_params: vec![Type::KeywordType(
+                                        type_params: vec![analyzer.intern(Type::KeywordType(
                                             TsKeywordTypeKind::Number
-                                        )]
-                                    })
+                                        ))]
+                                    }))
                                 ])),
                                 type_params: vec![],
                                 this_param: None,
                                 is_async: false
-                            }),
+                            })),
                             is_optional: false,
                             is_readonly: false,
                         }
                     ]
-                })
+                }))
             )
         );
     }
@@ -432,9 +445,10 @@ const h = false;
             analyzer.get_symbol("Box").unwrap(),
             &Symbol::new(
                 "Box".to_string(),
-                Type::Interface(TsInterface {
+                analyzer.intern(Type::Interface(TsInterface {
                     name: "Box".to_string(),
                     extends: vec![],
+                    type_args: vec![],
                     type_params: vec![TypeParam {
                         name: "T".to_string(),
                         constraint: None,
@@ -442,14 +456,14 @@ const h = false;
                     }],
                     properties: vec![TsInterfaceProperty {
                         name: "value".to_string(),
-                        type_info: Type::TypeRef(TsTypeRef {
+                        type_info: analyzer.intern(Type::TypeRef(TsTypeRef {
                             name: "T".to_string(),
                             type_params: vec![]
-                        }),
+                        })),
                         is_optional: false,
                         is_readonly: false,
                     }]
-                })
+                }))
             )
         );
 
@@ -457,42 +471,43 @@ const h = false;
             analyzer.get_symbol("Pair").unwrap(),
             &Symbol::new(
                 "Pair".to_string(),
-                Type::Interface(TsInterface {
+                analyzer.intern(Type::Interface(TsInterface {
                     name: "Pair".to_string(),
                     extends: vec![],
+                    type_args: vec![],
                     type_params: vec![
                         TypeParam {
                             name: "T".to_string(),
                             constraint: None,
-                            default: Some(Type::KeywordType(TsKeywordTypeKind::String)),
+                            default: Some(analyzer.intern(Type::KeywordType(TsKeywordTypeKind::String))),
                         },
                         TypeParam {
                             name: "U".to_string(),
-                            constraint: Some(Type::KeywordType(TsKeywordTypeKind::Number)),
+                            constraint: Some(analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Number))),
                             default: None,
                         }
                     ],
                     properties: vec![
                         TsInterfaceProperty {
                             name: "first".to_string(),
-                            type_info: Type::TypeRef(TsTypeRef {
+                            type_info: analyzer.intern(Type::TypeRef(TsTypeRef {
                                 name: "T".to_string(),
                                 type_params: vec![]
-                            }),
+                            })),
                             is_optional: false,
                             is_readonly: false,
                         },
                         TsInterfaceProperty {
                             name: "second".to_string(),
-                            type_info: Type::TypeRef(TsTypeRef {
+                            type_info: analyzer.intern(Type::TypeRef(TsTypeRef {
                                 name: "U".to_string(),
                                 type_params: vec![]
-                            }),
+                            })),
                             is_optional: false,
                             is_readonly: false,
                         }
                     ]
-                })
+                }))
             )
         );
     }
@@ -519,10 +534,10 @@ const h = false;
             analyzer.get_symbol("ref").unwrap(),
             &Symbol::new(
                 "ref".to_string(),
-                Type::TypeRef(TsTypeRef {
+                analyzer.intern(Type::TypeRef(TsTypeRef {
                     name: "Array".to_string(),
                     type_params: vec![]
-                })
+                }))
             )
         );
 
@@ -530,10 +545,10 @@ const h = false;
             analyzer.get_symbol("withTypeArg").unwrap(),
             &Symbol::new(
                 "withTypeArg".to_string(),
-                Type::TypeRef(TsTypeRef {
+                analyzer.intern(Type::TypeRef(TsTypeRef {
                     name: "Array".to_string(),
-                    type_params: vec![Type::KeywordType(TsKeywordTypeKind::Number)]
-                })
+                    type_params: vec![analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Number))]
+                }))
             )
         );
 
@@ -541,13 +556,13 @@ const h = false;
             analyzer.get_symbol("nested").unwrap(),
             &Symbol::new(
                 "nested".to_string(),
-                Type::TypeRef(TsTypeRef {
+                analyzer.intern(Type::TypeRef(TsTypeRef {
                     name: "Array".to_string(),
-                    type_params: vec![Type::TypeRef(TsTypeRef {
+                    type_params: vec![analyzer.intern(Type::TypeRef(TsTypeRef {
                         name: "Array".to_string(),
-                        type_params: vec![Type::KeywordType(TsKeywordTypeKind::String)]
-                    })]
-                })
+                        type_params: vec![analyzer.intern(Type::KeywordType(TsKeywordTypeKind::String))]
+                    }))]
+                }))
             )
         );
 
@@ -555,10 +570,10 @@ const h = false;
             analyzer.get_symbol("person").unwrap(),
             &Symbol::new(
                 "person".to_string(),
-                Type::TypeRef(TsTypeRef {
+                analyzer.intern(Type::TypeRef(TsTypeRef {
                     name: "Person".to_string(),
                     type_params: vec![]
-                })
+                }))
             )
         );
     }
@@ -578,10 +593,10 @@ const h = false;
             analyzer.get_symbol("basic").unwrap(),
             &Symbol::new(
                 "basic".to_string(),
-                Type::Union(vec![
-                    Type::KeywordType(TsKeywordTypeKind::String),
-                    Type::KeywordType(TsKeywordTypeKind::Number)
-                ])
+                analyzer.intern(Type::Union(vec![
+                    analyzer.intern(Type::KeywordType(TsKeywordTypeKind::String)),
+                    analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Number))
+                ]))
             )
         );
 
@@ -589,11 +604,11 @@ const h = false;
             analyzer.get_symbol("withLiteral").unwrap(),
             &Symbol::new(
                 "withLiteral".to_string(),
-                Type::Union(vec![
-                    Type::Literal(TsLiteralTypeKind::String("\"foo\"".to_string())),
-                    Type::Literal(TsLiteralTypeKind::Number(42)),
-                    Type::Literal(TsLiteralTypeKind::Boolean(BoolLiteral::True))
-                ])
+                analyzer.intern(Type::Union(vec![
+                    analyzer.intern(Type::Literal(TsLiteralTypeKind::String("\"foo\"".to_string()))),
+                    analyzer.intern(Type::Literal(TsLiteralTypeKind::Number(42))),
+                    analyzer.intern(Type::Literal(TsLiteralTypeKind::Boolean(BoolLiteral::True)))
+                ]))
             )
         );
 
@@ -601,16 +616,16 @@ const h = false;
             analyzer.get_symbol("withRef").unwrap(),
             &Symbol::new(
                 "withRef".to_string(),
-                Type::Union(vec![
-                    Type::TypeRef(TsTypeRef {
+                analyzer.intern(Type::Union(vec![
+                    analyzer.intern(Type::TypeRef(TsTypeRef {
                         name: "Array".to_string(),
-                        type_params: vec![Type::KeywordType(TsKeywordTypeKind::String)]
-                    }),
-                    Type::TypeRef(TsTypeRef {
+                        type_params: vec![analyzer.intern(Type::KeywordType(TsKeywordTypeKind::String))]
+                    })),
+                    analyzer.intern(Type::TypeRef(TsTypeRef {
                         name: "Promise".to_string(),
-                        type_params: vec![Type::KeywordType(TsKeywordTypeKind::Number)]
-                    })
-                ])
+                        type_params: vec![analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Number))]
+                    }))
+                ]))
             )
         );
 
@@ -618,16 +633,16 @@ const h = false;
             analyzer.get_symbol("nested").unwrap(),
             &Symbol::new(
                 "nested".to_string(),
-                Type::Union(vec![
-                    Type::Union(vec![
-                        Type::KeywordType(TsKeywordTypeKind::String),
-                        Type::KeywordType(TsKeywordTypeKind::Number)
-                    ]),
-                    Type::Union(vec![
-                        Type::KeywordType(TsKeywordTypeKind::Boolean),
-                        Type::KeywordType(TsKeywordTypeKind::Null)
-                    ])
-                ])
+                analyzer.intern(Type::Union(vec![
+                    analyzer.intern(Type::Union(vec![
+                        analyzer.intern(Type::KeywordType(TsKeywordTypeKind::String)),
+                        analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Number))
+                    ])),
+                    analyzer.intern(Type::Union(vec![
+                        analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Boolean)),
+                        analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Null))
+                    ]))
+                ]))
             )
         );
     }
@@ -647,13 +662,13 @@ const h = false;
             analyzer.get_symbol("basic").unwrap(),
             &Symbol::new(
                 "basic".to_string(),
-                Type::Function(TsFunctionSignature {
+                analyzer.intern(Type::Function(TsFunctionSignature {
                     params: vec![],
-                    return_type: Box::new(Type::KeywordType(TsKeywordTypeKind::Void)),
+                    return_type: analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Void)),
                     type_params: vec![],
                     this_param: None,
                     is_async: false,
-                })
+                }))
             )
         );
 
@@ -661,24 +676,26 @@ const h = false;
             analyzer.get_symbol("withParams").unwrap(),
             &Symbol::new(
                 "withParams".to_string(),
-                Type::Function(TsFunctionSignature {
+                analyzer.intern(Type::Function(TsFunctionSignature {
                     params: vec![
                         FunctionParam {
                             name: "x".to_string(),
-                            param_type: Type::KeywordType(TsKeywordTypeKind::Number),
-                            is_optional: false
+                            param_type: analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Number)),
+                            is_optional: false,
+                            is_rest: false
                         },
                         FunctionParam {
                             name: "y".to_string(),
-                            param_type: Type::KeywordType(TsKeywordTypeKind::String),
-                            is_optional: false
+                            param_type: analyzer.intern(Type::KeywordType(TsKeywordTypeKind::String)),
+                            is_optional: false,
+                            is_rest: false
                         }
                     ],
-                    return_type: Box::new(Type::KeywordType(TsKeywordTypeKind::Boolean)),
+                    return_type: analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Boolean)),
                     type_params: vec![],
                     this_param: None,
                     is_async: false,
-                })
+                }))
             )
         );
 
@@ -686,17 +703,18 @@ const h = false;
             analyzer.get_symbol("withOptional").unwrap(),
             &Symbol::new(
                 "withOptional".to_string(),
-                Type::Function(TsFunctionSignature {
+                analyzer.intern(Type::Function(TsFunctionSignature {
                     params: vec![FunctionParam {
                         name: "x".to_string(),
-                        param_type: Type::KeywordType(TsKeywordTypeKind::Number),
-                        is_optional: true
+                        param_type: analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Number)),
+                        is_optional: true,
+                        is_rest: false
                     }],
-                    return_type: Box::new(Type::KeywordType(TsKeywordTypeKind::String)),
+                    return_type: analyzer.intern(Type::KeywordType(TsKeywordTypeKind::String)),
                     type_params: vec![],
                     this_param: None,
                     is_async: false,
-                })
+                }))
             )
         );
 
@@ -704,16 +722,17 @@ const h = false;
             analyzer.get_symbol("generic").unwrap(),
             &Symbol::new(
                 "generic".to_string(),
-                Type::Function(TsFunctionSignature {
+                analyzer.intern(Type::Function(TsFunctionSignature {
                     params: vec![FunctionParam {
                         name: "value".to_string(),
-                        param_type: Type::TypeRef(TsTypeRef {
+                        param_type: analyzer.intern(Type::TypeRef(TsTypeRef {
                             name: "T".to_string(),
                             type_params: vec![]
-                        }),
-                        is_optional: false
+                        })),
+                        is_optional: false,
+                        is_rest: false
                     }],
-                    return_type: Box::new(Type::TypeRef(TsTypeRef {
+                    return_type: analyzer.intern(Type::TypeRef(TsTypeRef {
                         name: "T".to_string(),
                         type_params: vec![]
                     })),
@@ -724,7 +743,7 @@ const h = false;
                     }],
                     this_param: None,
                     is_async: false,
-                })
+                }))
             )
         );
     }
@@ -739,16 +758,16 @@ const h = false;
             analyzer.get_symbol("foo").unwrap(),
             &Symbol::new(
                 "foo".to_string(),
-                Type::Function(TsFunctionSignature {
+                analyzer.intern(Type::Function(TsFunctionSignature {
                     params: vec![],
-                    return_type: Box::new(Type::TypeRef(TsTypeRef {
+                    return_type: analyzer.intern(Type::TypeRef(TsTypeRef {
                         name: "Promise".to_string(),
-                        type_params: vec![Type::KeywordType(TsKeywordTypeKind::String)]
+                        type_params: vec![analyzer.intern(Type::KeywordType(TsKeywordTypeKind::String))]
                     })),
                     type_params: vec![],
                     this_param: None,
                     is_async: false,
-                })
+                }))
             )
         );
     }
@@ -769,16 +788,16 @@ const h = false;
             symbol,
             &Symbol::new(
                 "test".to_string(),
-                Type::Function(TsFunctionSignature {
+                analyzer.intern(Type::Function(TsFunctionSignature {
                     params: vec![],
-                    return_type: Box::new(Type::TypeRef(TsTypeRef {
+                    return_type: analyzer.intern(Type::TypeRef(TsTypeRef {
                         name: "Promise".to_string(),
-                        type_params: vec![Type::KeywordType(TsKeywordTypeKind::Void)]
+                        type_params: vec![analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Void))]
                     })),
                     type_params: vec![],
                     this_param: None,
                     is_async: true,
-                })
+                }))
             )
         );
     }
@@ -807,6 +826,1222 @@ const h = false;
         dbg!(&type_info);
     }
 
+    #[test]
+    fn test_this_type() {
+        let src = r#"
+        interface Builder {
+            set(this: Builder, value: number): this;
+        }
+        declare const builder: Builder;
+        declare function bound(this: Builder, x: number): void;
+        const setter = builder.set;
+        "#;
+
+        let analyzer = test_analyzer(src, JsFileSource::ts());
+        let builder = analyzer.intern(Type::TypeRef(TsTypeRef {
+            name: "Builder".to_string(),
+            type_params: vec![],
+        }));
+
+        assert_eq!(
+            analyzer.get_symbol("bound").unwrap(),
+            &Symbol::new(
+                "bound".to_string(),
+                analyzer.intern(Type::Function(TsFunctionSignature {
+                    params: vec![FunctionParam {
+                        name: "x".to_string(),
+                        param_type: analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Number)),
+                        is_optional: false,
+                        is_rest: false
+                    }],
+                    return_type: analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Void)),
+                    type_params: vec![],
+                    this_param: Some(builder),
+                    is_async: false,
+                }))
+            )
+        );
+
+        match &*analyzer.get_type(symbol_type(&analyzer, "setter")) {
+            Type::Function(func) => assert_eq!(func.return_type, builder),
+            ty => panic!("expected a function, got {:?}", ty),
+        }
+    }
+
+    #[test]
+    fn test_this_expression() {
+        let src = r#"
+        interface Builder {
+            value: number;
+        }
+        function withThis(this: Builder) {
+            const arrow = () => this;
+        }
+        "#;
+
+        let parsed = parse(src, JsFileSource::ts(), Default::default());
+        let analyzer = test_analyzer(src, JsFileSource::ts());
+        let this_expr = parsed
+            .syntax()
+            .descendants()
+            .find_map(JsThisExpression::cast)
+            .unwrap();
+
+        assert_eq!(
+            analyzer.analyze_expression(&AnyJsExpression::JsThisExpression(this_expr)),
+            analyzer.intern(Type::TypeRef(TsTypeRef {
+                name: "Builder".to_string(),
+                type_params: vec![]
+            }))
+        );
+    }
+
+    #[test]
+    fn test_this_in_object_literals_and_classes() {
+        let src = r#"
+        const object = {
+            value: 1,
+            method() {
+                return this;
+            },
+            get twice() {
+                return this;
+            },
+        };
+        class Counter {
+            count() {
+                this;
+            }
+        }
+        "#;
+
+        let parsed = parse(src, JsFileSource::ts(), Default::default());
+        let analyzer = test_analyzer(src, JsFileSource::ts());
+        let object = parsed
+            .syntax()
+            .descendants()
+            .find_map(AnyJsExpression::cast)
+            .filter(|expr| matches!(expr, AnyJsExpression::JsObjectExpression(_)))
+            .unwrap();
+        let this_types: Vec<_> = parsed
+            .syntax()
+            .descendants()
+            .filter_map(JsThisExpression::cast)
+            .map(|this_expr| {
+                analyzer.analyze_expression(&AnyJsExpression::JsThisExpression(this_expr))
+            })
+            .collect();
+
+        let object_type = analyzer.analyze_expression(&object);
+        assert_eq!(this_types[0], object_type);
+        assert_eq!(this_types[1], object_type);
+        assert_eq!(this_types[2], analyzer.intern(Type::Unknown));
+    }
+
+    #[test]
+    fn test_rest_and_default_parameters() {
+        let src = r#"
+        declare function rest(first: string, ...others: number[]): void;
+        declare function tuple(...args: [number, string]): void;
+        declare function optional(...args: [number, label?: string]): void;
+        declare function open(...args: [number, ...rest: boolean[]]): void;
+        function withDefault(x = 1, { a, b }: { a: string; b: number }) {}
+        "#;
+
+        let analyzer = test_analyzer(src, JsFileSource::ts());
+
+        match &*analyzer.get_type(symbol_type(&analyzer, "rest")) {
+            Type::Function(func) => {
+                assert_eq!(
+                    func.params[1],
+                    FunctionParam {
+                        name: "others".to_string(),
+                        param_type: analyzer.intern(Type::TypeRef(TsTypeRef {
+                            name: "Array".to_string(),
+                            type_params: vec![
+                                analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Number))
+                            ]
+                        })),
+                        is_optional: false,
+                        is_rest: true
+                    }
+                );
+                let types = analyzer.types();
+                assert_eq!(func.arity(types), (1, None));
+                assert!(!func.accepts_argument_count(types, 0));
+                assert!(func.accepts_argument_count(types, 5));
+            }
+            ty => panic!("expected a function, got {:?}", ty),
+        }
+
+        match &*analyzer.get_type(symbol_type(&analyzer, "tuple")) {
+            Type::Function(func) => assert_eq!(func.arity(analyzer.types()), (2, Some(2))),
+            ty => panic!("expected a function, got {:?}", ty),
+        }
+        match &*analyzer.get_type(symbol_type(&analyzer, "optional")) {
+            Type::Function(func) => {
+                assert_eq!(func.arity(analyzer.types()), (1, Some(2)));
+                assert!(func.accepts_argument_count(analyzer.types(), 1));
+            }
+            ty => panic!("expected a function, got {:?}", ty),
+        }
+        match &*analyzer.get_type(symbol_type(&analyzer, "open")) {
+            Type::Function(func) => assert_eq!(func.arity(analyzer.types()), (1, None)),
+            ty => panic!("expected a function, got {:?}", ty),
+        }
+
+        match &*analyzer.get_type(symbol_type(&analyzer, "withDefault")) {
+            Type::Function(func) => {
+                assert_eq!(
+                    func.params[0],
+                    FunctionParam {
+                        name: "x".to_string(),
+                        param_type: analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Number)),
+                        is_optional: true,
+                        is_rest: false
+                    }
+                );
+                assert_eq!(func.params[1].name, "__1");
+                assert_eq!(func.arity(analyzer.types()), (2, Some(2)));
+            }
+            ty => panic!("expected a function, got {:?}", ty),
+        }
+    }
+
+    #[test]
+    fn test_destructuring_parameter_bindings() {
+        let src = r#"
+        const renamed = "outer";
+        function f({ a, b: renamed }: { a: string; b: number }, [first]: [boolean]) {
+            return renamed;
+        }
+        "#;
+
+        let parsed = parse(src, JsFileSource::ts(), Default::default());
+        let analyzer = test_analyzer(src, JsFileSource::ts());
+        let params = parsed
+            .syntax()
+            .descendants()
+            .find_map(JsParameters::cast)
+            .unwrap();
+
+        assert_eq!(
+            analyzer.analyze_parameter_bindings(&params).unwrap(),
+            vec![
+                Symbol::new("a".to_string(), analyzer.intern(Type::KeywordType(TsKeywordTypeKind::String))),
+                Symbol::new(
+                    "renamed".to_string(),
+                    analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Number))
+                ),
+                Symbol::new(
+                    "first".to_string(),
+                    analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Boolean))
+                ),
+            ]
+        );
+
+        // The parameter shadows the outer `renamed` in the body.
+        let returned = parsed
+            .syntax()
+            .descendants()
+            .find_map(JsReturnStatement::cast)
+            .and_then(|statement| statement.argument())
+            .unwrap();
+        assert_eq!(
+            analyzer.analyze_expression(&returned),
+            analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Number))
+        );
+    }
+
+    #[test]
+    fn test_call_arity() {
+        let src = r#"
+        declare function twoArgs(a: number, b?: string): void;
+        twoArgs();
+        twoArgs(1);
+        twoArgs(1, "a", 2);
+        "#;
+
+        let parsed = parse(src, JsFileSource::ts(), Default::default());
+        let analyzer = test_analyzer(src, JsFileSource::ts());
+        let arity: Vec<bool> = parsed
+            .syntax()
+            .descendants()
+            .filter_map(JsCallExpression::cast)
+            .map(|call| analyzer.check_call_arity(&call).unwrap().is_none())
+            .collect();
+
+        assert_eq!(arity, vec![false, true, false]);
+        let mismatches: Vec<_> = analyzer
+            .arity_mismatches()
+            .iter()
+            .map(|mismatch| mismatch.to_string())
+            .collect();
+        assert_eq!(
+            mismatches,
+            [
+                "Expected 1-2 arguments, but got 0.",
+                "Expected 1-2 arguments, but got 3."
+            ]
+        );
+    }
+
+    #[test]
+    fn test_overloaded_calls() {
+        let src = r#"
+        function pick(a: string): string;
+        function pick(a: number, b: number): number;
+        function pick(a: any, b?: any): any {
+            return a;
+        }
+        interface Parser {
+            parse(): boolean;
+            parse(text: string, strict: boolean): string;
+        }
+        declare const parser: Parser;
+        pick("a");
+        pick(1, 2);
+        pick();
+        parser.parse();
+        parser.parse("a", true);
+        parser.parse("a");
+        const picked = pick(1, 2);
+        const parsed = parser.parse();
+        "#;
+
+        let analyzer = test_analyzer(src, JsFileSource::ts());
+        let mismatches: Vec<_> = analyzer
+            .arity_mismatches()
+            .iter()
+            .map(|mismatch| (&src[mismatch.range], mismatch.to_string()))
+            .collect();
+        assert_eq!(
+            mismatches,
+            [
+                ("pick()", "Expected 1-2 arguments, but got 0.".to_string()),
+                (
+                    "parser.parse(\"a\")",
+                    "Expected 0-2 arguments, but got 1.".to_string()
+                ),
+            ]
+        );
+
+        // The first overload accepting the arguments gives the return type.
+        assert_eq!(
+            symbol_type(&analyzer, "picked"),
+            analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Number))
+        );
+        assert_eq!(
+            symbol_type(&analyzer, "parsed"),
+            analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Boolean))
+        );
+    }
+
+    #[test]
+    fn test_destructuring_declarations() {
+        let src = r#"
+        interface Response {
+            data: string;
+            status: number;
+        }
+        declare function get(): Promise<Response>;
+        declare const pair: [number, string];
+        declare const list: string[];
+        declare const partial: [number, string?, ...boolean[]];
+
+        const { data, status: code = 0 } = await get();
+        const [a, b] = pair;
+        const [, maybe, flag, ...flags] = partial;
+        const [head, ...tail] = list;
+        const { nested: { inner } } = { nested: { inner: 1 } };
+        const { missing = "fallback" } = {};
+        "#;
+
+        let analyzer = test_analyzer(src, JsFileSource::ts());
+        let ty = |name: &str| symbol_type(&analyzer, name);
+        let string = analyzer.intern(Type::KeywordType(TsKeywordTypeKind::String));
+        let number = analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Number));
+
+        assert_eq!(ty("data"), string);
+        assert_eq!(ty("code"), number);
+        assert_eq!(ty("a"), number);
+        assert_eq!(ty("b"), string);
+        assert_eq!(
+            ty("maybe"),
+            analyzer.intern(Type::Union(vec![
+                string,
+                analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Undefined))
+            ]))
+        );
+        let boolean = analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Boolean));
+        assert_eq!(ty("flag"), boolean);
+        assert_eq!(
+            ty("flags"),
+            analyzer.intern(Type::Tuple(vec![TupleElement {
+                ty: analyzer.intern(Type::TypeRef(TsTypeRef {
+                    name: "Array".to_string(),
+                    type_params: vec![boolean]
+                })),
+                is_optional: false,
+                is_rest: true
+            }]))
+        );
+        assert_eq!(ty("head"), string);
+        assert_eq!(
+            ty("tail"),
+            analyzer.intern(Type::TypeRef(TsTypeRef {
+                name: "Array".to_string(),
+                type_params: vec![string]
+            }))
+        );
+        assert_eq!(
+            ty("inner"),
+            analyzer.intern(Type::Literal(TsLiteralTypeKind::Number(1)))
+        );
+        assert_eq!(ty("missing"), string);
+        assert!(analyzer.get_symbol("nested").is_none());
+    }
+
+    #[test]
+    fn test_mutable_bindings() {
+        let src = r#"
+        let definite!: string;
+        let widened = 1;
+        let narrowed: string | number = "a";
+        let reassigned: string | number = "a";
+        reassigned = 1;
+        let choice: "a" | "b" = "b";
+        choice = "a";
+        if (true) {
+            var hoisted = true;
+            let scoped = 1;
+            const object = {
+                method() {
+                    var inMethod = 1;
+                },
+                get value() {
+                    var inGetter = 1;
+                    return inGetter;
+                },
+            };
+        }
+        "#;
+
+        let analyzer = test_analyzer(src, JsFileSource::ts());
+        let string_or_number = analyzer.intern(Type::Union(vec![
+            analyzer.intern(Type::KeywordType(TsKeywordTypeKind::String)),
+            analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Number)),
+        ]));
+
+        assert_eq!(
+            analyzer.get_symbol("definite").unwrap(),
+            &Symbol::mutable(
+                "definite".to_string(),
+                analyzer.intern(Type::KeywordType(TsKeywordTypeKind::String))
+            )
+        );
+        assert_eq!(
+            analyzer.get_symbol("widened").unwrap(),
+            &Symbol::mutable(
+                "widened".to_string(),
+                analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Number))
+            )
+        );
+
+        let narrowed = analyzer.get_symbol("narrowed").unwrap();
+        assert_eq!(narrowed.ty, string_or_number);
+        assert_eq!(
+            narrowed.current_type(),
+            analyzer.intern(Type::KeywordType(TsKeywordTypeKind::String))
+        );
+
+        let reassigned = analyzer.get_symbol("reassigned").unwrap();
+        assert_eq!(reassigned.ty, string_or_number);
+        assert_eq!(
+            reassigned.current_type(),
+            analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Number))
+        );
+
+        assert_eq!(
+            analyzer.get_symbol("hoisted").unwrap(),
+            &Symbol::mutable(
+                "hoisted".to_string(),
+                analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Boolean))
+            )
+        );
+        assert!(analyzer.get_symbol("scoped").is_none());
+        assert!(analyzer.get_symbol("inMethod").is_none());
+        assert!(analyzer.get_symbol("inGetter").is_none());
+
+        // Assigning a literal of the declared union keeps the literal.
+        let choice = analyzer.get_symbol("choice").unwrap();
+        assert_eq!(
+            choice.current_type(),
+            analyzer.intern(Type::Literal(TsLiteralTypeKind::String("\"a\"".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_flow_types_by_position() {
+        let src = r#"
+        let value: string | number = "a";
+        const before = value;
+        value = 1;
+        const after = value;
+        if (true) {
+            value = "b";
+        }
+        function f() {
+            const declared = value;
+            let local: string | boolean = true;
+            local = "c";
+            return local;
+        }
+        "#;
+
+        let parsed = parse(src, JsFileSource::ts(), Default::default());
+        let analyzer = test_analyzer(src, JsFileSource::ts());
+        let string = analyzer.intern(Type::KeywordType(TsKeywordTypeKind::String));
+        let number = analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Number));
+        let initializer = |name: &str| {
+            parsed
+                .syntax()
+                .descendants()
+                .filter_map(JsVariableDeclarator::cast)
+                .find(|declarator| declarator.id().unwrap().text() == name)
+                .and_then(|declarator| declarator.initializer())
+                .and_then(|initializer| initializer.expression().ok())
+                .unwrap()
+        };
+
+        assert_eq!(analyzer.get_symbol("before").unwrap().ty, string);
+        assert_eq!(analyzer.analyze_expression(&initializer("before")), string);
+        assert_eq!(analyzer.analyze_expression(&initializer("after")), number);
+        // The branch counts as taken at the end of the file.
+        assert_eq!(analyzer.get_symbol("value").unwrap().current_type(), string);
+        // Functions may run after any assignment.
+        assert_eq!(
+            analyzer.analyze_expression(&initializer("declared")),
+            analyzer.intern(Type::Union(vec![string, number]))
+        );
+
+        let returned = parsed
+            .syntax()
+            .descendants()
+            .find_map(JsReturnStatement::cast)
+            .and_then(|statement| statement.argument())
+            .unwrap();
+        assert_eq!(analyzer.analyze_expression(&returned), string);
+    }
+
+    #[test]
+    fn test_declaration_merging_and_aliases() {
+        let src = r#"
+        interface Base {
+            id: number;
+        }
+        interface Named extends Base {
+            name: string;
+        }
+        interface Named {
+            extra: boolean;
+        }
+        type Box<T = string> = { value: T };
+        declare const box: Box;
+        const value = box.value;
+        "#;
+
+        let analyzer = test_analyzer(src, JsFileSource::ts());
+        let named = analyzer.intern(Type::TypeRef(TsTypeRef {
+            name: "Named".to_string(),
+            type_params: vec![],
+        }));
+        let number = analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Number));
+
+        match &*analyzer.get_type(symbol_type(&analyzer, "Named")) {
+            Type::Interface(interface) => {
+                let names: Vec<_> = interface.properties.iter().map(|p| &p.name).collect();
+                assert_eq!(names, vec!["extra", "name"]);
+            }
+            ty => panic!("expected an interface, got {:?}", ty),
+        }
+        assert_eq!(
+            analyzer.resolve_member_type(named, "id", &PathBuf::new()),
+            number
+        );
+        assert_eq!(
+            symbol_type(&analyzer, "value"),
+            analyzer.intern(Type::KeywordType(TsKeywordTypeKind::String))
+        );
+    }
+
+    #[test]
+    fn test_unsupported_declarations_are_skipped() {
+        let src = r#"
+        declare namespace Intl {
+            interface Collator {}
+        }
+        declare class Foo {}
+        enum Color { Red }
+        type Mapped = { [K in "a" | "b"]: K };
+        interface Iterable<T> {
+            [Symbol.iterator](): Iterator<T>;
+            readonly [index: number]: T;
+            new (value: T): Iterable<T>;
+            (value: T): void;
+            get size(): number;
+        }
+        export interface Exported {}
+        "#;
+
+        let analyzer = test_analyzer(src, JsFileSource::ts());
+
+        assert_eq!(
+            symbol_type(&analyzer, "Mapped"),
+            analyzer.intern(Type::Alias(TsTypeAlias {
+                name: "Mapped".to_string(),
+                type_params: vec![],
+                aliased_type: analyzer.intern(Type::Unknown)
+            }))
+        );
+        match &*analyzer.get_type(symbol_type(&analyzer, "Iterable")) {
+            Type::Interface(interface) => {
+                let names: Vec<_> = interface.properties.iter().map(|p| &p.name).collect();
+                assert_eq!(names, vec!["__index", "__new", "__call", "size"]);
+            }
+            ty => panic!("expected an interface, got {:?}", ty),
+        }
+        assert!(analyzer.get_symbol("Exported").is_some());
+    }
+
+    #[test]
+    fn test_value_and_type_namespaces() {
+        let src = r#"
+        interface Box<T> {
+            value: T;
+        }
+        interface BoxConstructor {
+            new <T>(value: T): Box<T>;
+            of(value: string): Box<string>;
+        }
+        declare var Box: BoxConstructor;
+        async function load() {}
+        const made = Box.of("a");
+        const constructed = new Box(1);
+        const loaded = load();
+        "#;
+
+        let analyzer = test_analyzer(src, JsFileSource::ts());
+        let type_ref = |name: &str, type_params: Vec<TypeId>| {
+            analyzer.intern(Type::TypeRef(TsTypeRef {
+                name: name.to_string(),
+                type_params,
+            }))
+        };
+        let string = analyzer.intern(Type::KeywordType(TsKeywordTypeKind::String));
+
+        assert_eq!(
+            symbol_type(&analyzer, "Box"),
+            type_ref("BoxConstructor", vec![])
+        );
+        let made = symbol_type(&analyzer, "made");
+        assert_eq!(made, type_ref("Box", vec![string]));
+        assert_eq!(
+            analyzer.resolve_member_type(made, "value", &PathBuf::new()),
+            string
+        );
+        assert!(matches!(
+            &*analyzer.get_type(symbol_type(&analyzer, "constructed")),
+            Type::TypeRef(type_ref) if type_ref.name == "Box"
+        ));
+        assert_eq!(
+            symbol_type(&analyzer, "loaded"),
+            type_ref("Promise", vec![analyzer.intern(Type::Unknown)])
+        );
+    }
+
+    #[test]
+    fn test_imports_and_exports() {
+        fn visit(analyzer: &mut TypeAnalyzer, path: &str, src: &str) {
+            analyzer.set_current_path(PathBuf::from(path));
+            let parsed = parse(src, JsFileSource::ts(), Default::default());
+            analyzer.visit(&parsed.tree());
+        }
+
+        let mut analyzer = TypeAnalyzer::new(vec![]).unwrap();
+        visit(
+            &mut analyzer,
+            "client.ts",
+            r#"
+            export interface Response {
+                data: string;
+            }
+            declare const client: { get(): Response };
+            declare const internal: number;
+            export default client;
+            export declare const version: number;
+            export { version as release };
+            "#,
+        );
+        analyzer.add_resolved_module("main.ts".into(), "client".into(), "client.ts".into());
+        let src = r#"
+            import client, { version as v, release, internal, Request } from "client";
+            import * as ns from "client";
+            import missing from "missing";
+            const response = client.get();
+            "#;
+        visit(&mut analyzer, "main.ts", src);
+
+        let main = PathBuf::from("main.ts");
+        let number = analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Number));
+        assert_eq!(symbol_type(&analyzer, "v"), number);
+        assert_eq!(symbol_type(&analyzer, "release"), number);
+        assert!(analyzer.get_symbol("version").is_none());
+        assert!(analyzer.get_symbol("missing").is_none());
+        // Only what the module exports can be imported.
+        assert!(analyzer.get_symbol("internal").is_none());
+        let unresolved: Vec<_> = analyzer
+            .unresolved_imports()
+            .iter()
+            .map(|import| (&src[import.range], import.to_string()))
+            .collect();
+        assert_eq!(
+            unresolved,
+            [
+                (
+                    "internal",
+                    "Module '\"client\"' has no exported member 'internal'.".to_string()
+                ),
+                (
+                    "Request",
+                    "Module '\"client\"' has no exported member 'Request'.".to_string()
+                ),
+                (
+                    "\"missing\"",
+                    "Cannot find module 'missing' or its corresponding type declarations."
+                        .to_string()
+                ),
+            ]
+        );
+        let response = symbol_type(&analyzer, "response");
+        // `Response` isn't imported, but resolves in the module declaring it.
+        assert_eq!(
+            analyzer.resolve_member_type(response, "data", &main),
+            analyzer.intern(Type::KeywordType(TsKeywordTypeKind::String))
+        );
+        let ns = symbol_type(&analyzer, "ns");
+        assert_eq!(analyzer.resolve_member_type(ns, "version", &main), number);
+        assert_eq!(
+            analyzer.resolve_member_type(ns, "internal", &main),
+            analyzer.intern(Type::Unknown)
+        );
+    }
+
+    #[test]
+    fn test_declaration_file_exports() {
+        let mut analyzer = TypeAnalyzer::new(vec![]).unwrap();
+        analyzer.set_current_path(PathBuf::from("types.d.ts"));
+        let src = r#"
+        declare function helper(): string;
+        interface Options {
+            verbose: boolean;
+        }
+        export declare const version: number;
+        "#;
+        let parsed = parse(src, JsFileSource::d_ts(), Default::default());
+        analyzer.visit(&parsed.tree());
+
+        analyzer.add_resolved_module("main.ts".into(), "./types".into(), "types.d.ts".into());
+        analyzer.set_current_path(PathBuf::from("main.ts"));
+        let src = r#"
+        import { helper, Options } from "./types";
+        const result = helper();
+        declare const options: Options;
+        "#;
+        let parsed = parse(src, JsFileSource::ts(), Default::default());
+        analyzer.visit(&parsed.tree());
+
+        // Declarations of a `.d.ts` module are exported without `export`.
+        assert!(analyzer.unresolved_imports().is_empty());
+        assert_eq!(
+            symbol_type(&analyzer, "result"),
+            analyzer.intern(Type::KeywordType(TsKeywordTypeKind::String))
+        );
+        let options = symbol_type(&analyzer, "options");
+        assert_eq!(
+            analyzer.resolve_member_type(options, "verbose", &PathBuf::from("main.ts")),
+            analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Boolean))
+        );
+    }
+
+    #[test]
+    fn test_ambient_modules() {
+        let mut analyzer = TypeAnalyzer::new(vec![]).unwrap();
+        let shims = r#"
+        declare module "*.svg" {
+            const content: string;
+            export default content;
+        }
+        declare module "assets/*.svg" {
+            export const sprite: number;
+        }
+        declare module "config" {
+            export interface Config {
+                name: string;
+            }
+            export function load(): Config;
+        }
+        declare module "untyped";
+        "#;
+        let parsed = parse(shims, JsFileSource::d_ts(), Default::default());
+        analyzer.visit(&parsed.tree());
+
+        assert_eq!(
+            analyzer.ambient_module("config"),
+            Some(PathBuf::from("\"config\""))
+        );
+        assert_eq!(
+            analyzer.ambient_module("./logo.svg"),
+            Some(PathBuf::from("\"*.svg\""))
+        );
+        assert_eq!(
+            analyzer.ambient_module("assets/icons.svg"),
+            Some(PathBuf::from("\"assets/*.svg\""))
+        );
+        assert_eq!(analyzer.ambient_module("other"), None);
+
+        analyzer.set_current_path(PathBuf::from("main.ts"));
+        let src = r#"
+        import logo, { content } from "./logo.svg";
+        import { load } from "config";
+        import anything from "untyped";
+        const config = load();
+        "#;
+        let parsed = parse(src, JsFileSource::ts(), Default::default());
+        analyzer.visit(&parsed.tree());
+
+        let string = analyzer.intern(Type::KeywordType(TsKeywordTypeKind::String));
+        assert_eq!(symbol_type(&analyzer, "logo"), string);
+        assert!(analyzer.get_symbol("anything").is_none());
+        // `export default` makes the other declarations of the module local.
+        let unresolved: Vec<_> = analyzer
+            .unresolved_imports()
+            .iter()
+            .map(|import| import.name.as_deref())
+            .collect();
+        assert_eq!(unresolved, [Some("content")]);
+        let config = symbol_type(&analyzer, "config");
+        assert_eq!(
+            analyzer.resolve_member_type(config, "name", &PathBuf::from("main.ts")),
+            string
+        );
+    }
+
+    #[test]
+    fn test_type_interning() {
+        let src = r#"
+        interface Box<T> {
+            value: T;
+        }
+        declare const a: Box<string>;
+        declare const b: Box<string>;
+        "#;
+
+        let analyzer = test_analyzer(src, JsFileSource::ts());
+        let string = analyzer.intern(Type::KeywordType(TsKeywordTypeKind::String));
+        assert_eq!(
+            string,
+            analyzer.intern(Type::KeywordType(TsKeywordTypeKind::String))
+        );
+        let a = analyzer.get_symbol("a").unwrap().ty;
+        assert_eq!(a, analyzer.get_symbol("b").unwrap().ty);
+
+        // Repeated lookups reuse the cached `Box<string>` instantiation and
+        // don't grow the arena.
+        let path = PathBuf::new();
+        assert_eq!(analyzer.resolve_member_type(a, "value", &path), string);
+        let interned = analyzer.types().len();
+        assert_eq!(analyzer.resolve_member_type(a, "value", &path), string);
+        assert_eq!(analyzer.types().len(), interned);
+    }
+
+    #[test]
+    fn test_circular_references() {
+        let src = r#"
+        type A = B;
+        type B = A;
+        interface Left extends Right {}
+        interface Right extends Left {}
+        interface ListNode {
+            value: number;
+            next: ListNode;
+        }
+        declare const a: A;
+        declare const left: Left;
+        declare const node: ListNode;
+        "#;
+
+        let analyzer = test_analyzer(src, JsFileSource::ts());
+        let path = PathBuf::new();
+        let member = |name: &str, member: &str| {
+            analyzer.resolve_member_type(symbol_type(&analyzer, name), member, &path)
+        };
+        let circular = analyzer.intern(Type::Circular);
+        assert_eq!(member("a", "value"), circular);
+        assert_eq!(member("left", "value"), circular);
+
+        // Self-referential members are resolved one access at a time.
+        let next = member("node", "next");
+        let next = analyzer.resolve_member_type(next, "next", &path);
+        assert_eq!(
+            analyzer.resolve_member_type(next, "value", &path),
+            analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Number))
+        );
+    }
+
+    #[test]
+    fn test_fork_and_merge() {
+        fn visit(analyzer: &mut TypeAnalyzer, src: &str) {
+            let parsed = parse(src, JsFileSource::ts(), Default::default());
+            analyzer.visit(&parsed.tree());
+        }
+
+        let mut analyzer = test_analyzer("interface Window { name: string; }", JsFileSource::ts());
+        let mut module = analyzer.fork();
+        module.set_current_path(PathBuf::from("a.ts"));
+        visit(&mut module, "export declare const a: number;");
+        let mut script = analyzer.fork();
+        visit(&mut script, "interface Window { width: number; }");
+        assert!(analyzer.get_symbol("a").is_none());
+
+        analyzer.merge(module, &PathBuf::from("a.ts"));
+        analyzer.merge(script, &PathBuf::new());
+
+        let number = analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Number));
+        let path = PathBuf::new();
+        let window = symbol_type(&analyzer, "Window");
+        assert_eq!(
+            analyzer.resolve_member_type(window, "name", &path),
+            analyzer.intern(Type::KeywordType(TsKeywordTypeKind::String))
+        );
+        assert_eq!(analyzer.resolve_member_type(window, "width", &path), number);
+        assert!(analyzer.get_symbol("a").is_none());
+        analyzer.set_current_path(PathBuf::from("a.ts"));
+        assert_eq!(symbol_type(&analyzer, "a"), number);
+    }
+
+    #[test]
+    fn test_resolved_references_across_forks() {
+        let mut analyzer = test_analyzer(
+            "interface Box { a: string; }\ndeclare const box: Box;",
+            JsFileSource::ts(),
+        );
+        let path = PathBuf::new();
+        let member = |analyzer: &TypeAnalyzer, name: &str| {
+            analyzer.resolve_member_type(symbol_type(analyzer, "box"), name, &path)
+        };
+        let string = analyzer.intern(Type::KeywordType(TsKeywordTypeKind::String));
+        let number = analyzer.intern(Type::KeywordType(TsKeywordTypeKind::Number));
+        let unknown = analyzer.intern(Type::Unknown);
+        assert_eq!(member(&analyzer, "b"), unknown);
+
+        // The fork starts from what the analyzer resolved, and stops sharing
+        // it once it declares something.
+        let mut script = analyzer.fork();
+        assert_eq!(member(&script, "a"), string);
+        let parsed = parse("interface Box { b: number; }", JsFileSource::ts(), Default::default());
+        script.visit(&parsed.tree());
+        assert_eq!(member(&script, "b"), number);
+        assert_eq!(member(&analyzer, "b"), unknown);
+
+        analyzer.merge(script, &path);
+        assert_eq!(member(&analyzer, "b"), number);
+    }
+
+    #[test]
+    fn test_declarations_and_references() {
+        let src = r#"interface Box { a: number; }
+interface Box { b: string; }
+declare const box: Box;
+const copy = box;
+function unbox(box: Box) { return box; }
+"#;
+        let parsed = parse(src, JsFileSource::ts(), Default::default());
+        let mut analyzer = TypeAnalyzer::new(vec![]).unwrap();
+        analyzer.set_current_file(PathBuf::from("box.ts"));
+        analyzer.visit(&parsed.tree());
+
+        let range = |start: usize, name: &str| {
+            biome_rowan::TextRange::at((start as u32).into(), (name.len() as u32).into())
+        };
+        let box_type = analyzer
+            .lookup_in_scope(&PathBuf::new(), symbol::Namespace::Type, "Box")
+            .unwrap();
+        let declarations: Vec<_> = box_type
+            .declarations
+            .iter()
+            .map(|location| (location.path.clone(), location.range))
+            .collect();
+        let expected: Vec<_> = src
+            .match_indices("Box {")
+            .map(|(start, _)| (PathBuf::from("box.ts"), range(start, "Box")))
+            .collect();
+        assert_eq!(declarations, expected);
+
+        // The parameter shadows the constant in the body of `unbox`.
+        let references = analyzer.references(&parsed.tree());
+        let of = |name: &str| {
+            references
+                .iter()
+                .filter(|reference| reference.symbol.name == name)
+                .map(|reference| reference.range)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(of("box"), vec![range(src.find("= box").unwrap() + 2, "box")]);
+        assert_eq!(of("Box").len(), 2);
+        assert_eq!(of("unbox"), vec![]);
+    }
+
+    #[test]
+    fn test_print_types() {
+        let src = r#"
+        type Id = string | number;
+        interface Item { id: Id; }
+        declare const a: Promise<string | number>;
+        declare const b: <T extends object = {}>(value: T, ...rest: string[]) => void;
+        declare const c: (string | (() => void))[];
+        declare const d: [Id, "a\"b", 1, true];
+        declare const f: [number, (string | boolean)?, ...number[]];
+        declare const e: Item & { ready: boolean };
+        const g = { name: "item", count: 1 };
+        interface Box<T> { value: T; }
+        declare const id: Id;
+        declare const boxed: Box<string>;
+        declare const ids: Id[];
+        "#;
+        let analyzer = test_analyzer(src, JsFileSource::ts());
+        let print = |name: &str, options: &PrintOptions| {
+            analyzer.types().print(symbol_type(&analyzer, name), options)
+        };
+        let default = PrintOptions::default();
+
+        assert_eq!(print("a", &default), "Promise<string | number>");
+        assert_eq!(
+            print("b", &default),
+            "<T extends object = {}>(value: T, ...rest: string[]) => void"
+        );
+        assert_eq!(print("c", &default), "(string | (() => void))[]");
+        assert_eq!(print("d", &default), r#"[Id, "a\"b", 1, true]"#);
+        assert_eq!(print("e", &default), "Item & { ready: boolean; }");
+        assert_eq!(
+            print("f", &default),
+            "[number, (string | boolean)?, ...number[]]"
+        );
+        assert_eq!(print("g", &default), r#"{ name: "item"; count: 1; }"#);
+
+        let alias = analyzer.lookup_in_scope(&PathBuf::new(), symbol::Namespace::Type, "Id");
+        let alias = alias.unwrap().ty;
+        let expanded = PrintOptions {
+            expand_aliases: true,
+            ..Default::default()
+        };
+        assert_eq!(analyzer.types().print(alias, &default), "Id");
+        assert_eq!(analyzer.types().print(alias, &expanded), "string | number");
+
+        // Uses of the alias are references, which the analyzer resolves.
+        let id = symbol_type(&analyzer, "id");
+        assert_eq!(analyzer.print_type(id, &default), "Id");
+        assert_eq!(analyzer.print_type(id, &expanded), "string | number");
+        let ids = symbol_type(&analyzer, "ids");
+        assert_eq!(analyzer.print_type(ids, &expanded), "(string | number)[]");
+
+        // Instances print their type arguments, not the declared parameters.
+        let boxed = symbol_type(&analyzer, "boxed");
+        let instance = analyzer.resolve_reference(boxed, &PathBuf::new());
+        assert_eq!(analyzer.types().print(instance, &default), "Box<string>");
+        assert_eq!(analyzer.print_type(boxed, &expanded), "Box<string>");
+
+        let multiline = PrintOptions {
+            multiline: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            print("g", &multiline),
+            "{\n    name: \"item\";\n    count: 1;\n}"
+        );
+        let truncated = PrintOptions {
+            max_length: Some(12),
+            ..Default::default()
+        };
+        assert_eq!(print("a", &truncated), "Promise<s...");
+    }
+
+    #[test]
+    fn test_emit_declarations() {
+        let src = r#"
+function parse(value: string): number;
+function parse(value: number): number;
+function parse(value: any) {
+    return 1;
+}
+const greet = (name = "world") => "hello";
+async function load(id) {
+    return await fetch(id);
+}
+class Counter {
+    #count = 0;
+    static step = 1;
+    protected label?: string;
+    increment() {
+        return true;
+    }
+}
+"#;
+        let analyzer = test_analyzer(src, JsFileSource::ts());
+        let root = parse(src, JsFileSource::ts(), Default::default()).tree();
+
+        assert_eq!(
+            analyzer.emit_declarations(&root),
+            r#"declare function parse(value: string): number;
+declare function parse(value: number): number;
+declare const greet: (name?: string) => string;
+declare function load(id: any): Promise<any>;
+declare class Counter {
+    #private;
+    static step: number;
+    protected label?: string;
+    increment(): boolean;
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_utility_types_from_lib() {
+        let src = r#"
+        interface Todo {
+            title: string;
+            readonly done?: boolean;
+        }
+        declare const partial: Partial<Todo>;
+        declare const required: Required<Todo>;
+        declare const picked: Pick<Todo, "title">;
+        declare const record: Record<"a" | "b", number>;
+        declare const key: keyof Todo;
+        declare const title: Todo["title"];
+        declare const query: typeof partial;
+        declare const conditional: Todo extends object ? 1 : 2;
+        "#;
+
+        let mut analyzer =
+            TypeAnalyzer::new(vec![PathBuf::from("../../src/lib/es5.d.ts")]).unwrap();
+        analyzer.visit(&parse(src, JsFileSource::ts(), Default::default()).tree());
+        let path = PathBuf::new();
+        let default = PrintOptions::default();
+        let print = |ty: TypeId| analyzer.types().print(ty, &default);
+        let resolved =
+            |name: &str| print(analyzer.resolve_reference(symbol_type(&analyzer, name), &path));
+        let evaluated =
+            |name: &str| print(analyzer.evaluate_type(symbol_type(&analyzer, name), &path));
+
+        assert_eq!(resolved("partial"), "{ title?: string; readonly done?: boolean; }");
+        assert_eq!(resolved("required"), "{ title: string; readonly done: boolean; }");
+        assert_eq!(resolved("picked"), "{ title: string; }");
+        assert_eq!(resolved("record"), "{ a: number; b: number; }");
+        assert_eq!(
+            analyzer.resolve_member_type(symbol_type(&analyzer, "partial"), "title", &path),
+            analyzer.intern(Type::KeywordType(TsKeywordTypeKind::String))
+        );
+
+        assert_eq!(print(symbol_type(&analyzer, "key")), "keyof Todo");
+        assert_eq!(evaluated("key"), r#""title" | "done""#);
+        assert_eq!(print(symbol_type(&analyzer, "title")), r#"Todo["title"]"#);
+        assert_eq!(evaluated("title"), "string");
+        assert_eq!(evaluated("query"), "Partial<Todo>");
+        // Conditional types aren't modelled, and say so.
+        assert_eq!(
+            *analyzer.get_type(symbol_type(&analyzer, "conditional")),
+            Type::Unsupported("Todo extends object ? 1 : 2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_unsupported_declarations() {
+        let src = r#"
+        class Counter {}
+        enum Color { Red, Green }
+        declare namespace Intl {
+            const locale: string;
+        }
+        export class Exported {}
+        declare const ready: boolean;
+        "#;
+        let analyzer = test_analyzer(src, JsFileSource::ts());
+
+        let unsupported: Vec<_> = analyzer
+            .unsupported_declarations()
+            .iter()
+            .map(|declaration| (declaration.kind, declaration.name.as_str()))
+            .collect();
+        assert_eq!(
+            unsupported,
+            vec![
+                (DeclarationKind::Class, "Counter"),
+                (DeclarationKind::Enum, "Color"),
+                (DeclarationKind::Namespace, "Intl"),
+                (DeclarationKind::Class, "Exported"),
+            ]
+        );
+        assert_eq!(
+            analyzer.unsupported_declarations()[0].to_string(),
+            "Class 'Counter' isn't analyzed yet; its type is unknown."
+        );
+        assert!(analyzer.get_symbol("ready").is_some());
+    }
+
+    #[test]
+    fn test_floating_promises() {
+        let src = r#"
+        declare function load(): Promise<string>;
+        load();
+        void load();
+        async function run() {
+            load().then(1);
+        }
+        function handle(onError: (error: unknown) => void, other: string) {
+            load().catch(onError);
+            load().catch(other);
+        }
+        "#;
+        let analyzer = test_analyzer(src, JsFileSource::ts());
+        let root = parse(src, JsFileSource::ts(), Default::default()).tree();
+        let floating: Vec<_> = root
+            .syntax()
+            .descendants()
+            .filter_map(JsExpressionStatement::cast)
+            .filter_map(|statement| analyzer.find_floating_promise(&statement))
+            .map(|floating| {
+                let text = floating.expression.syntax().text_trimmed().to_string();
+                (
+                    text,
+                    floating.non_function_handler,
+                    floating.can_await,
+                    floating.is_void,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            floating,
+            [
+                ("load()".to_string(), false, false, false),
+                ("load()".to_string(), false, false, true),
+                ("load().then(1)".to_string(), true, true, false),
+                ("load().catch(other)".to_string(), true, false, false),
+            ]
+        );
+    }
+
     #[test]
     #[ignore]
     fn quick_test() {
@@ -0,0 +1,257 @@
+use std::{fmt, path::PathBuf, str::FromStr};
+
+use biome_rowan::{TextRange, TextSize};
+
+mod render;
+mod report;
+
+pub use render::RenderOptions;
+pub use report::{Report, ReportFormat, Summary};
+
+/// How serious a diagnostic is, from least to most.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+impl FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [Severity::Info, Severity::Warning, Severity::Error]
+            .into_iter()
+            .find(|severity| severity.as_str() == s)
+            .ok_or_else(|| {
+                format!(
+                    "Unknown severity `{}`, expected `info`, `warning` or `error`",
+                    s
+                )
+            })
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A line and column, both starting at 1. Columns count characters, or
+/// UTF-16 code units, see [ColumnUnit].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    pub line: u32,
+    pub column: u32,
+}
+
+/// A range of a file, with the positions it starts and ends at.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub range: TextRange,
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Span {
+    pub fn new(index: &LineIndex, range: TextRange) -> Self {
+        Self {
+            range,
+            start: index.position(range.start()),
+            end: index.position(range.end()),
+        }
+    }
+}
+
+/// Another span a diagnostic points at, with what it shows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+}
+
+/// A change of the source that addresses a diagnostic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    pub message: String,
+    pub edits: Vec<TextEdit>,
+}
+
+/// Replaces `span`, which is empty for insertions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub span: Span,
+    pub replacement: String,
+}
+
+/// A problem a rule found in a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The rule that reported it, e.g. `no-floating-promises`.
+    pub rule: String,
+    pub severity: Severity,
+    pub message: String,
+    pub path: PathBuf,
+    /// Where the problem is.
+    pub span: Span,
+    /// Related code, e.g. the argument that made a call invalid.
+    pub labels: Vec<Label>,
+    pub notes: Vec<String>,
+    pub fixes: Vec<Fix>,
+}
+
+impl Diagnostic {
+    pub fn new(
+        rule: impl Into<String>,
+        severity: Severity,
+        path: PathBuf,
+        span: Span,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            rule: rule.into(),
+            severity,
+            message: message.into(),
+            path,
+            span,
+            labels: vec![],
+            notes: vec![],
+            fixes: vec![],
+        }
+    }
+
+    pub fn with_label(mut self, span: Span, message: impl Into<String>) -> Self {
+        self.labels.push(Label {
+            span,
+            message: message.into(),
+        });
+        self
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    pub fn with_fix(mut self, fix: Fix) -> Self {
+        self.fixes.push(fix);
+        self
+    }
+}
+
+/// `path:line:column: severity[rule]: message`, like compilers print
+/// errors on one line.
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {}[{}]: {}",
+            self.path.display(),
+            self.span.start.line,
+            self.span.start.column,
+            self.severity,
+            self.rule,
+            self.message
+        )
+    }
+}
+
+/// What the columns of a [Position] count.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColumnUnit {
+    #[default]
+    Char,
+    /// Like LSP positions.
+    Utf16,
+}
+
+impl ColumnUnit {
+    fn len(self, c: char) -> usize {
+        match self {
+            ColumnUnit::Char => 1,
+            ColumnUnit::Utf16 => c.len_utf16(),
+        }
+    }
+}
+
+/// Converts between byte offsets of a text and [Position]s.
+pub struct LineIndex<'a> {
+    text: &'a str,
+    line_starts: Vec<usize>,
+    unit: ColumnUnit,
+}
+
+impl<'a> LineIndex<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self::with_unit(text, ColumnUnit::Char)
+    }
+
+    pub fn with_unit(text: &'a str, unit: ColumnUnit) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+        Self {
+            text,
+            line_starts,
+            unit,
+        }
+    }
+
+    pub fn position(&self, offset: TextSize) -> Position {
+        let offset = usize::from(offset).min(self.text.len());
+        let line = self.line_starts.partition_point(|start| *start <= offset) - 1;
+        let start = self.line_starts[line];
+        let column: usize = self.text[start..offset]
+            .chars()
+            .map(|c| self.unit.len(c))
+            .sum();
+        Position {
+            line: line as u32 + 1,
+            column: column as u32 + 1,
+        }
+    }
+
+    /// The offset of `position`, or of the end of its line when the column
+    /// is past it. `None` when the line doesn't exist.
+    pub fn offset(&self, position: Position) -> Option<TextSize> {
+        let index = (position.line as usize).checked_sub(1)?;
+        let start = *self.line_starts.get(index)?;
+        let line = self.text[start..].split('\n').next().unwrap_or_default();
+        let column = (position.column as usize).saturating_sub(1);
+        let mut units = 0;
+        for (index, c) in line.char_indices() {
+            if units >= column {
+                return TextSize::try_from(start + index).ok();
+            }
+            units += self.unit.len(c);
+        }
+        TextSize::try_from(start + line.len()).ok()
+    }
+
+    /// The text of the `line`th line, without its line break.
+    pub fn line(&self, line: u32) -> &'a str {
+        let Some(start) = (line as usize)
+            .checked_sub(1)
+            .and_then(|index| self.line_starts.get(index))
+        else {
+            return "";
+        };
+        let end = self
+            .line_starts
+            .get(line as usize)
+            .copied()
+            .unwrap_or(self.text.len());
+        self.text[*start..end].trim_end_matches(['\n', '\r'])
+    }
+}
@@ -0,0 +1,205 @@
+use crate::{Diagnostic, LineIndex, Severity, Span};
+
+/// How [Diagnostic::render] draws a diagnostic.
+#[derive(Debug, Clone, Default)]
+pub struct RenderOptions {
+    /// Colors the output with ANSI escape codes, for terminals.
+    pub colors: bool,
+}
+
+/// A span underlined in the code frame.
+struct Mark<'a> {
+    span: &'a Span,
+    is_primary: bool,
+    message: Option<&'a str>,
+}
+
+impl Diagnostic {
+    /// The diagnostic with a frame of the code it points at, like rustc
+    /// prints errors. `source` is the text of the file at `path`.
+    ///
+    /// ```text
+    /// error[no-floating-promises]: Unhandled Promise
+    ///  --> src/index.ts:2:1
+    ///   |
+    /// 2 | load();
+    ///   | ^^^^^^^
+    ///   |
+    ///   = note: Promises must be awaited, ...
+    /// ```
+    pub fn render(&self, source: &str, options: &RenderOptions) -> String {
+        let index = LineIndex::new(source);
+        let style = Style {
+            colors: options.colors,
+            severity: self.severity,
+        };
+        let mut marks = vec![Mark {
+            span: &self.span,
+            is_primary: true,
+            message: None,
+        }];
+        marks.extend(self.labels.iter().map(|label| Mark {
+            span: &label.span,
+            is_primary: false,
+            message: Some(&label.message),
+        }));
+        // Multi-line spans show their first and last line.
+        let mut lines: Vec<u32> = marks
+            .iter()
+            .flat_map(|mark| [mark.span.start.line, mark.span.end.line])
+            .collect();
+        lines.sort_unstable();
+        lines.dedup();
+        let width = lines.last().map_or(1, |line| line.to_string().len());
+        let margin = " ".repeat(width);
+
+        let mut out = String::new();
+        let header = format!("{}[{}]", self.severity, self.rule);
+        push_line(
+            &mut out,
+            format!(
+                "{}{}",
+                style.severity(&header),
+                style.bold(&format!(": {}", self.message))
+            ),
+        );
+        push_line(
+            &mut out,
+            format!(
+                "{}{} {}:{}:{}",
+                margin,
+                style.gutter("-->"),
+                self.path.display(),
+                self.span.start.line,
+                self.span.start.column
+            ),
+        );
+        push_line(&mut out, format!("{} {}", margin, style.gutter("|")));
+
+        let mut previous: Option<u32> = None;
+        for line in lines {
+            if previous.is_some_and(|previous| line > previous + 1) {
+                push_line(&mut out, style.gutter("..."));
+            }
+            previous = Some(line);
+            let text = index.line(line);
+            push_line(
+                &mut out,
+                format!(
+                    "{} {}",
+                    style.gutter(&format!("{:>width$} |", line)),
+                    expand_tabs(text)
+                ),
+            );
+            for mark in &marks {
+                if mark.span.start.line != line && mark.span.end.line != line {
+                    continue;
+                }
+                let chars: Vec<char> = text.chars().collect();
+                let start = if mark.span.start.line == line {
+                    mark.span.start.column as usize - 1
+                } else {
+                    chars.iter().take_while(|c| c.is_whitespace()).count()
+                };
+                let end = if mark.span.end.line == line {
+                    mark.span.end.column as usize - 1
+                } else {
+                    chars.len()
+                };
+                let start = start.min(chars.len());
+                let end = end.clamp(start, chars.len());
+                let offset = display_width(&chars[..start]);
+                let length = display_width(&chars[start..end]).max(1);
+                let underline = if mark.is_primary { "^" } else { "-" }.repeat(length);
+                let annotation = match mark.message {
+                    Some(message) => format!("{} {}", underline, message),
+                    None => underline,
+                };
+                push_line(
+                    &mut out,
+                    format!(
+                        "{} {}{}",
+                        style.gutter(&format!("{} |", margin)),
+                        " ".repeat(offset),
+                        style.mark(mark.is_primary, &annotation)
+                    ),
+                );
+            }
+        }
+
+        if !self.notes.is_empty() || !self.fixes.is_empty() {
+            push_line(&mut out, format!("{} {}", margin, style.gutter("|")));
+        }
+        for note in &self.notes {
+            push_line(
+                &mut out,
+                format!("{} {} {}", margin, style.bold("= note:"), note),
+            );
+        }
+        for fix in &self.fixes {
+            push_line(
+                &mut out,
+                format!("{} {} {}", margin, style.bold("= help:"), fix.message),
+            );
+        }
+        out
+    }
+}
+
+struct Style {
+    colors: bool,
+    severity: Severity,
+}
+
+impl Style {
+    fn severity(&self, text: &str) -> String {
+        let color = match self.severity {
+            Severity::Error => "1;31",
+            Severity::Warning => "1;33",
+            Severity::Info => "1;36",
+        };
+        self.paint(color, text)
+    }
+
+    fn mark(&self, is_primary: bool, text: &str) -> String {
+        if is_primary {
+            self.severity(text)
+        } else {
+            self.gutter(text)
+        }
+    }
+
+    fn gutter(&self, text: &str) -> String {
+        self.paint("1;34", text)
+    }
+
+    fn bold(&self, text: &str) -> String {
+        self.paint("1", text)
+    }
+
+    fn paint(&self, code: &str, text: &str) -> String {
+        if self.colors {
+            format!("\x1b[{}m{}\x1b[0m", code, text)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+fn push_line(out: &mut String, line: String) {
+    out.push_str(line.trim_end());
+    out.push('\n');
+}
+
+const TAB_WIDTH: usize = 4;
+
+fn expand_tabs(text: &str) -> String {
+    text.replace('\t', &" ".repeat(TAB_WIDTH))
+}
+
+fn display_width(chars: &[char]) -> usize {
+    chars
+        .iter()
+        .map(|c| if *c == '\t' { TAB_WIDTH } else { 1 })
+        .sum()
+}
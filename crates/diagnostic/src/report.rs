@@ -0,0 +1,515 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use serde_json::{json, Value};
+
+use crate::{Diagnostic, RenderOptions, Severity, Span};
+
+/// The output formats of a [Report].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReportFormat {
+    /// Code frames, like [Diagnostic::render], and a summary.
+    #[default]
+    Pretty,
+    /// One JSON object per line and diagnostic.
+    Json,
+    /// A SARIF 2.1.0 log, for code scanning dashboards.
+    Sarif,
+    /// JUnit XML, with a test suite per file.
+    Junit,
+    /// Checkstyle XML.
+    Checkstyle,
+    /// GitHub Actions workflow commands, e.g. `::error file=..,line=..::`.
+    Github,
+}
+
+impl ReportFormat {
+    pub const ALL: [ReportFormat; 6] = [
+        ReportFormat::Pretty,
+        ReportFormat::Json,
+        ReportFormat::Sarif,
+        ReportFormat::Junit,
+        ReportFormat::Checkstyle,
+        ReportFormat::Github,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportFormat::Pretty => "pretty",
+            ReportFormat::Json => "json",
+            ReportFormat::Sarif => "sarif",
+            ReportFormat::Junit => "junit",
+            ReportFormat::Checkstyle => "checkstyle",
+            ReportFormat::Github => "github",
+        }
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|format| format.as_str() == s)
+            .ok_or_else(|| {
+                let names: Vec<_> = Self::ALL.iter().map(ReportFormat::as_str).collect();
+                format!(
+                    "Unknown format `{}`, expected one of: {}",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+impl fmt::Display for ReportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// How many files were checked and how many diagnostics of each severity
+/// were found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Summary {
+    pub files: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    pub infos: usize,
+}
+
+impl Summary {
+    pub fn has_errors(&self) -> bool {
+        self.errors > 0
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Checked {}: found {} and {}",
+            plural(self.files, "file"),
+            plural(self.errors, "error"),
+            plural(self.warnings, "warning")
+        )?;
+        if self.infos > 0 {
+            write!(f, " ({})", plural(self.infos, "info"))?;
+        }
+        f.write_str(".")
+    }
+}
+
+fn plural(count: usize, word: &str) -> String {
+    if count == 1 {
+        format!("{} {}", count, word)
+    } else {
+        format!("{} {}s", count, word)
+    }
+}
+
+/// The diagnostics of the checked files, written in one of the
+/// [ReportFormat]s.
+pub struct Report {
+    tool: String,
+    root: Option<PathBuf>,
+    files: Vec<(PathBuf, String)>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Report {
+    /// `tool` names the checker in SARIF and JUnit reports.
+    pub fn new(tool: impl Into<String>) -> Self {
+        Self {
+            tool: tool.into(),
+            root: None,
+            files: vec![],
+            diagnostics: vec![],
+        }
+    }
+
+    /// Writes paths relative to `root`, as CI annotations expect them.
+    pub fn with_root(mut self, root: PathBuf) -> Self {
+        self.root = Some(root);
+        self
+    }
+
+    /// Adds a checked file, its text and the diagnostics found in it.
+    pub fn add_file(
+        &mut self,
+        path: PathBuf,
+        source: String,
+        diagnostics: impl IntoIterator<Item = Diagnostic>,
+    ) {
+        self.files.push((path, source));
+        self.diagnostics.extend(diagnostics);
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    pub fn summary(&self) -> Summary {
+        let mut summary = Summary {
+            files: self.files.len(),
+            ..Default::default()
+        };
+        for diagnostic in &self.diagnostics {
+            match diagnostic.severity {
+                Severity::Error => summary.errors += 1,
+                Severity::Warning => summary.warnings += 1,
+                Severity::Info => summary.infos += 1,
+            }
+        }
+        summary
+    }
+
+    /// The report in `format`. Only [ReportFormat::Pretty] includes the
+    /// summary, so that the other formats can be parsed as a whole.
+    pub fn format(&self, format: ReportFormat, options: &RenderOptions) -> String {
+        match format {
+            ReportFormat::Pretty => self.pretty(options),
+            ReportFormat::Json => self.json_lines(),
+            ReportFormat::Sarif => self.sarif(),
+            ReportFormat::Junit => self.junit(),
+            ReportFormat::Checkstyle => self.checkstyle(),
+            ReportFormat::Github => self.github(),
+        }
+    }
+
+    fn pretty(&self, options: &RenderOptions) -> String {
+        let mut out = String::new();
+        for (path, source) in &self.files {
+            for diagnostic in self.diagnostics_of(path) {
+                out.push_str(&diagnostic.render(source, options));
+                out.push('\n');
+            }
+        }
+        out.push_str(&self.summary().to_string());
+        out.push('\n');
+        out
+    }
+
+    fn json_lines(&self) -> String {
+        let mut out = String::new();
+        for diagnostic in &self.diagnostics {
+            let labels: Vec<Value> = diagnostic
+                .labels
+                .iter()
+                .map(|label| {
+                    json!({
+                        "span": span_json(&label.span),
+                        "message": label.message,
+                    })
+                })
+                .collect();
+            let fixes: Vec<Value> = diagnostic
+                .fixes
+                .iter()
+                .map(|fix| {
+                    let edits: Vec<Value> = fix
+                        .edits
+                        .iter()
+                        .map(|edit| {
+                            json!({
+                                "span": span_json(&edit.span),
+                                "replacement": edit.replacement,
+                            })
+                        })
+                        .collect();
+                    json!({ "message": fix.message, "edits": edits })
+                })
+                .collect();
+            let line = json!({
+                "rule": diagnostic.rule,
+                "severity": diagnostic.severity.as_str(),
+                "message": diagnostic.message,
+                "path": self.display_path(&diagnostic.path),
+                "span": span_json(&diagnostic.span),
+                "labels": labels,
+                "notes": diagnostic.notes,
+                "fixes": fixes,
+            });
+            out.push_str(&line.to_string());
+            out.push('\n');
+        }
+        out
+    }
+
+    fn sarif(&self) -> String {
+        let mut rules: Vec<&str> = self
+            .diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.rule.as_str())
+            .collect();
+        rules.sort_unstable();
+        rules.dedup();
+
+        let results: Vec<Value> = self
+            .diagnostics
+            .iter()
+            .map(|diagnostic| {
+                let uri = self.display_path(&diagnostic.path);
+                let location = |span: &Span| {
+                    json!({
+                        "artifactLocation": { "uri": uri },
+                        "region": sarif_region(span),
+                    })
+                };
+                let related: Vec<Value> = diagnostic
+                    .labels
+                    .iter()
+                    .enumerate()
+                    .map(|(id, label)| {
+                        json!({
+                            "id": id,
+                            "physicalLocation": location(&label.span),
+                            "message": { "text": label.message },
+                        })
+                    })
+                    .collect();
+                let fixes: Vec<Value> = diagnostic
+                    .fixes
+                    .iter()
+                    .map(|fix| {
+                        let replacements: Vec<Value> = fix
+                            .edits
+                            .iter()
+                            .map(|edit| {
+                                json!({
+                                    "deletedRegion": sarif_region(&edit.span),
+                                    "insertedContent": { "text": edit.replacement },
+                                })
+                            })
+                            .collect();
+                        json!({
+                            "description": { "text": fix.message },
+                            "artifactChanges": [{
+                                "artifactLocation": { "uri": uri },
+                                "replacements": replacements,
+                            }],
+                        })
+                    })
+                    .collect();
+                let mut text = diagnostic.message.clone();
+                for note in &diagnostic.notes {
+                    text.push('\n');
+                    text.push_str(note);
+                }
+                json!({
+                    "ruleId": diagnostic.rule,
+                    "ruleIndex": rules.binary_search(&diagnostic.rule.as_str()).unwrap(),
+                    "level": match diagnostic.severity {
+                        Severity::Error => "error",
+                        Severity::Warning => "warning",
+                        Severity::Info => "note",
+                    },
+                    "message": { "text": text },
+                    "locations": [{ "physicalLocation": location(&diagnostic.span) }],
+                    "relatedLocations": related,
+                    "fixes": fixes,
+                })
+            })
+            .collect();
+        let artifacts: Vec<Value> = self
+            .files
+            .iter()
+            .map(|(path, _)| json!({ "location": { "uri": self.display_path(path) } }))
+            .collect();
+        let rules: Vec<Value> = rules.iter().map(|rule| json!({ "id": rule })).collect();
+
+        let log = json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": { "driver": { "name": self.tool, "rules": rules } },
+                "columnKind": "unicodeCodePoints",
+                "artifacts": artifacts,
+                "results": results,
+            }],
+        });
+        let mut out = serde_json::to_string_pretty(&log).unwrap();
+        out.push('\n');
+        out
+    }
+
+    fn junit(&self) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let failures = self.diagnostics.len();
+        // A file without diagnostics is one passing test case.
+        let tests: usize = self
+            .files
+            .iter()
+            .map(|(path, _)| self.diagnostics_of(path).count().max(1))
+            .sum();
+        out.push_str(&format!(
+            "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\">\n",
+            escape_xml(&self.tool),
+            tests,
+            failures
+        ));
+        for (path, _) in &self.files {
+            let name = escape_xml(&self.display_path(path));
+            let diagnostics: Vec<_> = self.diagnostics_of(path).collect();
+            out.push_str(&format!(
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\">\n",
+                name,
+                diagnostics.len().max(1),
+                diagnostics.len()
+            ));
+            if diagnostics.is_empty() {
+                out.push_str(&format!(
+                    "    <testcase name=\"{}\" classname=\"{}\" />\n",
+                    name, name
+                ));
+            }
+            for diagnostic in diagnostics {
+                let location = format!(
+                    "{}:{}:{}",
+                    self.display_path(path),
+                    diagnostic.span.start.line,
+                    diagnostic.span.start.column
+                );
+                out.push_str(&format!(
+                    "    <testcase name=\"{}\" classname=\"{}\">\n",
+                    escape_xml(&location),
+                    escape_xml(&diagnostic.rule)
+                ));
+                let mut text = format!("{}: {}", location, diagnostic.message);
+                for note in &diagnostic.notes {
+                    text.push('\n');
+                    text.push_str(note);
+                }
+                out.push_str(&format!(
+                    "      <failure message=\"{}\" type=\"{}\">{}</failure>\n",
+                    escape_xml(&diagnostic.message),
+                    diagnostic.severity,
+                    escape_xml(&text)
+                ));
+                out.push_str("    </testcase>\n");
+            }
+            out.push_str("  </testsuite>\n");
+        }
+        out.push_str("</testsuites>\n");
+        out
+    }
+
+    fn checkstyle(&self) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<checkstyle version=\"4.3\">\n");
+        for (path, _) in &self.files {
+            out.push_str(&format!(
+                "  <file name=\"{}\">\n",
+                escape_xml(&self.display_path(path))
+            ));
+            for diagnostic in self.diagnostics_of(path) {
+                out.push_str(&format!(
+                    "    <error line=\"{}\" column=\"{}\" severity=\"{}\" message=\"{}\" source=\"{}\" />\n",
+                    diagnostic.span.start.line,
+                    diagnostic.span.start.column,
+                    diagnostic.severity,
+                    escape_xml(&diagnostic.message),
+                    escape_xml(&diagnostic.rule)
+                ));
+            }
+            out.push_str("  </file>\n");
+        }
+        out.push_str("</checkstyle>\n");
+        out
+    }
+
+    fn github(&self) -> String {
+        let mut out = String::new();
+        for diagnostic in &self.diagnostics {
+            let command = match diagnostic.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Info => "notice",
+            };
+            let Span { start, end, .. } = &diagnostic.span;
+            out.push_str(&format!(
+                "::{} file={},line={},col={},endLine={},endColumn={},title={}::{}\n",
+                command,
+                escape_github_property(&self.display_path(&diagnostic.path)),
+                start.line,
+                start.column,
+                end.line,
+                end.column,
+                escape_github_property(&diagnostic.rule),
+                escape_github_data(&diagnostic.message)
+            ));
+        }
+        out
+    }
+
+    fn diagnostics_of<'a>(&'a self, path: &'a Path) -> impl Iterator<Item = &'a Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(move |diagnostic| diagnostic.path == path)
+    }
+
+    fn display_path(&self, path: &Path) -> String {
+        let path = self
+            .root
+            .as_deref()
+            .and_then(|root| path.strip_prefix(root).ok())
+            .unwrap_or(path);
+        path.to_string_lossy().replace('\\', "/")
+    }
+}
+
+fn span_json(span: &Span) -> Value {
+    json!({
+        "range": {
+            "start": u32::from(span.range.start()),
+            "end": u32::from(span.range.end()),
+        },
+        "start": { "line": span.start.line, "column": span.start.column },
+        "end": { "line": span.end.line, "column": span.end.column },
+    })
+}
+
+fn sarif_region(span: &Span) -> Value {
+    json!({
+        "startLine": span.start.line,
+        "startColumn": span.start.column,
+        "endLine": span.end.line,
+        "endColumn": span.end.column,
+    })
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\n' | '\r' | '\t' => out.push(c),
+            // Not allowed in XML 1.0, even escaped.
+            c if c.is_control() => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Escapes the message of a workflow command.
+fn escape_github_data(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escapes a `key=value` parameter of a workflow command.
+fn escape_github_property(text: &str) -> String {
+    escape_github_data(text)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
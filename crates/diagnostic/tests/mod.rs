@@ -0,0 +1,129 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use biome_rowan::{TextRange, TextSize};
+    use diagnostic::{
+        ColumnUnit, Diagnostic, Fix, LineIndex, Position, RenderOptions, Report, ReportFormat,
+        Severity, Span, Summary, TextEdit,
+    };
+
+    #[test]
+    fn test_line_index_columns() {
+        let text = "let s = \"😀\";\nlet é = 1;";
+        let chars = LineIndex::new(text);
+        let utf16 = LineIndex::with_unit(text, ColumnUnit::Utf16);
+        let position = |line, column| Position { line, column };
+
+        // After the emoji, which is one character but two UTF-16 code units.
+        assert_eq!(chars.position(TextSize::from(13)), position(1, 11));
+        assert_eq!(utf16.position(TextSize::from(13)), position(1, 12));
+        assert_eq!(utf16.offset(position(1, 12)), Some(TextSize::from(13)));
+        // `é` is two bytes but one code unit.
+        assert_eq!(utf16.position(TextSize::from(23)), position(2, 7));
+        assert_eq!(utf16.offset(position(2, 7)), Some(TextSize::from(23)));
+        // Columns past the end of a line stop at its end.
+        assert_eq!(utf16.offset(position(1, 100)), Some(TextSize::from(15)));
+        assert_eq!(utf16.offset(position(3, 1)), None);
+    }
+
+    #[test]
+    fn test_reports() {
+        let src = "async function load(): Promise<string> {\n  return \"\";\n}\nload();\n";
+        let (path, clean) = (
+            PathBuf::from("/project/src/test.ts"),
+            PathBuf::from("/project/src/clean.ts"),
+        );
+        // What `no-floating-promises` reports for the last line.
+        let index = LineIndex::new(src);
+        let statement = TextRange::new(56.into(), 63.into());
+        let diagnostic = Diagnostic::new(
+            "no-floating-promises",
+            Severity::Error,
+            path.clone(),
+            Span::new(&index, statement),
+            "Unhandled Promise",
+        )
+        .with_fix(Fix {
+            message: "Add `void` to ignore the Promise".to_string(),
+            edits: vec![TextEdit {
+                span: Span::new(&index, TextRange::empty(statement.start())),
+                replacement: "void ".to_string(),
+            }],
+        });
+        let mut report = Report::new("type_poc_v3").with_root(PathBuf::from("/project"));
+        report.add_file(path, src.to_string(), vec![diagnostic]);
+        report.add_file(clean, String::new(), vec![]);
+
+        let summary = report.summary();
+        assert_eq!(
+            summary,
+            Summary {
+                files: 2,
+                errors: 1,
+                warnings: 0,
+                infos: 0
+            }
+        );
+        assert!(summary.has_errors());
+        assert_eq!(
+            summary.to_string(),
+            "Checked 2 files: found 1 error and 0 warnings."
+        );
+
+        let format = |format: &str| {
+            let format: ReportFormat = format.parse().unwrap();
+            report.format(format, &RenderOptions::default())
+        };
+        assert!("xml".parse::<ReportFormat>().is_err());
+        assert!(format("pretty").ends_with("Checked 2 files: found 1 error and 0 warnings.\n"));
+        assert_eq!(
+            format("github"),
+            "::error file=src/test.ts,line=4,col=1,endLine=4,endColumn=8,title=no-floating-promises::Unhandled Promise\n"
+        );
+
+        let json = format("json");
+        assert_eq!(json.lines().count(), 1);
+        let line: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(line["path"], "src/test.ts");
+        assert_eq!(line["severity"], "error");
+        assert_eq!(line["span"]["range"]["start"], 56);
+        assert_eq!(line["span"]["start"]["line"], 4);
+        assert_eq!(line["fixes"][0]["edits"][0]["replacement"], "void ");
+
+        let sarif: serde_json::Value = serde_json::from_str(&format("sarif")).unwrap();
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        assert_eq!(
+            run["tool"]["driver"]["rules"][0]["id"],
+            "no-floating-promises"
+        );
+        assert_eq!(run["artifacts"].as_array().unwrap().len(), 2);
+        let result = &run["results"][0];
+        assert_eq!(result["level"], "error");
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/test.ts");
+        assert_eq!(location["region"]["startLine"], 4);
+        assert_eq!(location["region"]["endColumn"], 8);
+
+        assert_eq!(
+            format("checkstyle"),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<checkstyle version="4.3">
+  <file name="src/test.ts">
+    <error line="4" column="1" severity="error" message="Unhandled Promise" source="no-floating-promises" />
+  </file>
+  <file name="src/clean.ts">
+  </file>
+</checkstyle>
+"#
+        );
+        let junit = format("junit");
+        assert!(junit.contains(r#"<testsuites name="type_poc_v3" tests="2" failures="1">"#));
+        assert!(
+            junit.contains(r#"<testcase name="src/test.ts:4:1" classname="no-floating-promises">"#)
+        );
+        assert!(junit.contains(r#"<failure message="Unhandled Promise" type="error">"#));
+        assert!(junit.contains(r#"<testcase name="src/clean.ts" classname="src/clean.ts" />"#));
+    }
+}
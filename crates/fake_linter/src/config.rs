@@ -0,0 +1,359 @@
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+use diagnostic::Severity;
+use file_system::FileSystem;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use server::{glob, tsconfig::strip_jsonc};
+
+use crate::RuleRegistry;
+
+/// The names a config file is looked up by, in order.
+pub const CONFIG_FILE_NAMES: [&str; 2] = ["lint.config.json", "lint.config.toml"];
+
+/// The files `include` picks up.
+const EXTENSIONS: [&str; 8] = [".ts", ".tsx", ".mts", ".cts", ".js", ".jsx", ".mjs", ".cjs"];
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawLintConfig {
+    include: Option<Vec<String>>,
+    #[serde(default)]
+    ignore: Vec<String>,
+    #[serde(default)]
+    rules: Map<String, Value>,
+    #[serde(default)]
+    overrides: Vec<RawOverride>,
+    #[serde(default, rename = "reportUnusedSuppressions")]
+    report_unused_suppressions: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawOverride {
+    files: Vec<String>,
+    #[serde(default)]
+    rules: Map<String, Value>,
+}
+
+/// Turns a rule off, or on with a severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleLevel {
+    Off,
+    On(Severity),
+}
+
+/// How a config file sets up a rule: `"warning"`, or
+/// `{ "severity": "warning", "options": { ... } }`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleSetting {
+    pub level: Option<RuleLevel>,
+    pub options: Option<Value>,
+}
+
+impl RuleSetting {
+    fn apply(&self, registry: &mut RuleRegistry, name: &str) -> Result<(), String> {
+        match self.level {
+            Some(RuleLevel::Off) => {
+                registry.set_enabled(name, false);
+            }
+            Some(RuleLevel::On(severity)) => {
+                registry.set_enabled(name, true);
+                registry.set_severity(name, severity);
+            }
+            None => {}
+        }
+        match &self.options {
+            Some(options) => registry.configure(name, options),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Rules set up differently for the files matching `files`.
+#[derive(Debug, Clone)]
+struct Override {
+    files: Vec<PathBuf>,
+    rules: Vec<(String, RuleSetting)>,
+}
+
+/// A lint config file: the files to lint, and how the rules are set up for
+/// them. Globs are relative to the file's directory.
+#[derive(Debug, Clone)]
+pub struct LintConfig {
+    pub path: PathBuf,
+    /// Whether suppression comments that suppress nothing are reported.
+    pub report_unused_suppressions: bool,
+    include: Option<Vec<PathBuf>>,
+    ignore: Vec<PathBuf>,
+    rules: Vec<(String, RuleSetting)>,
+    overrides: Vec<Override>,
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(PathBuf, io::Error),
+    Parse(PathBuf, String),
+    UnknownRule {
+        path: PathBuf,
+        rule: String,
+        suggestion: Option<String>,
+    },
+    InvalidRule {
+        path: PathBuf,
+        rule: String,
+        message: String,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(path, err) => write!(f, "Cannot read {}: {}", path.display(), err),
+            Self::Parse(path, err) => write!(f, "Cannot parse {}: {}", path.display(), err),
+            Self::UnknownRule {
+                path,
+                rule,
+                suggestion,
+            } => {
+                write!(f, "{}: unknown rule `{}`", path.display(), rule)?;
+                match suggestion {
+                    Some(suggestion) => write!(f, ", did you mean `{}`?", suggestion),
+                    None => Ok(()),
+                }
+            }
+            Self::InvalidRule {
+                path,
+                rule,
+                message,
+            } => write!(
+                f,
+                "{}: invalid setting for `{}`: {}",
+                path.display(),
+                rule,
+                message
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl LintConfig {
+    /// The config file in `dir` or in the closest of its parents that has
+    /// one, like tsc looks for `tsconfig.json`.
+    pub fn find(fs: &dyn FileSystem, dir: &Path) -> Option<PathBuf> {
+        dir.ancestors().find_map(|dir| {
+            CONFIG_FILE_NAMES
+                .iter()
+                .map(|name| dir.join(name))
+                .find(|path| fs.is_file(path))
+        })
+    }
+
+    /// Reads the JSON (comments are allowed, like in tsconfig files) or
+    /// TOML config at `path`, and checks it against the built-in rules.
+    pub fn load(fs: &dyn FileSystem, path: &Path) -> Result<Self, ConfigError> {
+        let path = std::path::absolute(path)
+            .map(|path| glob::normalize(&path))
+            .map_err(|err| ConfigError::Io(path.to_path_buf(), err))?;
+        let src = fs
+            .read_to_string(&path)
+            .map_err(|err| ConfigError::Io(path.clone(), err))?;
+        let parse_error =
+            |err: &dyn fmt::Display| ConfigError::Parse(path.clone(), err.to_string());
+        let value: Value = if path.extension().is_some_and(|ext| ext == "toml") {
+            toml::from_str(&src).map_err(|err| parse_error(&err))?
+        } else {
+            serde_json::from_str(&strip_jsonc(&src)).map_err(|err| parse_error(&err))?
+        };
+        let raw: RawLintConfig = serde_json::from_value(value).map_err(|err| parse_error(&err))?;
+
+        let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        let resolve = |patterns: Vec<String>| -> Vec<PathBuf> {
+            patterns
+                .iter()
+                .map(|pattern| glob::normalize(&dir.join(pattern)))
+                .collect()
+        };
+        let mut overrides = vec![];
+        for raw_override in raw.overrides {
+            overrides.push(Override {
+                files: resolve(raw_override.files),
+                rules: parse_rules(&path, raw_override.rules)?,
+            });
+        }
+        let config = Self {
+            rules: parse_rules(&path, raw.rules)?,
+            path: path.clone(),
+            report_unused_suppressions: raw.report_unused_suppressions,
+            include: raw.include.map(resolve),
+            ignore: resolve(raw.ignore),
+            overrides,
+        };
+
+        // Rules check their own options.
+        let mut registry = RuleRegistry::builtin();
+        let settings = config
+            .rules
+            .iter()
+            .chain(config.overrides.iter().flat_map(|o| &o.rules));
+        for (name, setting) in settings {
+            setting
+                .apply(&mut registry, name)
+                .map_err(|message| ConfigError::InvalidRule {
+                    path: path.clone(),
+                    rule: name.clone(),
+                    message,
+                })?;
+        }
+        Ok(config)
+    }
+
+    /// The files matching `include` that aren't ignored. `None` if the
+    /// config doesn't say which files to lint.
+    pub fn files(&self, fs: &dyn FileSystem) -> Option<Vec<PathBuf>> {
+        let mut files = vec![];
+        for pattern in self.include.as_ref()? {
+            let pattern = glob::include_pattern(pattern.clone());
+            glob::walk(fs, &glob::base_dir(&pattern), &self.ignore, &mut |path| {
+                let name = path.to_string_lossy();
+                if glob::matches(&pattern, path) && EXTENSIONS.iter().any(|ext| name.ends_with(ext))
+                {
+                    files.push(path.to_path_buf());
+                }
+            });
+        }
+        files.sort();
+        files.dedup();
+        Some(files)
+    }
+
+    /// Whether the file at `path` is left out by `include` or `ignore`.
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        let path = absolute(path);
+        let included = self.include.as_ref().map_or(true, |include| {
+            include
+                .iter()
+                .any(|pattern| glob::matches_prefix(pattern, &path))
+        });
+        !included
+            || self
+                .ignore
+                .iter()
+                .any(|pattern| glob::matches_prefix(pattern, &path))
+    }
+
+    /// The built-in rules set up for the file at `path`: the `rules` of the
+    /// config, then those of the overrides matching the file, in order.
+    pub fn registry(&self, path: &Path) -> RuleRegistry {
+        let path = absolute(path);
+        let mut registry = RuleRegistry::builtin();
+        let overrides = self.overrides.iter().filter(|o| {
+            o.files
+                .iter()
+                .any(|pattern| glob::matches_prefix(pattern, &path))
+        });
+        let settings = self.rules.iter().chain(overrides.flat_map(|o| &o.rules));
+        for (name, setting) in settings {
+            setting
+                .apply(&mut registry, name)
+                .expect("settings are checked when the config is loaded");
+        }
+        registry
+    }
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    glob::normalize(&std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf()))
+}
+
+fn parse_rules(
+    path: &Path,
+    rules: Map<String, Value>,
+) -> Result<Vec<(String, RuleSetting)>, ConfigError> {
+    let registry = RuleRegistry::builtin();
+    let mut settings = vec![];
+    for (name, value) in rules {
+        if registry.get(&name).is_none() {
+            let suggestion = registry
+                .rules()
+                .map(|rule| rule.meta().name)
+                .min_by_key(|known| edit_distance(known, &name))
+                .filter(|known| edit_distance(known, &name) <= 3)
+                .map(str::to_string);
+            return Err(ConfigError::UnknownRule {
+                path: path.to_path_buf(),
+                rule: name,
+                suggestion,
+            });
+        }
+        let setting = parse_rule_setting(value).map_err(|message| ConfigError::InvalidRule {
+            path: path.to_path_buf(),
+            rule: name.clone(),
+            message,
+        })?;
+        settings.push((name, setting));
+    }
+    Ok(settings)
+}
+
+fn parse_rule_setting(value: Value) -> Result<RuleSetting, String> {
+    match value {
+        Value::String(level) => Ok(RuleSetting {
+            level: Some(parse_level(&level)?),
+            options: None,
+        }),
+        Value::Object(mut setting) => {
+            if let Some(key) = setting
+                .keys()
+                .find(|key| !matches!(key.as_str(), "severity" | "options"))
+            {
+                return Err(format!(
+                    "unknown field `{}`, expected `severity` or `options`",
+                    key
+                ));
+            }
+            let level = match setting.remove("severity") {
+                Some(Value::String(level)) => Some(parse_level(&level)?),
+                Some(_) => return Err("`severity` must be a string".to_string()),
+                None => None,
+            };
+            Ok(RuleSetting {
+                level,
+                options: setting.remove("options"),
+            })
+        }
+        _ => Err("expected a severity, or an object with `severity` and `options`".to_string()),
+    }
+}
+
+fn parse_level(level: &str) -> Result<RuleLevel, String> {
+    match level {
+        "off" => Ok(RuleLevel::Off),
+        _ => level.parse().map(RuleLevel::On).map_err(|_| {
+            format!(
+                "unknown severity `{}`, expected `off`, `info`, `warning` or `error`",
+                level
+            )
+        }),
+    }
+}
+
+/// The Levenshtein distance between `a` and `b`, to suggest rule names.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != *b);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
@@ -0,0 +1,76 @@
+use std::{path::Path, sync::Arc};
+
+use analyzer::TypeAnalyzer;
+use biome_js_syntax::{AnyJsExpression, TextRange};
+use diagnostic::{Diagnostic, LineIndex, Span};
+use server::Server;
+use type_info::{symbol::Symbol, Type, TypeId};
+
+use crate::RuleMeta;
+
+/// What rules know about the file being linted. Types are looked up from
+/// the file, without changing the server.
+pub struct RuleContext<'a> {
+    server: &'a Server,
+    analyzer: TypeAnalyzer,
+    path: &'a Path,
+    index: LineIndex<'a>,
+}
+
+impl<'a> RuleContext<'a> {
+    /// `source` is the text of the file at `path`.
+    pub fn new(server: &'a Server, path: &'a Path, source: &'a str) -> Self {
+        Self {
+            server,
+            analyzer: server.scoped_analyzer(path),
+            path,
+            index: LineIndex::new(source),
+        }
+    }
+
+    pub fn server(&self) -> &'a Server {
+        self.server
+    }
+
+    pub fn path(&self) -> &'a Path {
+        self.path
+    }
+
+    pub fn span(&self, range: TextRange) -> Span {
+        Span::new(&self.index, range)
+    }
+
+    /// A diagnostic of the rule described by `meta`, with its severity.
+    pub fn diagnostic(
+        &self,
+        meta: &RuleMeta,
+        range: TextRange,
+        message: impl Into<String>,
+    ) -> Diagnostic {
+        Diagnostic::new(
+            meta.name,
+            meta.severity,
+            self.path.to_path_buf(),
+            self.span(range),
+            message,
+        )
+    }
+
+    /// The types where the file is, for checks shared with other linters.
+    pub fn types(&self) -> &TypeAnalyzer {
+        &self.analyzer
+    }
+
+    pub fn infer_expression_type(&self, expr: &AnyJsExpression) -> TypeId {
+        self.analyzer.analyze_expression(expr)
+    }
+
+    /// The value or else the type named `name` where the file is.
+    pub fn symbol(&self, name: &str) -> Option<&Symbol> {
+        self.analyzer.get_symbol(name)
+    }
+
+    pub fn get_type(&self, id: TypeId) -> Arc<Type> {
+        self.analyzer.get_type(id)
+    }
+}
@@ -0,0 +1,73 @@
+use std::path::Path;
+
+use biome_js_syntax::AnyJsRoot;
+use biome_rowan::AstNode;
+use diagnostic::{Diagnostic, Severity};
+use server::Server;
+
+use crate::{suppression::Suppressions, Rule, RuleContext, RuleRegistry};
+
+/// Runs the enabled rules of a registry on files.
+pub struct Linter {
+    registry: RuleRegistry,
+    report_unused_suppressions: bool,
+}
+
+impl Linter {
+    pub fn new(registry: RuleRegistry) -> Self {
+        Self {
+            registry,
+            report_unused_suppressions: false,
+        }
+    }
+
+    /// Reports the suppression comments that don't suppress anything.
+    pub fn with_unused_suppressions(mut self, report: bool) -> Self {
+        self.report_unused_suppressions = report;
+        self
+    }
+
+    pub fn registry(&self) -> &RuleRegistry {
+        &self.registry
+    }
+
+    /// Lints `root`, the file at `path` analyzed by `server`. The tree is
+    /// walked once, and each node is given to the rules interested in its
+    /// kind. Diagnostics suppressed by comments are left out.
+    pub fn lint(&self, server: &Server, path: &Path, root: &AnyJsRoot) -> Vec<Diagnostic> {
+        let source = root.syntax().text().to_string();
+        let ctx = RuleContext::new(server, path, &source);
+        let rules: Vec<(&dyn Rule, Severity)> = self
+            .registry
+            .enabled()
+            .filter_map(|rule| Some((rule, self.registry.severity(rule.meta().name)?)))
+            .collect();
+        let mut diagnostics = vec![];
+        for node in root.syntax().descendants() {
+            for (rule, severity) in &rules {
+                if rule.kinds().contains(&node.kind()) {
+                    diagnostics.extend(rule.run(&ctx, &node).into_iter().map(|mut diagnostic| {
+                        diagnostic.severity = *severity;
+                        diagnostic
+                    }));
+                }
+            }
+        }
+
+        let (mut suppressions, invalid) = Suppressions::parse(root, &ctx, &self.registry);
+        let mut diagnostics = suppressions.apply(diagnostics);
+        diagnostics.extend(invalid);
+        if self.report_unused_suppressions {
+            diagnostics.extend(suppressions.unused(&ctx, &self.registry));
+        }
+        diagnostics.sort_by_key(|diagnostic| diagnostic.span.range.start());
+        diagnostics
+    }
+}
+
+/// A linter with the built-in rules.
+impl Default for Linter {
+    fn default() -> Self {
+        Self::new(RuleRegistry::builtin())
+    }
+}
@@ -0,0 +1,119 @@
+use diagnostic::Severity;
+use serde_json::Value;
+
+use crate::{rules, Rule};
+
+struct RegisteredRule {
+    rule: Box<dyn Rule>,
+    enabled: bool,
+    severity: Severity,
+}
+
+/// The rules a [Linter](crate::Linter) knows, and which of them are
+/// enabled.
+#[derive(Default)]
+pub struct RuleRegistry {
+    rules: Vec<RegisteredRule>,
+}
+
+impl RuleRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A registry of the built-in rules, all enabled.
+    pub fn builtin() -> Self {
+        let mut registry = Self::new();
+        for rule in rules::builtin() {
+            registry.register(rule);
+        }
+        registry
+    }
+
+    /// Adds `rule`, enabled. Panics if a rule of the same name was added.
+    pub fn register(&mut self, rule: Box<dyn Rule>) {
+        let name = rule.meta().name;
+        assert!(
+            self.get(name).is_none(),
+            "Rule `{}` is already registered",
+            name
+        );
+        let severity = rule.meta().severity;
+        self.rules.push(RegisteredRule {
+            rule,
+            enabled: true,
+            severity,
+        });
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn Rule> {
+        self.find(name).map(|registered| registered.rule.as_ref())
+    }
+
+    /// All rules, in the order they were added.
+    pub fn rules(&self) -> impl Iterator<Item = &dyn Rule> {
+        self.rules.iter().map(|registered| registered.rule.as_ref())
+    }
+
+    pub fn enabled(&self) -> impl Iterator<Item = &dyn Rule> {
+        self.rules
+            .iter()
+            .filter(|registered| registered.enabled)
+            .map(|registered| registered.rule.as_ref())
+    }
+
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.find(name).is_some_and(|registered| registered.enabled)
+    }
+
+    /// The severity the diagnostics of the rule `name` are reported with,
+    /// `None` if it isn't enabled.
+    pub fn severity(&self, name: &str) -> Option<Severity> {
+        self.find(name)
+            .filter(|registered| registered.enabled)
+            .map(|registered| registered.severity)
+    }
+
+    /// Enables or disables the rule `name`. `false` if there is none.
+    pub fn set_enabled(&mut self, name: &str, enabled: bool) -> bool {
+        match self.find_mut(name) {
+            Some(registered) => {
+                registered.enabled = enabled;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Reports the diagnostics of the rule `name` with `severity` instead of
+    /// the rule's own. `false` if there is no such rule.
+    pub fn set_severity(&mut self, name: &str, severity: Severity) -> bool {
+        match self.find_mut(name) {
+            Some(registered) => {
+                registered.severity = severity;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Passes `options` to the rule `name`, see [Rule::configure].
+    pub fn configure(&mut self, name: &str, options: &Value) -> Result<(), String> {
+        match self.find_mut(name) {
+            Some(registered) => registered.rule.configure(options),
+            None => Err(format!("Unknown rule `{}`", name)),
+        }
+    }
+
+    fn find(&self, name: &str) -> Option<&RegisteredRule> {
+        self.rules
+            .iter()
+            .find(|registered| registered.rule.meta().name == name)
+    }
+
+    fn find_mut(&mut self, name: &str) -> Option<&mut RegisteredRule> {
+        self.rules
+            .iter_mut()
+            .find(|registered| registered.rule.meta().name == name)
+    }
+}
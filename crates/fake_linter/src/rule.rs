@@ -0,0 +1,35 @@
+use biome_js_syntax::{JsSyntaxKind, JsSyntaxNode};
+use diagnostic::{Diagnostic, Severity};
+use serde_json::Value;
+
+use crate::RuleContext;
+
+/// Describes a rule, for registries, configuration and reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuleMeta {
+    /// Identifies the rule, e.g. `no-floating-promises`.
+    pub name: &'static str,
+    pub description: &'static str,
+    /// The severity of the rule's diagnostics.
+    pub severity: Severity,
+}
+
+/// A check the [Linter](crate::Linter) runs on the nodes of the kinds the
+/// rule is interested in.
+pub trait Rule: Send + Sync {
+    fn meta(&self) -> &'static RuleMeta;
+
+    /// The kinds of the nodes `run` is called with.
+    fn kinds(&self) -> &'static [JsSyntaxKind];
+
+    fn run(&self, ctx: &RuleContext, node: &JsSyntaxNode) -> Vec<Diagnostic>;
+
+    /// Applies the options the rule is given in a config file. Rules
+    /// without options only accept an empty object.
+    fn configure(&mut self, options: &Value) -> Result<(), String> {
+        match options.as_object() {
+            Some(options) if options.is_empty() => Ok(()),
+            _ => Err(format!("`{}` has no options", self.meta().name)),
+        }
+    }
+}
@@ -0,0 +1,10 @@
+mod no_floating_promises;
+
+pub use no_floating_promises::{NoFloatingPromises, NoFloatingPromisesOptions};
+
+use crate::Rule;
+
+/// The rules that come with the linter.
+pub fn builtin() -> Vec<Box<dyn Rule>> {
+    vec![Box::new(NoFloatingPromises::default())]
+}
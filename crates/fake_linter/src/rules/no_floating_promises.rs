@@ -0,0 +1,108 @@
+use biome_js_syntax::*;
+use biome_rowan::AstNode;
+use diagnostic::{Diagnostic, Fix, Severity, TextEdit};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{Rule, RuleContext, RuleMeta};
+
+/// What handling a Promise takes.
+const NOTE: &str = "Promises must be awaited, end with a call to `.catch`, or end with a call \
+    to `.then` with a rejection handler.";
+
+/// The options of the rule, named like typescript-eslint's.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields, default)]
+pub struct NoFloatingPromisesOptions {
+    /// Whether `void` marks a Promise as deliberately not awaited.
+    pub ignore_void: bool,
+}
+
+impl Default for NoFloatingPromisesOptions {
+    fn default() -> Self {
+        Self { ignore_void: true }
+    }
+}
+
+/// Reports Promises that are neither awaited nor handled, like
+/// typescript-eslint's rule of the same name.
+#[derive(Default)]
+pub struct NoFloatingPromises {
+    options: NoFloatingPromisesOptions,
+}
+
+impl NoFloatingPromises {
+    pub const META: RuleMeta = RuleMeta {
+        name: "no-floating-promises",
+        description: "Promises must be awaited or have their rejections handled.",
+        severity: Severity::Error,
+    };
+}
+
+impl Rule for NoFloatingPromises {
+    fn meta(&self) -> &'static RuleMeta {
+        &Self::META
+    }
+
+    fn kinds(&self) -> &'static [JsSyntaxKind] {
+        &[JsSyntaxKind::JS_EXPRESSION_STATEMENT]
+    }
+
+    fn run(&self, ctx: &RuleContext, node: &JsSyntaxNode) -> Vec<Diagnostic> {
+        let Some(expr_stmt) = JsExpressionStatement::cast_ref(node) else {
+            return vec![];
+        };
+        self.check_expression_statement(ctx, &expr_stmt)
+            .into_iter()
+            .collect()
+    }
+
+    fn configure(&mut self, options: &Value) -> Result<(), String> {
+        self.options = serde_json::from_value(options.clone()).map_err(|err| err.to_string())?;
+        Ok(())
+    }
+}
+
+impl NoFloatingPromises {
+    fn check_expression_statement(
+        &self,
+        ctx: &RuleContext,
+        expr_stmt: &JsExpressionStatement,
+    ) -> Option<Diagnostic> {
+        let floating = ctx
+            .types()
+            .find_floating_promise(expr_stmt)
+            .filter(|floating| !(floating.is_void && self.options.ignore_void))?;
+        // Worded like typescript-eslint's.
+        let message = if floating.non_function_handler {
+            "Unhandled Promise with non-function handler"
+        } else {
+            "Unhandled Promise"
+        };
+        let mut diagnostic = ctx
+            .diagnostic(
+                &Self::META,
+                expr_stmt.syntax().text_trimmed_range(),
+                message,
+            )
+            .with_note(NOTE);
+        if let Some(range) = floating.handler_arguments {
+            diagnostic = diagnostic.with_label(ctx.span(range), "not a function");
+        }
+        let start = floating.expression.syntax().text_trimmed_range().start();
+        let insert = |message: &str, text: &str| Fix {
+            message: message.to_string(),
+            edits: vec![TextEdit {
+                span: ctx.span(TextRange::empty(start)),
+                replacement: text.to_string(),
+            }],
+        };
+        if floating.can_await {
+            diagnostic = diagnostic.with_fix(insert("Add `await`", "await "));
+        }
+        if !floating.is_void {
+            diagnostic = diagnostic.with_fix(insert("Add `void` to ignore the Promise", "void "));
+        }
+        Some(diagnostic)
+    }
+}
@@ -0,0 +1,274 @@
+use biome_js_syntax::{AnyJsRoot, TextRange, TextSize};
+use biome_rowan::{AstNode, Direction};
+use diagnostic::{Diagnostic, Fix, Severity, Span, TextEdit};
+
+use crate::{RuleContext, RuleMeta, RuleRegistry};
+
+/// Reported for suppression comments that are ignored: without rules or a
+/// reason, or for unknown rules.
+pub const INVALID_SUPPRESSION: RuleMeta = RuleMeta {
+    name: "invalid-suppression",
+    description: "Reports suppression comments that can't be applied.",
+    severity: Severity::Error,
+};
+
+/// Reported for suppression comments that suppress nothing, when the
+/// linter is asked to.
+pub const UNUSED_SUPPRESSION: RuleMeta = RuleMeta {
+    name: "unused-suppression",
+    description: "Reports suppression comments that don't suppress any diagnostic.",
+    severity: Severity::Warning,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Directive {
+    /// `// lint-ignore-next-line rule: reason`
+    IgnoreNextLine,
+    /// `// lint-ignore-file rule: reason`
+    IgnoreFile,
+    /// `// lint-disable rule: reason`, until `// lint-enable rule`.
+    Disable,
+    Enable,
+}
+
+impl Directive {
+    const ALL: [(&'static str, Directive); 4] = [
+        ("lint-ignore-next-line", Directive::IgnoreNextLine),
+        ("lint-ignore-file", Directive::IgnoreFile),
+        ("lint-disable", Directive::Disable),
+        ("lint-enable", Directive::Enable),
+    ];
+}
+
+#[derive(Debug)]
+struct Comment {
+    directive: Directive,
+    rules: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Scope {
+    Line(u32),
+    Range(TextRange),
+    File,
+}
+
+impl Scope {
+    fn contains(&self, span: &Span) -> bool {
+        match self {
+            Self::Line(line) => span.start.line == *line,
+            Self::Range(range) => range.contains(span.range.start()),
+            Self::File => true,
+        }
+    }
+}
+
+/// A rule suppressed by a comment.
+#[derive(Debug)]
+struct Suppression {
+    rule: String,
+    scope: Scope,
+    /// The index of the comment in [Suppressions::comments].
+    comment: usize,
+    used: bool,
+}
+
+/// The suppression comments of a file.
+#[derive(Debug, Default)]
+pub(crate) struct Suppressions {
+    comments: Vec<TextRange>,
+    suppressions: Vec<Suppression>,
+}
+
+impl Suppressions {
+    /// Reads the suppression comments of `root`. Comments that can't be
+    /// applied are returned as diagnostics instead.
+    pub fn parse(
+        root: &AnyJsRoot,
+        ctx: &RuleContext,
+        registry: &RuleRegistry,
+    ) -> (Self, Vec<Diagnostic>) {
+        let mut suppressions = Self::default();
+        let mut diagnostics = vec![];
+        // The `lint-disable` comments not enabled again yet: rule, where the
+        // range starts and the comment.
+        let mut open: Vec<(String, TextSize, usize)> = vec![];
+        for token in root.syntax().descendants_tokens(Direction::Next) {
+            let trivia = token
+                .leading_trivia()
+                .pieces()
+                .chain(token.trailing_trivia().pieces());
+            for piece in trivia.filter(|piece| piece.is_comments()) {
+                let range = piece.text_range();
+                let Some(comment) = parse_comment(piece.text()) else {
+                    continue;
+                };
+                let comment =
+                    match comment.and_then(|comment| check_comment(comment, registry, &open)) {
+                        Ok(comment) => comment,
+                        Err(message) => {
+                            diagnostics.push(ctx.diagnostic(&INVALID_SUPPRESSION, range, message));
+                            continue;
+                        }
+                    };
+                let index = suppressions.comments.len();
+                let scope = match comment.directive {
+                    Directive::IgnoreNextLine => Scope::Line(ctx.span(range).end.line + 1),
+                    Directive::IgnoreFile => Scope::File,
+                    Directive::Disable => {
+                        suppressions.comments.push(range);
+                        open.extend(
+                            comment
+                                .rules
+                                .into_iter()
+                                .map(|rule| (rule, range.end(), index)),
+                        );
+                        continue;
+                    }
+                    Directive::Enable => {
+                        for rule in comment.rules {
+                            if let Some(position) = open.iter().position(|(open, ..)| *open == rule)
+                            {
+                                let (rule, start, comment) = open.remove(position);
+                                let scope = Scope::Range(TextRange::new(start, range.start()));
+                                suppressions.push(rule, scope, comment);
+                            }
+                        }
+                        continue;
+                    }
+                };
+                suppressions.comments.push(range);
+                for rule in comment.rules {
+                    suppressions.push(rule, scope, index);
+                }
+            }
+        }
+        // Disabled until the end of the file.
+        let end = root.syntax().text_range().end();
+        for (rule, start, comment) in open {
+            suppressions.push(rule, Scope::Range(TextRange::new(start, end)), comment);
+        }
+        (suppressions, diagnostics)
+    }
+
+    fn push(&mut self, rule: String, scope: Scope, comment: usize) {
+        self.suppressions.push(Suppression {
+            rule,
+            scope,
+            comment,
+            used: false,
+        });
+    }
+
+    /// Removes the suppressed diagnostics, and records which suppressions
+    /// were used.
+    pub fn apply(&mut self, diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+        diagnostics
+            .into_iter()
+            .filter(|diagnostic| {
+                let mut suppressed = false;
+                for suppression in &mut self.suppressions {
+                    if suppression.rule == diagnostic.rule
+                        && suppression.scope.contains(&diagnostic.span)
+                    {
+                        suppression.used = true;
+                        suppressed = true;
+                    }
+                }
+                !suppressed
+            })
+            .collect()
+    }
+
+    /// The comments suppressing enabled rules that didn't report anything.
+    /// Comments that are entirely unused can be removed.
+    pub fn unused(&self, ctx: &RuleContext, registry: &RuleRegistry) -> Vec<Diagnostic> {
+        let mut diagnostics = vec![];
+        for (index, range) in self.comments.iter().enumerate() {
+            let suppressions: Vec<_> = self
+                .suppressions
+                .iter()
+                .filter(|suppression| suppression.comment == index)
+                .collect();
+            let unused: Vec<_> = suppressions
+                .iter()
+                .filter(|suppression| !suppression.used && registry.is_enabled(&suppression.rule))
+                .map(|suppression| format!("`{}`", suppression.rule))
+                .collect();
+            if unused.is_empty() {
+                continue;
+            }
+            let message = format!("Unused suppression of {}", unused.join(", "));
+            let mut diagnostic = ctx.diagnostic(&UNUSED_SUPPRESSION, *range, message);
+            if unused.len() == suppressions.len() {
+                diagnostic = diagnostic.with_fix(Fix {
+                    message: "Remove the comment".to_string(),
+                    edits: vec![TextEdit {
+                        span: ctx.span(*range),
+                        replacement: String::new(),
+                    }],
+                });
+            }
+            diagnostics.push(diagnostic);
+        }
+        diagnostics
+    }
+}
+
+/// The directive of a suppression comment, `None` for other comments.
+fn parse_comment(text: &str) -> Option<Result<Comment, String>> {
+    let text = match text.strip_prefix("//") {
+        Some(text) => text,
+        None => text.strip_prefix("/*")?.strip_suffix("*/")?,
+    };
+    let text = text.trim();
+    let (name, directive, rest) = Directive::ALL.iter().find_map(|(name, directive)| {
+        let rest = text.strip_prefix(name)?;
+        let separated =
+            rest.is_empty() || rest.starts_with(|c: char| c.is_whitespace() || c == ':');
+        separated.then_some((*name, *directive, rest))
+    })?;
+    let (rules, reason) = rest.split_once(':').unwrap_or((rest, ""));
+    let mut rules: Vec<String> = vec![];
+    for rule in rules.split(',').map(str::trim) {
+        if !rule.is_empty() && !rules.iter().any(|known| known == rule) {
+            rules.push(rule.to_string());
+        }
+    }
+    if rules.is_empty() {
+        return Some(Err(format!("`{}` needs the rules it applies to", name)));
+    }
+    if reason.trim().is_empty() && directive != Directive::Enable {
+        return Some(Err(format!(
+            "Suppressions need a reason, like `{} {}: <reason>`",
+            name,
+            rules.join(", ")
+        )));
+    }
+    Some(Ok(Comment { directive, rules }))
+}
+
+/// Checks that the rules of `comment` exist, and that `lint-disable` and
+/// `lint-enable` comments pair up.
+fn check_comment(
+    comment: Comment,
+    registry: &RuleRegistry,
+    open: &[(String, TextSize, usize)],
+) -> Result<Comment, String> {
+    let is_open = |rule: &str| open.iter().any(|(open, ..)| open == rule);
+    for rule in &comment.rules {
+        if registry.get(rule).is_none() {
+            return Err(format!("Unknown rule `{}`", rule));
+        }
+        match comment.directive {
+            Directive::Disable if is_open(rule) => {
+                return Err(format!("`{}` is already disabled", rule))
+            }
+            Directive::Enable if !is_open(rule) => {
+                return Err(format!("`{}` isn't disabled", rule))
+            }
+            _ => {}
+        }
+    }
+    Ok(comment)
+}
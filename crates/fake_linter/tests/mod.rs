@@ -0,0 +1,637 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+        sync::Arc,
+    };
+
+    use biome_js_parser::parse;
+    use biome_js_syntax::JsFileSource;
+    use diagnostic::RenderOptions;
+    use fake_linter::{LintConfig, Linter};
+    use file_system::{OsFileSystem, OverlayFileSystem};
+    use server::{Server, ServerOptions};
+
+    /// The lib files and fixtures are at the root of the repository.
+    fn repo_path(path: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .ancestors()
+            .nth(2)
+            .unwrap()
+            .join(path)
+    }
+
+    /// A server that analyzed `src` as the unsaved file `test.ts`.
+    fn setup_server(src: &str) -> Server {
+        let fs = Arc::new(OverlayFileSystem::new(Arc::new(OsFileSystem)));
+        let path = PathBuf::from("test.ts");
+        fs.open(&path, src);
+        let options = ServerOptions { fs, cache: None };
+        let mut server =
+            Server::with_lib_options(&repo_path("src/lib"), &["es2018".to_string()], options)
+                .unwrap();
+        server.analyze(vec![path]).unwrap();
+        server.print_symbol_table();
+        server
+    }
+
+    #[test]
+    fn test_project_imports() {
+        let tsconfig = &repo_path("src/fixtures/project/tsconfig.json");
+        let mut server = Server::from_tsconfig(&repo_path("src/lib"), tsconfig).unwrap();
+        let paths = server.files().to_vec();
+        server.analyze(paths.clone()).unwrap();
+        let linter = Linter::default();
+
+        let mut diagnostics = vec![];
+        for path in paths {
+            let src = fs::read_to_string(&path).unwrap();
+            let src_type = JsFileSource::try_from(path.as_path()).unwrap();
+            let root = parse(&src, src_type, Default::default()).tree();
+            diagnostics.extend(linter.lint(&server, &path, &root));
+        }
+
+        // `axios.get(...)` and `load()` from src/client.ts.
+        assert_eq!(diagnostics.len(), 2);
+    }
+
+    #[test]
+    fn test_in_memory_project() {
+        let fs = Arc::new(OverlayFileSystem::new(Arc::new(OsFileSystem)));
+        let dir = Path::new("/memory/project");
+        let (tsconfig, index, util) = (
+            dir.join("tsconfig.json"),
+            dir.join("src/index.ts"),
+            dir.join("src/util.ts"),
+        );
+        fs.open(&tsconfig, r#"{ "compilerOptions": { "lib": ["es2018"] } }"#);
+        fs.open(&index, "import { load } from \"./util\";\nload();");
+        fs.open(&util, "export declare function load(): Promise<string>;");
+        let options = ServerOptions {
+            fs: fs.clone(),
+            cache: None,
+        };
+        let mut server =
+            Server::from_tsconfig_options(&repo_path("src/lib"), &tsconfig, options).unwrap();
+        assert_eq!(server.files(), [index.clone(), util.clone()]);
+        server.analyze(server.files().to_vec()).unwrap();
+        assert!(server.unresolved_imports(&index).is_empty());
+
+        let lint = |server: Server| {
+            let src = fs.read_to_string(&index).unwrap();
+            let root = parse(&src, JsFileSource::ts(), Default::default()).tree();
+            Linter::default().lint(&server, &index, &root).len()
+        };
+        assert_eq!(lint(server), 1);
+
+        // An unsaved edit, as an editor would make it.
+        let mut server = Server::from_tsconfig_options(
+            &repo_path("src/lib"),
+            &tsconfig,
+            ServerOptions {
+                fs: fs.clone(),
+                cache: None,
+            },
+        )
+        .unwrap();
+        fs.open(&util, "export declare function load(): string;");
+        server.analyze(server.files().to_vec()).unwrap();
+        assert_eq!(lint(server), 0);
+    }
+
+    #[test]
+    fn test_parse_and_read_errors() {
+        let fs = Arc::new(OverlayFileSystem::new(Arc::new(OsFileSystem)));
+        let (index, util) = (
+            PathBuf::from("/memory/errors/index.ts"),
+            PathBuf::from("/memory/errors/util.ts"),
+        );
+        let src = "import { load } from \"./util\";\nconst = 1;\nload();";
+        fs.open(&index, src);
+        fs.open(&util, "export declare function load(): Promise<string>;");
+        let options = ServerOptions {
+            fs: fs.clone(),
+            cache: None,
+        };
+        let mut server =
+            Server::with_lib_options(&repo_path("src/lib"), &["es2018".to_string()], options)
+                .unwrap();
+
+        // The recovered tree is analyzed, and its syntax errors are kept.
+        server.analyze(vec![index.clone()]).unwrap();
+        let errors = server.parse_errors(&index);
+        assert!(!errors.is_empty());
+        assert_eq!(errors[0].range.map(|range| &src[range]), Some("="));
+        assert!(server.parse_errors(&util).is_empty());
+        let root = parse(src, JsFileSource::ts(), Default::default()).tree();
+        assert_eq!(Linter::default().lint(&server, &index, &root).len(), 1);
+
+        let missing = PathBuf::from("/memory/errors/missing.ts");
+        let err = server.analyze(vec![missing.clone()]).unwrap_err();
+        assert_eq!(err.path, missing);
+    }
+
+    #[test]
+    fn test_recovered_trees() {
+        let fs = Arc::new(OverlayFileSystem::new(Arc::new(OsFileSystem)));
+        let index = PathBuf::from("/memory/recovered/index.ts");
+        let src = "declare function load(): Promise<string>;\n\
+            function run(a: string, 1, b) {}\n\
+            const handle = (, e) => e;\n\
+            load().catch(, 1);\n\
+            load().catch(";
+        fs.open(&index, src);
+        let options = ServerOptions {
+            fs: fs.clone(),
+            cache: None,
+        };
+        let mut server =
+            Server::with_lib_options(&repo_path("src/lib"), &["es2018".to_string()], options)
+                .unwrap();
+
+        // Bogus parameters and missing arguments are skipped, not unwrapped.
+        server.analyze(vec![index.clone()]).unwrap();
+        assert!(!server.parse_errors(&index).is_empty());
+        let root = parse(src, JsFileSource::ts(), Default::default()).tree();
+        Linter::default().lint(&server, &index, &root);
+    }
+
+    #[test]
+    fn test_references_and_ambient_modules() {
+        let index = repo_path("src/fixtures/ambient/index.ts");
+        let mut server = Server::with_lib(&repo_path("src/lib"), &["es2018".to_string()]).unwrap();
+        server.analyze(vec![index.clone()]).unwrap();
+        assert_eq!(
+            server.module_graph().references(&index),
+            [
+                repo_path("src/fixtures/ambient/types/shims.d.ts"),
+                repo_path("src/fixtures/ambient/node_modules/@types/fetcher/index.d.ts"),
+            ]
+        );
+
+        server.set_current_path(&index);
+        assert!(server.get_type_info("logo".to_string()).is_some());
+
+        let src = fs::read_to_string(&index).unwrap();
+        let root = parse(&src, JsFileSource::ts(), Default::default()).tree();
+        let diagnostics = Linter::default().lint(&server, &index, &root);
+
+        // `loadConfig()` from the "config" module and the global `fetchData()`.
+        assert_eq!(diagnostics.len(), 2);
+    }
+
+    #[test]
+    fn test_no_floating_promises_linter() {
+        let src = r#"
+        async function test(): Promise<void> {
+            Promise.resolve("value");
+            Promise.resolve("value").then(() => {});
+            Promise.resolve("value").catch();
+            Promise.resolve("value").finally();
+        }
+        "#;
+
+        let server = setup_server(src);
+
+        let src_type = JsFileSource::ts();
+        let root = parse(src, src_type, Default::default()).tree();
+
+        let diagnostics = Linter::default().lint(&server, Path::new("test.ts"), &root);
+        for d in &diagnostics {
+            println!("{}", d);
+        }
+        assert_eq!(diagnostics.len(), 4);
+    }
+
+    #[test]
+    fn test_diagnostic_rendering() {
+        use diagnostic::{Position, Severity};
+        use fake_linter::rules::NoFloatingPromises;
+
+        let src = "async function load(): Promise<string> {\n  return \"\";\n}\nasync function main() {\n  load().catch(1);\n}\nload();\n";
+        let server = setup_server(src);
+        let root = parse(src, JsFileSource::ts(), Default::default()).tree();
+        let diagnostics = Linter::default().lint(&server, Path::new("test.ts"), &root);
+        assert_eq!(diagnostics.len(), 2);
+        let (handler, floating) = (&diagnostics[0], &diagnostics[1]);
+        assert_eq!(handler.rule, NoFloatingPromises::META.name);
+        assert_eq!(handler.severity, Severity::Error);
+        assert_eq!(handler.span.start, Position { line: 5, column: 3 });
+        assert_eq!(
+            handler.span.end,
+            Position {
+                line: 5,
+                column: 19
+            }
+        );
+        let fixes: Vec<_> = handler
+            .fixes
+            .iter()
+            .map(|fix| fix.message.as_str())
+            .collect();
+        assert_eq!(fixes, ["Add `await`", "Add `void` to ignore the Promise"]);
+        assert_eq!(handler.fixes[0].edits[0].replacement, "await ");
+        assert_eq!(
+            handler.render(src, &RenderOptions::default()),
+            "\
+error[no-floating-promises]: Unhandled Promise with non-function handler
+ --> test.ts:5:3
+  |
+5 |   load().catch(1);
+  |   ^^^^^^^^^^^^^^^^
+  |              --- not a function
+  |
+  = note: Promises must be awaited, end with a call to `.catch`, or end with a call to `.then` with a rejection handler.
+  = help: Add `await`
+  = help: Add `void` to ignore the Promise
+"
+        );
+
+        // `await` can't be used outside of an async function.
+        assert_eq!(floating.fixes.len(), 1);
+        assert_eq!(
+            floating.to_string(),
+            "test.ts:7:1: error[no-floating-promises]: Unhandled Promise"
+        );
+    }
+
+    #[test]
+    fn test_rule_registry() {
+        use biome_js_syntax::{JsSyntaxKind, JsSyntaxNode};
+        use diagnostic::{Diagnostic, Severity};
+        use fake_linter::{Rule, RuleContext, RuleMeta, RuleRegistry};
+
+        struct NoDebugger;
+        const NO_DEBUGGER: RuleMeta = RuleMeta {
+            name: "no-debugger",
+            description: "Disallows `debugger` statements.",
+            severity: Severity::Warning,
+        };
+        impl Rule for NoDebugger {
+            fn meta(&self) -> &'static RuleMeta {
+                &NO_DEBUGGER
+            }
+
+            fn kinds(&self) -> &'static [JsSyntaxKind] {
+                &[JsSyntaxKind::JS_DEBUGGER_STATEMENT]
+            }
+
+            fn run(&self, ctx: &RuleContext, node: &JsSyntaxNode) -> Vec<Diagnostic> {
+                vec![ctx.diagnostic(
+                    &NO_DEBUGGER,
+                    node.text_trimmed_range(),
+                    "Unexpected `debugger`",
+                )]
+            }
+        }
+
+        let src =
+            "async function load(): Promise<string> {\n  return \"\";\n}\ndebugger;\nload();\n";
+        let server = setup_server(src);
+        let root = parse(src, JsFileSource::ts(), Default::default()).tree();
+        let mut registry = RuleRegistry::builtin();
+        registry.register(Box::new(NoDebugger));
+        let names: Vec<_> = registry.rules().map(|rule| rule.meta().name).collect();
+        assert_eq!(names, ["no-floating-promises", "no-debugger"]);
+
+        let lint = |registry: RuleRegistry| {
+            let diagnostics = Linter::new(registry).lint(&server, Path::new("test.ts"), &root);
+            diagnostics
+                .into_iter()
+                .map(|diagnostic| {
+                    (
+                        diagnostic.rule,
+                        diagnostic.severity,
+                        diagnostic.span.start.line,
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            lint(registry),
+            [
+                ("no-debugger".to_string(), Severity::Warning, 4),
+                ("no-floating-promises".to_string(), Severity::Error, 5),
+            ]
+        );
+
+        let mut registry = RuleRegistry::builtin();
+        registry.register(Box::new(NoDebugger));
+        assert!(registry.set_enabled("no-floating-promises", false));
+        assert!(!registry.set_enabled("no-such-rule", false));
+        assert!(!registry.is_enabled("no-floating-promises"));
+        assert_eq!(
+            lint(registry),
+            [("no-debugger".to_string(), Severity::Warning, 4)]
+        );
+    }
+
+    #[test]
+    fn test_lint_config() {
+        use diagnostic::Severity;
+        use file_system::MemoryFileSystem;
+
+        let dir = Path::new("/memory/config");
+        let fs: MemoryFileSystem = [
+            (
+                dir.join("lint.config.json"),
+                r#"{
+                    // Comments are allowed.
+                    "include": ["src"],
+                    "ignore": ["src/generated"],
+                    "rules": {
+                        "no-floating-promises": {
+                            "severity": "warning",
+                            "options": { "ignoreVoid": false }
+                        }
+                    },
+                    "overrides": [
+                        { "files": ["src/legacy/**/*.ts"], "rules": { "no-floating-promises": "off" } }
+                    ]
+                }"#,
+            ),
+            (dir.join("src/index.ts"), ""),
+            (dir.join("src/index.d.ts"), ""),
+            (dir.join("src/styles.css"), ""),
+            (dir.join("src/legacy/old.ts"), ""),
+            (dir.join("src/generated/api.ts"), ""),
+            (dir.join("test/index.test.ts"), ""),
+        ]
+        .into_iter()
+        .collect();
+        let path = LintConfig::find(&fs, dir).unwrap();
+        assert_eq!(path, dir.join("lint.config.json"));
+        // Configs apply to the directories below them.
+        assert_eq!(
+            LintConfig::find(&fs, &dir.join("src/legacy")),
+            Some(path.clone())
+        );
+        let config = LintConfig::load(&fs, &path).unwrap();
+        assert_eq!(
+            config.files(&fs).unwrap(),
+            [
+                dir.join("src/index.d.ts"),
+                dir.join("src/index.ts"),
+                dir.join("src/legacy/old.ts"),
+            ]
+        );
+        assert!(config.is_ignored(&dir.join("src/generated/api.ts")));
+        assert!(config.is_ignored(&dir.join("test/index.test.ts")));
+        assert!(!config.is_ignored(&dir.join("src/index.ts")));
+
+        let src =
+            "async function load(): Promise<string> {\n  return \"\";\n}\nload();\nvoid load();\n";
+        let server = setup_server(src);
+        let root = parse(src, JsFileSource::ts(), Default::default()).tree();
+        let lint = |path: &Path| {
+            Linter::new(config.registry(path))
+                .lint(&server, path, &root)
+                .into_iter()
+                .map(|diagnostic| (diagnostic.severity, diagnostic.span.start.line))
+                .collect::<Vec<_>>()
+        };
+        // `void` doesn't handle the rejection with `ignoreVoid` off.
+        assert_eq!(
+            lint(&dir.join("src/index.ts")),
+            [(Severity::Warning, 4), (Severity::Warning, 5)]
+        );
+        assert_eq!(lint(&dir.join("src/legacy/old.ts")), []);
+
+        // The same config in TOML.
+        fs.insert(
+            dir.join("lint.config.toml"),
+            "include = [\"src\"]\n\n[rules]\nno-floating-promises = \"info\"\n",
+        );
+        let config = LintConfig::load(&fs, &dir.join("lint.config.toml")).unwrap();
+        let registry = config.registry(&dir.join("src/index.ts"));
+        assert_eq!(
+            registry.severity("no-floating-promises"),
+            Some(Severity::Info)
+        );
+
+        let error = |text: &str| {
+            fs.insert(dir.join("lint.config.json"), text);
+            LintConfig::load(&fs, &dir.join("lint.config.json"))
+                .unwrap_err()
+                .to_string()
+        };
+        let path = dir.join("lint.config.json");
+        assert_eq!(
+            error(r#"{ "rules": { "no-floating-promise": "error" } }"#),
+            format!(
+                "{}: unknown rule `no-floating-promise`, did you mean `no-floating-promises`?",
+                path.display()
+            )
+        );
+        assert_eq!(
+            error(r#"{ "rules": { "no-floating-promises": "fatal" } }"#),
+            format!(
+                "{}: invalid setting for `no-floating-promises`: unknown severity `fatal`, expected `off`, `info`, `warning` or `error`",
+                path.display()
+            )
+        );
+        assert!(error(
+            r#"{ "rules": { "no-floating-promises": { "options": { "ignoreAll": true } } } }"#
+        )
+        .starts_with(&format!(
+            "{}: invalid setting for `no-floating-promises`: unknown field `ignoreAll`",
+            path.display()
+        )));
+        assert!(
+            error(r#"{ "exclude": [] }"#).starts_with(&format!("Cannot parse {}", path.display()))
+        );
+    }
+
+    #[test]
+    fn test_suppression_comments() {
+        use fake_linter::RuleRegistry;
+
+        let src = r#"async function load(): Promise<string> {
+  return "";
+}
+// lint-ignore-next-line no-floating-promises: fire and forget
+load();
+load();
+/* lint-disable no-floating-promises: legacy code */
+load();
+load();
+// lint-enable no-floating-promises
+load();
+// lint-ignore-next-line no-floating-promises
+load();
+// lint-ignore-next-line no-floating-promise: typo
+load();
+// lint-ignore-next-line no-floating-promises: nothing to suppress
+const x = 1;
+"#;
+        let server = setup_server(src);
+        let root = parse(src, JsFileSource::ts(), Default::default()).tree();
+        let lint = |linter: Linter| {
+            linter
+                .lint(&server, Path::new("test.ts"), &root)
+                .into_iter()
+                .map(|diagnostic| {
+                    (
+                        diagnostic.rule,
+                        diagnostic.span.start.line,
+                        diagnostic.message,
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        let unhandled = |line| {
+            (
+                "no-floating-promises".to_string(),
+                line,
+                "Unhandled Promise".to_string(),
+            )
+        };
+        let invalid =
+            |line, message: &str| ("invalid-suppression".to_string(), line, message.to_string());
+        let mut expected = vec![
+            unhandled(6),
+            unhandled(11),
+            invalid(
+                12,
+                "Suppressions need a reason, like `lint-ignore-next-line no-floating-promises: <reason>`",
+            ),
+            unhandled(13),
+            invalid(14, "Unknown rule `no-floating-promise`"),
+            unhandled(15),
+        ];
+        assert_eq!(lint(Linter::default()), expected);
+
+        expected.push((
+            "unused-suppression".to_string(),
+            16,
+            "Unused suppression of `no-floating-promises`".to_string(),
+        ));
+        assert_eq!(
+            lint(Linter::default().with_unused_suppressions(true)),
+            expected
+        );
+
+        // A disabled rule isn't reported as unused.
+        let mut registry = RuleRegistry::builtin();
+        registry.set_enabled("no-floating-promises", false);
+        let diagnostics = lint(Linter::new(registry).with_unused_suppressions(true));
+        assert!(diagnostics
+            .iter()
+            .all(|(rule, ..)| rule == "invalid-suppression"));
+
+        // File-level suppressions, and `lint-enable` without `lint-disable`.
+        let src = "// lint-ignore-file no-floating-promises: generated\nasync function load(): Promise<string> {\n  return \"\";\n}\nload();\n// lint-enable no-floating-promises\n";
+        let server = setup_server(src);
+        let root = parse(src, JsFileSource::ts(), Default::default()).tree();
+        let diagnostics = Linter::default().with_unused_suppressions(true).lint(
+            &server,
+            Path::new("test.ts"),
+            &root,
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, "invalid-suppression");
+        assert_eq!(
+            diagnostics[0].message,
+            "`no-floating-promises` isn't disabled"
+        );
+    }
+
+    #[test]
+    fn test_1() {
+        let src = r#"
+        async function returnPromise(): Promise<string> {
+            return "value";
+        }
+        // invalid
+        returnPromise();
+        returnPromise().then(() => {});
+        returnPromise().catch();
+        returnPromise().finally();
+
+        //valid
+        await returnPromise();
+        (async () => {
+            await returnPromise();
+            await returnPromise().then(() => {});
+            await returnPromise().catch();
+            await returnPromise().finally();
+        })();
+        "#;
+
+        let server = setup_server(src);
+
+        let src_type = JsFileSource::ts();
+        let root = parse(src, src_type, Default::default()).tree();
+
+        let diagnostics = Linter::default().lint(&server, Path::new("test.ts"), &root);
+        for d in &diagnostics {
+            println!("{}", d);
+        }
+    }
+
+    #[test]
+    fn test_2() {
+        let src = r#"
+        declare const promiseValue: Promise<number>;
+        async function test4(): Promise<void> {
+            promiseValue;
+            promiseValue.then(() => {});
+            promiseValue.catch();
+            await promiseValue.finally();
+        }
+        test4();
+        "#;
+
+        let server = setup_server(src);
+
+        let src_type = JsFileSource::ts();
+        let root = parse(src, src_type, Default::default()).tree();
+
+        let diagnostics = Linter::default().lint(&server, Path::new("test.ts"), &root);
+        for d in &diagnostics {
+            println!("{}", d);
+        }
+    }
+
+    #[test]
+    fn test_3() {
+        let src = r#"
+        declare const promiseOrNumber: Promise<number> | number;
+
+async function test() {
+  promiseOrNumber;
+}
+        "#;
+
+        let server = setup_server(src);
+
+        let src_type = JsFileSource::ts();
+        let root = parse(src, src_type, Default::default()).tree();
+
+        let diagnostics = Linter::default().lint(&server, Path::new("test.ts"), &root);
+        for d in &diagnostics {
+            println!("{}", d);
+        }
+    }
+
+    #[test]
+    fn test_4() {
+        let src = r#"
+        const foo = async (): Promise<void> => {
+            Promise.resolve("value");
+        }
+        foo();
+        "#;
+
+        let server = setup_server(src);
+
+        let src_type = JsFileSource::ts();
+        let root = parse(src, src_type, Default::default()).tree();
+
+        let diagnostics = Linter::default().lint(&server, Path::new("test.ts"), &root);
+        for d in &diagnostics {
+            println!("{}", d);
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt, fs, io,
+    path::{Component, Path, PathBuf},
+    sync::{Arc, RwLock},
+};
+
+/// Where sources, configs and packages are read from. Paths are used as
+/// given: callers join and normalize them.
+pub trait FileSystem: fmt::Debug + Send + Sync {
+    fn read_to_string(&self, path: &Path) -> io::Result<String>;
+
+    fn is_file(&self, path: &Path) -> bool;
+
+    fn is_dir(&self, path: &Path) -> bool;
+
+    /// The paths of the entries of the directory at `path`, sorted.
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>>;
+}
+
+/// A file that can't be read.
+#[derive(Debug)]
+pub struct ReadError {
+    pub path: PathBuf,
+    pub error: io::Error,
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Cannot read {}: {}", self.path.display(), self.error)
+    }
+}
+
+impl std::error::Error for ReadError {}
+
+/// The real file system.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct OsFileSystem;
+
+impl FileSystem for OsFileSystem {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        fs::read_to_string(path)
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        path.is_dir()
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let mut paths = fs::read_dir(path)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<io::Result<Vec<_>>>()?;
+        paths.sort();
+        Ok(paths)
+    }
+}
+
+/// Files held in memory, e.g. a multi-file project set up by a test.
+/// Directories exist as long as some file is in them.
+#[derive(Debug, Default)]
+pub struct MemoryFileSystem {
+    files: RwLock<BTreeMap<PathBuf, String>>,
+}
+
+impl MemoryFileSystem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the file at `path`, or replaces its text.
+    pub fn insert(&self, path: impl Into<PathBuf>, text: impl Into<String>) {
+        self.files.write().unwrap().insert(path.into(), text.into());
+    }
+
+    pub fn remove(&self, path: &Path) -> Option<String> {
+        self.files.write().unwrap().remove(path)
+    }
+}
+
+impl<P: Into<PathBuf>, T: Into<String>> FromIterator<(P, T)> for MemoryFileSystem {
+    fn from_iter<I: IntoIterator<Item = (P, T)>>(files: I) -> Self {
+        let files = files
+            .into_iter()
+            .map(|(path, text)| (path.into(), text.into()))
+            .collect();
+        Self {
+            files: RwLock::new(files),
+        }
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.files
+            .read()
+            .unwrap()
+            .get(path)
+            .cloned()
+            .ok_or_else(|| not_found(path))
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.files.read().unwrap().contains_key(path)
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.files
+            .read()
+            .unwrap()
+            .keys()
+            .any(|file| file != path && file.starts_with(path))
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let entries: BTreeSet<_> = self
+            .files
+            .read()
+            .unwrap()
+            .keys()
+            .filter_map(
+                |file| match file.strip_prefix(path).ok()?.components().next()? {
+                    Component::Normal(name) => Some(path.join(name)),
+                    _ => None,
+                },
+            )
+            .collect();
+        if entries.is_empty() {
+            return Err(not_found(path));
+        }
+        Ok(entries.into_iter().collect())
+    }
+}
+
+/// Unsaved editor buffers over another file system. Opened files shadow the
+/// files of the base, and may not exist there at all.
+#[derive(Debug)]
+pub struct OverlayFileSystem {
+    base: Arc<dyn FileSystem>,
+    overlay: MemoryFileSystem,
+}
+
+impl OverlayFileSystem {
+    pub fn new(base: Arc<dyn FileSystem>) -> Self {
+        Self {
+            base,
+            overlay: MemoryFileSystem::new(),
+        }
+    }
+
+    /// Sets the unsaved text of the file at `path`.
+    pub fn open(&self, path: impl Into<PathBuf>, text: impl Into<String>) {
+        self.overlay.insert(path, text);
+    }
+
+    /// Goes back to the base's version of the file at `path`.
+    pub fn close(&self, path: &Path) {
+        self.overlay.remove(path);
+    }
+}
+
+impl FileSystem for OverlayFileSystem {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.overlay
+            .read_to_string(path)
+            .or_else(|_| self.base.read_to_string(path))
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.overlay.is_file(path) || self.base.is_file(path)
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.overlay.is_dir(path) || self.base.is_dir(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let overlay = self.overlay.read_dir(path);
+        let base = self.base.read_dir(path);
+        if overlay.is_err() {
+            return base;
+        }
+        let entries: BTreeSet<_> = overlay.into_iter().chain(base).flatten().collect();
+        Ok(entries.into_iter().collect())
+    }
+}
+
+fn not_found(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("{} not found", path.display()),
+    )
+}
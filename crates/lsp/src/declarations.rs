@@ -0,0 +1,81 @@
+use biome_js_syntax::{AnyJsRoot, JsSyntaxKind, JsSyntaxNode, TextRange};
+use biome_rowan::AstNode;
+use lsp_types::SymbolKind;
+
+/// A name declared at the top level of a file, or of a namespace or
+/// `declare module` block in it.
+pub(crate) struct Declaration {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// The whole declaration.
+    pub range: TextRange,
+    /// The declared name.
+    pub name_range: TextRange,
+}
+
+/// The top-level declarations of `root`, in source order. Imports aren't
+/// declarations of the file.
+pub(crate) fn declarations(root: &AnyJsRoot) -> Vec<Declaration> {
+    root.syntax()
+        .descendants()
+        .filter(|node| {
+            matches!(
+                node.kind(),
+                JsSyntaxKind::JS_IDENTIFIER_BINDING | JsSyntaxKind::TS_IDENTIFIER_BINDING
+            )
+        })
+        .filter(is_top_level)
+        .filter_map(|binding| {
+            let declaration = binding.ancestors().skip(1).find_map(|node| {
+                let kind = symbol_kind(node.kind())?;
+                Some((node, kind))
+            });
+            let (node, kind) = declaration?;
+            Some(Declaration {
+                name: binding.text_trimmed().to_string(),
+                kind,
+                range: node.text_trimmed_range(),
+                name_range: binding.text_trimmed_range(),
+            })
+        })
+        .collect()
+}
+
+/// Whether a binding declares a name visible from the whole file, rather
+/// than a parameter or a local of some function or block.
+fn is_top_level(binding: &JsSyntaxNode) -> bool {
+    !binding.ancestors().skip(1).any(|node| {
+        matches!(
+            node.kind(),
+            JsSyntaxKind::JS_IMPORT
+                | JsSyntaxKind::JS_FUNCTION_BODY
+                | JsSyntaxKind::JS_PARAMETERS
+                | JsSyntaxKind::JS_ARROW_FUNCTION_EXPRESSION
+                | JsSyntaxKind::JS_FUNCTION_EXPRESSION
+                | JsSyntaxKind::JS_BLOCK_STATEMENT
+                | JsSyntaxKind::JS_CATCH_CLAUSE
+                | JsSyntaxKind::JS_FOR_STATEMENT
+                | JsSyntaxKind::JS_FOR_IN_STATEMENT
+                | JsSyntaxKind::JS_FOR_OF_STATEMENT
+                | JsSyntaxKind::JS_CLASS_MEMBER_LIST
+                | JsSyntaxKind::TS_TYPE_MEMBER_LIST
+                | JsSyntaxKind::TS_TYPE_PARAMETERS
+        )
+    })
+}
+
+fn symbol_kind(kind: JsSyntaxKind) -> Option<SymbolKind> {
+    let kind = match kind {
+        JsSyntaxKind::JS_FUNCTION_DECLARATION | JsSyntaxKind::TS_DECLARE_FUNCTION_DECLARATION => {
+            SymbolKind::FUNCTION
+        }
+        JsSyntaxKind::JS_CLASS_DECLARATION => SymbolKind::CLASS,
+        JsSyntaxKind::TS_INTERFACE_DECLARATION => SymbolKind::INTERFACE,
+        JsSyntaxKind::TS_TYPE_ALIAS_DECLARATION => SymbolKind::TYPE_PARAMETER,
+        JsSyntaxKind::TS_ENUM_DECLARATION => SymbolKind::ENUM,
+        JsSyntaxKind::TS_MODULE_DECLARATION => SymbolKind::NAMESPACE,
+        JsSyntaxKind::JS_VARIABLE_DECLARATOR => SymbolKind::VARIABLE,
+        _ => return None,
+    };
+    Some(kind)
+}
@@ -0,0 +1,556 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use biome_js_parser::{parse, Parse};
+use biome_js_semantic::{semantic_model, Binding, SemanticModelOptions};
+use biome_js_syntax::{
+    AnyJsBinding, AnyJsBindingPattern, AnyJsExpression, AnyJsRoot, JsFileSource,
+    JsFunctionDeclaration, JsIdentifierBinding, JsReferenceIdentifier, JsSyntaxKind, JsSyntaxNode,
+    JsSyntaxToken, JsVariableDeclarator, TextRange, TextSize, TsReferenceType,
+};
+use biome_rowan::AstNode;
+use diagnostic::{ColumnUnit, LineIndex, Severity};
+use fake_linter::Linter;
+use file_system::{FileSystem, OsFileSystem, OverlayFileSystem};
+use lsp_types::{
+    Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, DidChangeTextDocumentParams,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams, DocumentSymbol, DocumentSymbolParams,
+    DocumentSymbolResponse, GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverContents,
+    HoverParams, InlayHint, InlayHintKind, InlayHintLabel, InlayHintParams, Location,
+    MarkupContent, MarkupKind, MessageType, Position, PublishDiagnosticsParams, Range,
+    ReferenceParams, ShowMessageParams, TextDocumentPositionParams, Url,
+};
+use rustc_hash::FxHashMap;
+use server::{Server, ServerOptions};
+use type_info::{
+    printer::DEFAULT_MAX_LENGTH,
+    symbol::{self, Namespace, Symbol},
+    PrintOptions, Type, TypeId,
+};
+
+use crate::declarations::declarations;
+
+const HOVER_OPTIONS: PrintOptions = PrintOptions {
+    max_length: Some(DEFAULT_MAX_LENGTH),
+    expand_aliases: false,
+    multiline: true,
+};
+
+/// Inlay hints sit in the code, so they are kept short.
+const INLAY_HINT_OPTIONS: PrintOptions = PrintOptions {
+    max_length: Some(30),
+    expand_aliases: false,
+    multiline: false,
+};
+
+/// The state behind the LSP front-end: a [Server] whose files are read
+/// through the unsaved buffers of the open documents.
+pub struct LanguageServer {
+    fs: Arc<OverlayFileSystem>,
+    server: Server,
+    /// The text of the open documents. It only reaches `fs` and the server
+    /// while it parses, since a half-typed edit would fail to analyze, so
+    /// `fs` keeps the text that was last analyzed.
+    documents: FxHashMap<PathBuf, String>,
+    /// Errors to show the user, e.g. a `tsconfig.json` that can't be loaded.
+    messages: Vec<ShowMessageParams>,
+}
+
+impl LanguageServer {
+    /// Loads the project of the `tsconfig.json` in `root`, if any, or else
+    /// only the `libs` from `lib_dir`. A project that fails to load falls
+    /// back to the `libs`, and libs that fail to load to none, with the
+    /// errors left in [LanguageServer::take_messages].
+    pub fn new(lib_dir: &Path, libs: &[String], root: Option<&Path>) -> Self {
+        let fs = Arc::new(OverlayFileSystem::new(Arc::new(OsFileSystem)));
+        let options = ServerOptions {
+            fs: fs.clone(),
+            cache: None,
+        };
+        let mut language_server = Self {
+            fs: fs.clone(),
+            server: Server::without_libs(options.clone()),
+            documents: FxHashMap::default(),
+            messages: vec![],
+        };
+        let tsconfig = root
+            .map(|root| root.join("tsconfig.json"))
+            .filter(|tsconfig| fs.is_file(tsconfig));
+        if let Some(tsconfig) = tsconfig {
+            match Server::from_tsconfig_options(lib_dir, &tsconfig, options.clone()) {
+                Ok(mut server) => {
+                    if let Err(err) = server.analyze(server.files().to_vec()) {
+                        language_server.show_error(err);
+                    }
+                    language_server.server = server;
+                    return language_server;
+                }
+                Err(err) => language_server.show_error(err),
+            }
+        }
+        match Server::with_lib_options(lib_dir, libs, options) {
+            Ok(server) => language_server.server = server,
+            Err(err) => language_server.show_error(err),
+        }
+        language_server
+    }
+
+    /// The errors to show the user since the last call, as
+    /// `window/showMessage` notifications.
+    pub fn take_messages(&mut self) -> Vec<ShowMessageParams> {
+        std::mem::take(&mut self.messages)
+    }
+
+    fn show_error(&mut self, err: impl ToString) {
+        self.messages.push(ShowMessageParams {
+            typ: MessageType::ERROR,
+            message: err.to_string(),
+        });
+    }
+
+    pub fn server(&self) -> &Server {
+        &self.server
+    }
+
+    fn server_mut(&mut self) -> &mut Server {
+        &mut self.server
+    }
+
+    pub fn did_open(&mut self, params: DidOpenTextDocumentParams) -> Vec<PublishDiagnosticsParams> {
+        let Ok(path) = params.text_document.uri.to_file_path() else {
+            return vec![];
+        };
+        self.sync(path, params.text_document.text);
+        self.lint_open_documents()
+    }
+
+    /// Applies full or incremental changes to the document's text. A change
+    /// with a range outside the text is an error, and leaves the document as
+    /// it was.
+    pub fn did_change(
+        &mut self,
+        params: DidChangeTextDocumentParams,
+    ) -> Vec<PublishDiagnosticsParams> {
+        let Ok(path) = params.text_document.uri.to_file_path() else {
+            return vec![];
+        };
+        let Some(mut text) = self.text(&path) else {
+            return vec![];
+        };
+        for change in params.content_changes {
+            let Some(range) = change.range else {
+                text = change.text;
+                continue;
+            };
+            let index = line_index(&text);
+            let (Some(start), Some(end)) = (
+                text_offset(&index, range.start),
+                text_offset(&index, range.end),
+            ) else {
+                self.show_error(format!("Invalid change range in {}", path.display()));
+                return vec![];
+            };
+            if start > end {
+                self.show_error(format!("Invalid change range in {}", path.display()));
+                return vec![];
+            }
+            text.replace_range(usize::from(start)..usize::from(end), &change.text);
+        }
+        self.sync(path, text);
+        self.lint_open_documents()
+    }
+
+    /// Goes back to the file on disk, and clears the document's diagnostics.
+    pub fn did_close(
+        &mut self,
+        params: DidCloseTextDocumentParams,
+    ) -> Vec<PublishDiagnosticsParams> {
+        let uri = params.text_document.uri;
+        let Ok(path) = uri.to_file_path() else {
+            return vec![];
+        };
+        self.documents.remove(&path);
+        self.fs.close(&path);
+        match self.fs.read_to_string(&path) {
+            Ok(text) => {
+                if let Err(err) = self.server_mut().update_file(&path, &text) {
+                    self.show_error(err);
+                }
+            }
+            Err(_) => self.server_mut().remove_file(&path),
+        }
+        let mut published = self.lint_open_documents();
+        published.push(PublishDiagnosticsParams::new(uri, vec![], None));
+        published
+    }
+
+    /// The inferred type of the expression, declaration or type reference
+    /// under the cursor.
+    pub fn hover(&self, params: HoverParams) -> Option<Hover> {
+        let (path, text, _, token) = self.token_at(&params.text_document_position_params)?;
+        let (range, ty) = self.type_at(&path, &token)?;
+        let printed = self
+            .server()
+            .scoped_analyzer(&path)
+            .print_type(ty, &HOVER_OPTIONS);
+        let index = line_index(&text);
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: format!("```ts\n{printed}\n```"),
+            }),
+            range: Some(lsp_range(&index, range)),
+        })
+    }
+
+    /// Where the name under the cursor is declared. Imported names lead to
+    /// their declaration in the imported file.
+    pub fn definition(&self, params: GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+        let (path, text, root, token) = self.token_at(&params.text_document_position_params)?;
+        let node = token.parent()?;
+        if let Some(symbol) = self.server().definition_of(&path, &node) {
+            return self.goto_declarations(symbol);
+        }
+        // Locals, which the server doesn't index.
+        let binding = local_binding(&root, &node)?;
+        let target = location(&path, &text, binding.syntax().text_trimmed_range())?;
+        Some(GotoDefinitionResponse::Scalar(target))
+    }
+
+    /// Where the type of the name or expression under the cursor is declared.
+    pub fn type_definition(&self, params: GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+        let (path, _, _, token) = self.token_at(&params.text_document_position_params)?;
+        let (_, ty) = self.type_at(&path, &token)?;
+        let name = match &*self.server().get_type(ty) {
+            Type::TypeRef(type_ref) => &type_ref.name,
+            Type::Interface(interface) => &interface.name,
+            Type::Alias(alias) => &alias.name,
+            _ => return None,
+        };
+        let analyzer = self.server().scoped_analyzer(&path);
+        let symbol = analyzer.lookup_in_scope(analyzer.current_path(), Namespace::Type, name)?;
+        self.goto_declarations(symbol)
+    }
+
+    /// The references to the name under the cursor, in every file for
+    /// symbols and in its file for locals.
+    pub fn references(&self, params: ReferenceParams) -> Option<Vec<Location>> {
+        let (path, text, root, token) = self.token_at(&params.text_document_position)?;
+        let node = token.parent()?;
+        if let Some(symbol) = self.server().definition_of(&path, &node) {
+            let mut locations = vec![];
+            if params.context.include_declaration {
+                locations.extend(symbol.declarations.iter().cloned());
+            }
+            locations.extend(self.server().references_of(symbol));
+            return locations
+                .iter()
+                .map(|location| self.symbol_location(location))
+                .collect();
+        }
+        let binding = local_binding(&root, &node)?;
+        let mut ranges = vec![];
+        if params.context.include_declaration {
+            ranges.push(binding.syntax().text_trimmed_range());
+        }
+        ranges.extend(
+            binding
+                .all_references()
+                .map(|reference| reference.syntax().text_trimmed_range()),
+        );
+        ranges
+            .into_iter()
+            .map(|range| location(&path, &text, range))
+            .collect()
+    }
+
+    /// The inferred types of variables and function return types that
+    /// aren't annotated.
+    pub fn inlay_hints(&self, params: InlayHintParams) -> Option<Vec<InlayHint>> {
+        let path = params.text_document.uri.to_file_path().ok()?;
+        let (text, root) = self.parse_analyzed(&path)?;
+        let index = line_index(&text);
+        let analyzer = self.server().scoped_analyzer(&path);
+        let symbol_type = |binding: &JsSyntaxNode| {
+            let symbol = analyzer.binding_symbol(binding)?;
+            Some(analyzer.flow_type_at(symbol, binding))
+        };
+
+        let mut hints = vec![];
+        for node in root.syntax().descendants() {
+            let hint = if let Some(declarator) = JsVariableDeclarator::cast_ref(&node) {
+                if declarator.variable_annotation().is_some() {
+                    continue;
+                }
+                let Ok(AnyJsBindingPattern::AnyJsBinding(AnyJsBinding::JsIdentifierBinding(
+                    binding,
+                ))) = declarator.id()
+                else {
+                    continue;
+                };
+                let ty = symbol_type(binding.syntax());
+                ty.map(|ty| (binding.syntax().text_trimmed_range().end(), ty))
+            } else if let Some(function) = JsFunctionDeclaration::cast_ref(&node) {
+                if function.return_type_annotation().is_some() {
+                    continue;
+                }
+                let (Ok(id), Ok(parameters)) = (function.id(), function.parameters()) else {
+                    continue;
+                };
+                let ty = symbol_type(id.syntax());
+                match ty.map(|ty| analyzer.get_type(ty)).as_deref() {
+                    Some(Type::Function(signature)) => Some((
+                        parameters.syntax().text_trimmed_range().end(),
+                        signature.return_type,
+                    )),
+                    _ => None,
+                }
+            } else {
+                None
+            };
+            let Some((offset, ty)) = hint else {
+                continue;
+            };
+            let position = lsp_position(&index, offset);
+            if position < params.range.start || position > params.range.end {
+                continue;
+            }
+            hints.push(InlayHint {
+                position,
+                label: InlayHintLabel::String(format!(
+                    ": {}",
+                    analyzer.print_type(ty, &INLAY_HINT_OPTIONS)
+                )),
+                kind: Some(InlayHintKind::TYPE),
+                text_edits: None,
+                tooltip: None,
+                padding_left: None,
+                padding_right: None,
+                data: None,
+            });
+        }
+        Some(hints)
+    }
+
+    /// The top-level declarations of the document.
+    #[allow(deprecated)]
+    pub fn document_symbols(&self, params: DocumentSymbolParams) -> Option<DocumentSymbolResponse> {
+        let path = params.text_document.uri.to_file_path().ok()?;
+        let (text, root) = self.parse(&path)?;
+        let index = line_index(&text);
+        let symbols = declarations(&root)
+            .into_iter()
+            .map(|declaration| DocumentSymbol {
+                name: declaration.name,
+                detail: None,
+                kind: declaration.kind,
+                tags: None,
+                deprecated: None,
+                range: lsp_range(&index, declaration.range),
+                selection_range: lsp_range(&index, declaration.name_range),
+                children: None,
+            })
+            .collect();
+        Some(DocumentSymbolResponse::Nested(symbols))
+    }
+
+    /// Keeps `text` as the document's text, and analyzes it if it parses.
+    fn sync(&mut self, path: PathBuf, text: String) {
+        if !parse_text(&path, &text).has_errors() {
+            self.fs.open(&path, text.as_str());
+            if let Err(err) = self.server_mut().update_file(&path, &text) {
+                self.show_error(err);
+            }
+        }
+        self.documents.insert(path, text);
+    }
+
+    /// Lints the open documents that parse. The others keep the diagnostics
+    /// published before the edit that broke them.
+    fn lint_open_documents(&mut self) -> Vec<PublishDiagnosticsParams> {
+        let mut paths: Vec<_> = self.documents.keys().cloned().collect();
+        paths.sort();
+        let linter = Linter::default();
+        let mut published = vec![];
+        for path in paths {
+            let text = &self.documents[&path];
+            let parsed = parse_text(&path, text);
+            let Ok(uri) = Url::from_file_path(&path) else {
+                continue;
+            };
+            if parsed.has_errors() {
+                continue;
+            }
+            let index = line_index(text);
+            let diagnostics = linter
+                .lint(self.server(), &path, &parsed.tree())
+                .iter()
+                .map(|diagnostic| lsp_diagnostic(&uri, &index, diagnostic))
+                .collect();
+            published.push(PublishDiagnosticsParams::new(uri, diagnostics, None));
+        }
+        published
+    }
+
+    /// The text of the open document at `path`, or else of the file.
+    fn text(&self, path: &Path) -> Option<String> {
+        match self.documents.get(path) {
+            Some(text) => Some(text.clone()),
+            None => self.fs.read_to_string(path).ok(),
+        }
+    }
+
+    fn parse(&self, path: &Path) -> Option<(String, AnyJsRoot)> {
+        let text = self.text(path)?;
+        let root = parse_text(path, &text).tree();
+        Some((text, root))
+    }
+
+    /// The text the server last analyzed for `path`, and its tree. Ranges
+    /// from the analysis only match this text, so an open document whose
+    /// edits don't parse yet has none, and queries on it get no answer.
+    fn parse_analyzed(&self, path: &Path) -> Option<(String, AnyJsRoot)> {
+        let text = self.fs.read_to_string(path).ok()?;
+        if self
+            .documents
+            .get(path)
+            .is_some_and(|document| *document != text)
+        {
+            return None;
+        }
+        let root = parse_text(path, &text).tree();
+        Some((text, root))
+    }
+
+    fn token_at(
+        &self,
+        position: &TextDocumentPositionParams,
+    ) -> Option<(PathBuf, String, AnyJsRoot, JsSyntaxToken)> {
+        let path = position.text_document.uri.to_file_path().ok()?;
+        let (text, root) = self.parse_analyzed(&path)?;
+        let offset = text_offset(&line_index(&text), position.position)?;
+        let token = root.syntax().token_at_offset(offset).right_biased()?;
+        Some((path, text, root, token))
+    }
+
+    /// The type of what `token` is part of, and the range it covers.
+    fn type_at(&self, path: &Path, token: &JsSyntaxToken) -> Option<(TextRange, TypeId)> {
+        let analyzer = self.server().scoped_analyzer(path);
+        let parent = token.parent()?;
+        let name = token.text_trimmed();
+        match parent.kind() {
+            JsSyntaxKind::JS_IDENTIFIER_BINDING => {
+                let symbol = analyzer.binding_symbol(&parent)?;
+                Some((
+                    parent.text_trimmed_range(),
+                    analyzer.flow_type_at(symbol, &parent),
+                ))
+            }
+            JsSyntaxKind::TS_IDENTIFIER_BINDING => {
+                let symbol =
+                    analyzer.lookup_in_scope(analyzer.current_path(), Namespace::Type, name)?;
+                Some((parent.text_trimmed_range(), symbol.ty))
+            }
+            _ if parent
+                .ancestors()
+                .any(|node| TsReferenceType::can_cast(node.kind())) =>
+            {
+                let symbol =
+                    analyzer.lookup_in_scope(analyzer.current_path(), Namespace::Type, name)?;
+                Some((parent.text_trimmed_range(), symbol.ty))
+            }
+            _ => {
+                let expr = parent.ancestors().find_map(AnyJsExpression::cast)?;
+                let ty = analyzer.analyze_expression(&expr);
+                Some((expr.syntax().text_trimmed_range(), ty))
+            }
+        }
+    }
+
+    fn goto_declarations(&self, symbol: &Symbol) -> Option<GotoDefinitionResponse> {
+        let mut locations: Vec<_> = symbol
+            .declarations
+            .iter()
+            .filter_map(|location| self.symbol_location(location))
+            .collect();
+        match locations.len() {
+            0 => None,
+            1 => locations.pop().map(GotoDefinitionResponse::Scalar),
+            _ => Some(GotoDefinitionResponse::Array(locations)),
+        }
+    }
+
+    fn symbol_location(&self, declared: &symbol::Location) -> Option<Location> {
+        let text = self.fs.read_to_string(&declared.path).ok()?;
+        location(&declared.path, &text, declared.range)
+    }
+}
+
+/// The binding `node`, a name, declares or refers to within its file.
+fn local_binding(root: &AnyJsRoot, node: &JsSyntaxNode) -> Option<Binding> {
+    let model = semantic_model(root, SemanticModelOptions::default());
+    match JsIdentifierBinding::cast_ref(node) {
+        Some(binding) => Some(model.as_binding(&binding)),
+        None => model.binding(&JsReferenceIdentifier::cast_ref(node)?),
+    }
+}
+
+/// LSP positions start at 0 and count UTF-16 code units.
+fn line_index(text: &str) -> LineIndex {
+    LineIndex::with_unit(text, ColumnUnit::Utf16)
+}
+
+fn lsp_position(index: &LineIndex, offset: TextSize) -> Position {
+    let position = index.position(offset);
+    Position::new(position.line - 1, position.column - 1)
+}
+
+fn lsp_range(index: &LineIndex, range: TextRange) -> Range {
+    Range::new(
+        lsp_position(index, range.start()),
+        lsp_position(index, range.end()),
+    )
+}
+
+fn text_offset(index: &LineIndex, position: Position) -> Option<TextSize> {
+    index.offset(diagnostic::Position {
+        line: position.line.checked_add(1)?,
+        column: position.character.saturating_add(1),
+    })
+}
+
+fn lsp_diagnostic(uri: &Url, index: &LineIndex, diagnostic: &diagnostic::Diagnostic) -> Diagnostic {
+    let severity = match diagnostic.severity {
+        Severity::Error => DiagnosticSeverity::ERROR,
+        Severity::Warning => DiagnosticSeverity::WARNING,
+        Severity::Info => DiagnosticSeverity::INFORMATION,
+    };
+    let related = diagnostic
+        .labels
+        .iter()
+        .map(|label| DiagnosticRelatedInformation {
+            location: Location::new(uri.clone(), lsp_range(index, label.span.range)),
+            message: label.message.clone(),
+        })
+        .collect::<Vec<_>>();
+    Diagnostic {
+        range: lsp_range(index, diagnostic.span.range),
+        severity: Some(severity),
+        source: Some(diagnostic.rule.clone()),
+        message: diagnostic.message.clone(),
+        related_information: (!related.is_empty()).then_some(related),
+        ..Default::default()
+    }
+}
+
+fn parse_text(path: &Path, text: &str) -> Parse<AnyJsRoot> {
+    let source_type = JsFileSource::try_from(path).unwrap_or(JsFileSource::ts());
+    parse(text, source_type, Default::default())
+}
+
+fn location(path: &Path, text: &str, range: TextRange) -> Option<Location> {
+    let uri = Url::from_file_path(path).ok()?;
+    Some(Location::new(uri, lsp_range(&line_index(text), range)))
+}
@@ -0,0 +1,157 @@
+//! A language server over stdio, backed by [server::Server] for types and
+//! by the no-floating-promises linter for diagnostics.
+
+mod declarations;
+mod language_server;
+
+use std::{error::Error, path::PathBuf};
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+        Notification as LspNotification, PublishDiagnostics, ShowMessage,
+    },
+    request::{
+        DocumentSymbolRequest, GotoDefinition, GotoTypeDefinition, HoverRequest, InlayHintRequest,
+        References, Request as LspRequest,
+    },
+    HoverProviderCapability, InitializeParams, OneOf, PublishDiagnosticsParams, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind, TypeDefinitionProviderCapability,
+};
+
+pub use language_server::LanguageServer;
+
+/// Serves the client on stdin/stdout until it shuts the server down. Files
+/// that no `tsconfig.json` in the workspace covers see the `default_libs`.
+pub fn run_stdio(
+    lib_dir: PathBuf,
+    default_libs: Vec<String>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let (connection, io_threads) = Connection::stdio();
+    let params = connection.initialize(serde_json::to_value(capabilities())?)?;
+    let params: InitializeParams = serde_json::from_value(params)?;
+    let root = workspace_root(&params);
+
+    let mut server = LanguageServer::new(&lib_dir, &default_libs, root.as_deref());
+    show_messages(&connection, &mut server)?;
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    break;
+                }
+                connection
+                    .sender
+                    .send(Message::Response(dispatch(&mut server, request)))?;
+            }
+            Message::Notification(notification) => {
+                for params in notify(&mut server, notification) {
+                    let notification =
+                        Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+                    connection
+                        .sender
+                        .send(Message::Notification(notification))?;
+                }
+            }
+            Message::Response(_) => {}
+        }
+        show_messages(&connection, &mut server)?;
+    }
+    io_threads.join()?;
+    Ok(())
+}
+
+/// Sends the errors the server ran into as `window/showMessage`.
+fn show_messages(
+    connection: &Connection,
+    server: &mut LanguageServer,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    for params in server.take_messages() {
+        let notification = Notification::new(ShowMessage::METHOD.to_string(), params);
+        connection
+            .sender
+            .send(Message::Notification(notification))?;
+    }
+    Ok(())
+}
+
+fn capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(
+            TextDocumentSyncKind::INCREMENTAL,
+        )),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        type_definition_provider: Some(TypeDefinitionProviderCapability::Simple(true)),
+        references_provider: Some(OneOf::Left(true)),
+        inlay_hint_provider: Some(OneOf::Left(true)),
+        document_symbol_provider: Some(OneOf::Left(true)),
+        ..Default::default()
+    }
+}
+
+#[allow(deprecated)]
+fn workspace_root(params: &InitializeParams) -> Option<PathBuf> {
+    let folder = params
+        .workspace_folders
+        .as_ref()
+        .and_then(|folders| folders.first())
+        .map(|folder| &folder.uri);
+    folder.or(params.root_uri.as_ref())?.to_file_path().ok()
+}
+
+fn dispatch(server: &mut LanguageServer, request: Request) -> Response {
+    match request.method.as_str() {
+        HoverRequest::METHOD => respond::<HoverRequest>(request, |params| server.hover(params)),
+        GotoDefinition::METHOD => {
+            respond::<GotoDefinition>(request, |params| server.definition(params))
+        }
+        GotoTypeDefinition::METHOD => {
+            respond::<GotoTypeDefinition>(request, |params| server.type_definition(params))
+        }
+        References::METHOD => respond::<References>(request, |params| server.references(params)),
+        InlayHintRequest::METHOD => {
+            respond::<InlayHintRequest>(request, |params| server.inlay_hints(params))
+        }
+        DocumentSymbolRequest::METHOD => {
+            respond::<DocumentSymbolRequest>(request, |params| server.document_symbols(params))
+        }
+        method => Response::new_err(
+            request.id,
+            ErrorCode::MethodNotFound as i32,
+            format!("unsupported request: {}", method),
+        ),
+    }
+}
+
+fn respond<R: LspRequest>(
+    request: Request,
+    handle: impl FnOnce(R::Params) -> R::Result,
+) -> Response {
+    match serde_json::from_value(request.params) {
+        Ok(params) => Response::new_ok(request.id, handle(params)),
+        Err(error) => Response::new_err(
+            request.id,
+            ErrorCode::InvalidParams as i32,
+            error.to_string(),
+        ),
+    }
+}
+
+/// Handles a document notification, returning the diagnostics to publish.
+fn notify(
+    server: &mut LanguageServer,
+    notification: Notification,
+) -> Vec<PublishDiagnosticsParams> {
+    let params = notification.params;
+    let published = match notification.method.as_str() {
+        DidOpenTextDocument::METHOD => serde_json::from_value(params).map(|p| server.did_open(p)),
+        DidChangeTextDocument::METHOD => {
+            serde_json::from_value(params).map(|p| server.did_change(p))
+        }
+        DidCloseTextDocument::METHOD => serde_json::from_value(params).map(|p| server.did_close(p)),
+        _ => return vec![],
+    };
+    published.unwrap_or_default()
+}
@@ -0,0 +1,231 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+    };
+
+    /// The lib files and fixtures are at the root of the repository.
+    fn repo_path(path: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .ancestors()
+            .nth(2)
+            .unwrap()
+            .join(path)
+    }
+
+    #[test]
+    fn test_language_server() {
+        use lsp::LanguageServer;
+        use lsp_types::{
+            DidChangeTextDocumentParams, DidOpenTextDocumentParams, DocumentSymbolParams,
+            DocumentSymbolResponse, GotoDefinitionParams, GotoDefinitionResponse, HoverContents,
+            HoverParams, Position, Range, TextDocumentContentChangeEvent, TextDocumentIdentifier,
+            TextDocumentItem, TextDocumentPositionParams, Url, VersionedTextDocumentIdentifier,
+        };
+
+        let mut server = LanguageServer::new(&repo_path("src/lib"), &["es2018".to_string()], None);
+        assert!(server.take_messages().is_empty());
+        let util = Url::from_file_path("/memory/lsp/util.ts").unwrap();
+        let index = Url::from_file_path("/memory/lsp/index.ts").unwrap();
+        let open = |uri: &Url, text: &str| DidOpenTextDocumentParams {
+            text_document: TextDocumentItem::new(uri.clone(), "typescript".into(), 1, text.into()),
+        };
+        let at = |uri: &Url, line, character| TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier::new(uri.clone()),
+            position: Position::new(line, character),
+        };
+
+        server.did_open(open(
+            &util,
+            "export declare function load(): Promise<string>;",
+        ));
+        let published = server.did_open(open(&index, "import { load } from \"./util\";\nload();"));
+        let diagnostics = |published: &[lsp_types::PublishDiagnosticsParams]| {
+            published
+                .iter()
+                .find(|params| params.uri == index)
+                .map(|params| params.diagnostics.len())
+        };
+        assert_eq!(diagnostics(&published), Some(1));
+
+        let hover = server
+            .hover(HoverParams {
+                text_document_position_params: at(&index, 1, 0),
+                work_done_progress_params: Default::default(),
+            })
+            .unwrap();
+        let HoverContents::Markup(contents) = hover.contents else {
+            panic!("expected markup");
+        };
+        assert!(contents.value.contains("Promise<string>"));
+
+        let definition = server.definition(GotoDefinitionParams {
+            text_document_position_params: at(&index, 1, 0),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        });
+        let Some(GotoDefinitionResponse::Scalar(location)) = definition else {
+            panic!("expected a definition");
+        };
+        assert_eq!(location.uri, util);
+        assert_eq!(location.range.start, Position::new(0, 24));
+
+        let symbols = server.document_symbols(DocumentSymbolParams {
+            text_document: TextDocumentIdentifier::new(util.clone()),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        });
+        let Some(DocumentSymbolResponse::Nested(symbols)) = symbols else {
+            panic!("expected document symbols");
+        };
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(symbols[0].name, "load");
+
+        // Typing `void ` before the call, one edit at a time.
+        let mut published = vec![];
+        for (character, text) in [(0, "vo"), (2, "id ")] {
+            published = server.did_change(DidChangeTextDocumentParams {
+                text_document: VersionedTextDocumentIdentifier::new(index.clone(), 2),
+                content_changes: vec![TextDocumentContentChangeEvent {
+                    range: Some(Range::new(
+                        Position::new(1, character),
+                        Position::new(1, character),
+                    )),
+                    range_length: None,
+                    text: text.into(),
+                }],
+            });
+        }
+        assert_eq!(diagnostics(&published), Some(0));
+
+        // While an edit doesn't parse, positions can't be mapped onto the
+        // text the server analyzed.
+        server.did_change(DidChangeTextDocumentParams {
+            text_document: VersionedTextDocumentIdentifier::new(index.clone(), 3),
+            content_changes: vec![TextDocumentContentChangeEvent {
+                range: Some(Range::new(Position::new(1, 0), Position::new(1, 0))),
+                range_length: None,
+                text: "const = ".into(),
+            }],
+        });
+        let hover = server.hover(HoverParams {
+            text_document_position_params: at(&index, 1, 13),
+            work_done_progress_params: Default::default(),
+        });
+        assert!(hover.is_none());
+    }
+
+    #[test]
+    fn test_language_server_shadowed_names() {
+        use lsp::LanguageServer;
+        use lsp_types::{
+            DidOpenTextDocumentParams, HoverContents, HoverParams, InlayHintLabel, InlayHintParams,
+            Position, Range, TextDocumentIdentifier, TextDocumentItem, TextDocumentPositionParams,
+            Url,
+        };
+
+        let mut server = LanguageServer::new(&repo_path("src/lib"), &["es2018".to_string()], None);
+        let uri = Url::from_file_path("/memory/lsp-shadowing/index.ts").unwrap();
+        let src = "declare const name: string;\nfunction greet(name: number) {\n    const label = name;\n}\nconst label = name;\n";
+        server.did_open(DidOpenTextDocumentParams {
+            text_document: TextDocumentItem::new(uri.clone(), "typescript".into(), 1, src.into()),
+        });
+
+        // The parameter, not the constant it shadows.
+        let hover = server
+            .hover(HoverParams {
+                text_document_position_params: TextDocumentPositionParams {
+                    text_document: TextDocumentIdentifier::new(uri.clone()),
+                    position: Position::new(1, 15),
+                },
+                work_done_progress_params: Default::default(),
+            })
+            .unwrap();
+        let HoverContents::Markup(contents) = hover.contents else {
+            panic!("expected markup");
+        };
+        assert!(contents.value.contains("number"));
+
+        let hints = server
+            .inlay_hints(InlayHintParams {
+                text_document: TextDocumentIdentifier::new(uri),
+                range: Range::new(Position::new(0, 0), Position::new(5, 0)),
+                work_done_progress_params: Default::default(),
+            })
+            .unwrap();
+        let labels: Vec<_> = hints
+            .iter()
+            .filter_map(|hint| match &hint.label {
+                InlayHintLabel::String(label) => Some((hint.position, label.as_str())),
+                _ => None,
+            })
+            .filter(|(position, _)| position.line != 1)
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                (Position::new(2, 15), ": number"),
+                (Position::new(4, 11), ": string"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_language_server_errors() {
+        use lsp::LanguageServer;
+        use lsp_types::{
+            DidChangeTextDocumentParams, DidOpenTextDocumentParams, MessageType, Position, Range,
+            TextDocumentContentChangeEvent, TextDocumentItem, Url, VersionedTextDocumentIdentifier,
+        };
+
+        let messages = |server: &mut LanguageServer| -> Vec<_> {
+            server
+                .take_messages()
+                .into_iter()
+                .map(|params| {
+                    assert_eq!(params.typ, MessageType::ERROR);
+                    params.message
+                })
+                .collect()
+        };
+
+        // A broken tsconfig falls back to the libs, and unknown libs to none.
+        let dir = std::env::temp_dir().join(format!("type_poc_lsp_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("tsconfig.json"),
+            r#"{ "compilerOptions": { "target": "es1" } }"#,
+        )
+        .unwrap();
+        let mut server =
+            LanguageServer::new(&repo_path("src/lib"), &["es9".to_string()], Some(&dir));
+        fs::remove_dir_all(&dir).unwrap();
+        let tsconfig = dir.join("tsconfig.json");
+        assert_eq!(
+            messages(&mut server),
+            [
+                format!("Unknown target 'es1' in {}", tsconfig.display()),
+                "Cannot find lib definition for 'es9'".to_string(),
+            ]
+        );
+
+        let uri = Url::from_file_path("/memory/lsp-errors/index.ts").unwrap();
+        server.did_open(DidOpenTextDocumentParams {
+            text_document: TextDocumentItem::new(uri.clone(), "typescript".into(), 1, "a;".into()),
+        });
+        let published = server.did_change(DidChangeTextDocumentParams {
+            text_document: VersionedTextDocumentIdentifier::new(uri, 2),
+            content_changes: vec![TextDocumentContentChangeEvent {
+                range: Some(Range::new(Position::new(0, 2), Position::new(0, 0))),
+                range_length: None,
+                text: "b".into(),
+            }],
+        });
+        assert!(published.is_empty());
+        assert_eq!(
+            messages(&mut server),
+            ["Invalid change range in /memory/lsp-errors/index.ts"]
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use std::{
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// Identifies cache files written by this tool.
+const MAGIC: &[u8; 8] = b"TPOCACHE";
+
+/// Bump when the layout of cached entries changes, e.g. a new `Type`
+/// variant. Entries of other versions are ignored.
+const FORMAT_VERSION: u32 = 7;
+
+/// Tells apart the temporary files of the entries being written at once.
+static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// An on-disk cache of analyzed declaration files: the lib files and the
+/// `.d.ts` files of a program. Entries are keyed by a hash of the content
+/// they were computed from, so changed files miss the cache.
+#[derive(Debug, Clone)]
+pub struct DeclarationCache {
+    dir: PathBuf,
+}
+
+/// The parts of a parsed declaration file needed to build the module graph.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ParsedDeclarations {
+    pub is_module: bool,
+    pub specifiers: Vec<String>,
+}
+
+impl DeclarationCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The entry stored under `key`, if any was stored by the same version
+    /// of the tool and can be read.
+    pub fn load<T: DeserializeOwned>(&self, key: CacheKey) -> Option<T> {
+        let bytes = fs::read(self.entry_path(key)).ok()?;
+        let payload = bytes.strip_prefix(header(key).as_slice())?;
+        bincode::deserialize(payload).ok()
+    }
+
+    /// Stores `value` under `key`. Failing to write only makes the next run
+    /// slower, so errors are ignored.
+    pub fn store<T: Serialize>(&self, key: CacheKey, value: &T) {
+        let Ok(payload) = bincode::serialize(value) else {
+            return;
+        };
+        let mut bytes = header(key);
+        bytes.extend(payload);
+        if fs::create_dir_all(&self.dir).is_err() {
+            return;
+        }
+        // Written aside and renamed, so that concurrent runs and threads
+        // never read a partial entry.
+        let path = self.entry_path(key);
+        let tmp = path.with_extension(format!(
+            "{}.{}.tmp",
+            std::process::id(),
+            TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        if fs::write(&tmp, bytes).is_ok() && fs::rename(&tmp, &path).is_err() {
+            let _ = fs::remove_file(&tmp);
+        }
+    }
+
+    fn entry_path(&self, key: CacheKey) -> PathBuf {
+        self.dir.join(format!("{}.bin", key.0.to_hex()))
+    }
+}
+
+/// Also holds the whole key, so that an entry is only used for the content
+/// it was computed from.
+fn header(key: CacheKey) -> Vec<u8> {
+    let mut header = MAGIC.to_vec();
+    header.extend(FORMAT_VERSION.to_le_bytes());
+    header.extend(env!("CARGO_PKG_VERSION").as_bytes());
+    header.push(0);
+    header.extend(key.0.as_bytes());
+    header
+}
+
+/// A BLAKE3 hash of the content a cache entry is computed from, wide enough
+/// that different content never gets the same key in practice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CacheKey(blake3::Hash);
+
+impl CacheKey {
+    /// Combines what an entry depends on, tagged with the kind of entry so
+    /// that different entries of the same content don't collide.
+    pub fn new(kind: &str, parts: impl IntoIterator<Item = impl Hash>) -> Self {
+        let mut hasher = KeyHasher(blake3::Hasher::new());
+        kind.hash(&mut hasher);
+        for part in parts {
+            part.hash(&mut hasher);
+        }
+        Self(hasher.0.finalize())
+    }
+
+    pub fn of_content(src: &str) -> Self {
+        Self::new("content", [src])
+    }
+}
+
+/// Feeds what [Hash] writes to BLAKE3, whose full output makes the key.
+struct KeyHasher(blake3::Hasher);
+
+impl Hasher for KeyHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let hash = self.0.finalize();
+        u64::from_le_bytes(hash.as_bytes()[..8].try_into().unwrap())
+    }
+}
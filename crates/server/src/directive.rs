@@ -0,0 +1,49 @@
+/// A `/// <reference ... />` directive at the top of a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TripleSlashReference {
+    /// `/// <reference lib="es2015" />`
+    Lib(String),
+    /// `/// <reference path="./globals.d.ts" />`
+    Path(String),
+    /// `/// <reference types="node" />`
+    Types(String),
+}
+
+/// Collects the triple-slash references of `src`. Like tsc, only the
+/// comments before the first statement are considered.
+pub fn triple_slash_references(src: &str) -> Vec<TripleSlashReference> {
+    src.lines()
+        .map(str::trim)
+        .take_while(|line| {
+            line.is_empty()
+                || line.starts_with("//")
+                || line.starts_with("/*")
+                || line.starts_with('*')
+        })
+        .filter_map(|line| {
+            let directive = line.strip_prefix("///")?.trim_start();
+            if !directive.starts_with("<reference") {
+                return None;
+            }
+            if let Some(lib) = attribute(directive, "lib") {
+                Some(TripleSlashReference::Lib(lib.to_string()))
+            } else if let Some(path) = attribute(directive, "path") {
+                Some(TripleSlashReference::Path(path.to_string()))
+            } else {
+                attribute(directive, "types")
+                    .map(|types| TripleSlashReference::Types(types.to_string()))
+            }
+        })
+        .collect()
+}
+
+fn attribute<'a>(directive: &'a str, name: &str) -> Option<&'a str> {
+    for quote in ['"', '\''] {
+        let prefix = format!(" {}={}", name, quote);
+        if let Some(start) = directive.find(&prefix) {
+            let value = &directive[start + prefix.len()..];
+            return value.find(quote).map(|end| &value[..end]);
+        }
+    }
+    None
+}
@@ -0,0 +1,111 @@
+use std::path::{Component, Path, PathBuf};
+
+use file_system::FileSystem;
+
+/// Matches `path` against a tsconfig `include`/`exclude` pattern. Both must be
+/// absolute and normalized. `*` and `?` don't cross `/`, and a `**` segment
+/// matches any number of directories.
+pub fn matches(pattern: &Path, path: &Path) -> bool {
+    let pattern = segments(pattern);
+    let path = segments(path);
+    match_segments(&pattern, &path)
+}
+
+/// Like [matches], but also true for files under a matched directory, the way
+/// tsc applies `exclude` patterns.
+pub fn matches_prefix(pattern: &Path, path: &Path) -> bool {
+    path.ancestors().any(|ancestor| matches(pattern, ancestor))
+}
+
+/// The directory to start walking from: the part of `pattern` before the
+/// first wildcard.
+pub fn base_dir(pattern: &Path) -> PathBuf {
+    pattern
+        .components()
+        .take_while(|component| !has_wildcard(&component.as_os_str().to_string_lossy()))
+        .collect()
+}
+
+pub fn has_wildcard(pattern: &str) -> bool {
+    pattern.contains(['*', '?'])
+}
+
+/// Resolves `.` and `..` without touching the file system.
+pub fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// A directory without wildcards, like `"src"`, includes everything in it.
+pub fn include_pattern(pattern: PathBuf) -> PathBuf {
+    let last = pattern
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    if !has_wildcard(&last) && !last.contains('.') {
+        pattern.join("**/*")
+    } else {
+        pattern
+    }
+}
+
+/// Calls `visit` with the files under `dir`, skipping those and the
+/// directories matching `exclude`.
+pub fn walk(fs: &dyn FileSystem, dir: &Path, exclude: &[PathBuf], visit: &mut dyn FnMut(&Path)) {
+    let Ok(paths) = fs.read_dir(dir) else {
+        return;
+    };
+    for path in paths {
+        if exclude.iter().any(|pattern| matches_prefix(pattern, &path)) {
+            continue;
+        }
+        if fs.is_dir(&path) {
+            walk(fs, &path, exclude, visit);
+        } else {
+            visit(&path);
+        }
+    }
+}
+
+fn segments(path: &Path) -> Vec<String> {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect()
+}
+
+fn match_segments(pattern: &[String], path: &[String]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((first, rest)) if first == "**" => {
+            (0..=path.len()).any(|skip| match_segments(rest, &path[skip..]))
+        }
+        Some((first, rest)) => match path.split_first() {
+            Some((segment, path)) => match_segment(first, segment) && match_segments(rest, path),
+            None => false,
+        },
+    }
+}
+
+fn match_segment(pattern: &str, segment: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let segment: Vec<char> = segment.chars().collect();
+    match_chars(&pattern, &segment)
+}
+
+fn match_chars(pattern: &[char], segment: &[char]) -> bool {
+    match pattern.split_first() {
+        None => segment.is_empty(),
+        Some(('*', rest)) => (0..=segment.len()).any(|skip| match_chars(rest, &segment[skip..])),
+        Some(('?', rest)) => !segment.is_empty() && match_chars(rest, &segment[1..]),
+        Some((c, rest)) => segment.first() == Some(c) && match_chars(rest, &segment[1..]),
+    }
+}
@@ -1,44 +1,677 @@
-use std::path::PathBuf;
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
-use analyzer::TypeAnalyzer;
+use analyzer::{
+    ArityMismatch, FileBindings, Reference, TypeAnalyzer, UnresolvedImport, UnsupportedDeclaration,
+};
+use biome_diagnostics::Diagnostic;
 use biome_js_parser::parse;
-use biome_js_syntax::{JsFileSource, JsSyntaxKind, JsSyntaxNode};
-use type_info::{symbol::Symbol, Type};
+use biome_js_syntax::{
+    AnyJsExpression, AnyJsRoot, JsFileSource, JsSyntaxKind, JsSyntaxNode, TextRange,
+};
+use biome_rowan::{AstNode, SendNode};
+pub use file_system::ReadError;
+use file_system::{FileSystem, OsFileSystem};
+use rayon::prelude::*;
+use rustc_hash::{FxHashMap, FxHashSet};
+use type_info::{
+    symbol::{Location, Namespace, Symbol},
+    Type, TypeArena, TypeId,
+};
 use visitor::Visitor;
 
+mod cache;
+pub mod directive;
+pub mod glob;
+mod lib_files;
+pub mod module_graph;
+pub mod module_resolution;
+pub mod tsconfig;
+pub mod version;
+
+use cache::ParsedDeclarations;
+pub use cache::{CacheKey, DeclarationCache};
+use directive::{triple_slash_references, TripleSlashReference};
+pub use lib_files::{find_lib_dir, resolve_lib_files, LibError};
+use module_graph::{is_module, module_specifiers, ModuleGraph, ModuleImport};
+use module_resolution::ModuleResolver;
+use tsconfig::{TsConfig, TsConfigError};
+
 #[derive(Debug)]
 pub struct Server {
     analyzer: TypeAnalyzer,
+    config: TsConfig,
+    files: Vec<PathBuf>,
+    resolver: ModuleResolver,
+    module_graph: ModuleGraph,
+    sources: Sources,
+    /// Files that declare globals or ambient modules.
+    shared_files: FxHashSet<PathBuf>,
+    /// What analyzing each file found besides its declarations.
+    analyses: FxHashMap<PathBuf, FileAnalysis>,
+    /// The number of types interned after the last full analysis. Updates
+    /// leave the types of the old text behind, so the arena is rebuilt once
+    /// it doubles.
+    live_types: usize,
+    fs: Arc<dyn FileSystem>,
+    cache: Option<DeclarationCache>,
+}
+
+/// What analyzing a file found besides its declarations. Files restored
+/// from the cache have nothing.
+#[derive(Debug, Default)]
+struct FileAnalysis {
+    /// The symbols the file refers to.
+    references: Vec<Reference>,
+    /// Its local names and the assignments to its bindings.
+    bindings: Arc<FileBindings>,
+    /// Its calls with the wrong number of arguments.
+    arity_mismatches: Vec<ArityMismatch>,
+    /// Its imports that bind nothing.
+    unresolved_imports: Vec<UnresolvedImport>,
+    /// Its classes, enums and namespaces, which aren't analyzed.
+    unsupported_declarations: Vec<UnsupportedDeclaration>,
+}
+
+/// A syntax error in a file. The file is still analyzed, as far as the
+/// parser recovered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub range: Option<TextRange>,
+    pub message: String,
+}
+
+impl ParseError {
+    fn new(diagnostic: &impl Diagnostic) -> Self {
+        Self {
+            range: diagnostic.location().span,
+            message: Description(diagnostic).to_string(),
+        }
+    }
+}
+
+struct Description<'a, D>(&'a D);
+
+impl<D: Diagnostic> fmt::Display for Description<'_, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.description(f)
+    }
+}
+
+/// The loaded files. Syntax trees are kept to analyze a file again after a
+/// change to a file it depends on.
+#[derive(Default)]
+struct Sources(FxHashMap<PathBuf, Source>);
+
+struct Source {
+    /// `None` when the file was loaded from the cache.
+    root: Option<SendNode>,
+    content_key: CacheKey,
+    /// The key of the cached analysis of a declaration file.
+    analysis_key: Option<CacheKey>,
+    parse_errors: Vec<ParseError>,
+}
+
+impl Sources {
+    fn get(&self, path: &Path) -> Option<&Source> {
+        self.0.get(path)
+    }
+
+    fn insert(&mut self, path: PathBuf, file: &mut ParsedFile) {
+        let source = Source {
+            root: file.root.take(),
+            content_key: file.content_key,
+            analysis_key: None,
+            parse_errors: std::mem::take(&mut file.parse_errors),
+        };
+        self.0.insert(path, source);
+    }
+
+    fn set_analysis_key(&mut self, path: &Path, key: Option<CacheKey>) {
+        if let Some(source) = self.0.get_mut(path) {
+            source.analysis_key = key;
+        }
+    }
+
+    fn remove(&mut self, path: &Path) {
+        self.0.remove(path);
+    }
+}
+
+impl fmt::Debug for Sources {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.0.keys()).finish()
+    }
+}
+
+/// How a [Server] reads files, and where it caches analyzed declaration
+/// files.
+#[derive(Debug, Clone)]
+pub struct ServerOptions {
+    pub fs: Arc<dyn FileSystem>,
+    pub cache: Option<DeclarationCache>,
+}
+
+impl Default for ServerOptions {
+    fn default() -> Self {
+        Self {
+            fs: Arc::new(OsFileSystem),
+            cache: None,
+        }
+    }
 }
 
 impl Server {
-    pub fn new(builtin_path: Vec<PathBuf>) -> Self {
+    /// Fails if a lib file at `builtin_path` can't be read.
+    pub fn new(builtin_path: Vec<PathBuf>) -> Result<Self, ReadError> {
+        Self::with_options(builtin_path, ServerOptions::default())
+    }
+
+    /// Like [Server::new], reading files through `options.fs`. With a cache,
+    /// the built-in types and the declaration files of the program are
+    /// loaded from it when they haven't changed.
+    pub fn with_options(
+        builtin_path: Vec<PathBuf>,
+        options: ServerOptions,
+    ) -> Result<Self, ReadError> {
+        let analyzer = match &options.cache {
+            Some(cache) => cached_builtins(options.fs.as_ref(), cache, builtin_path)?,
+            None => TypeAnalyzer::with_file_system(options.fs.as_ref(), builtin_path)?,
+        };
+        Ok(Self::with_analyzer(analyzer, options))
+    }
+
+    /// A server without lib files, e.g. when they can't be loaded.
+    pub fn without_libs(options: ServerOptions) -> Self {
+        Self::with_analyzer(TypeAnalyzer::default(), options)
+    }
+
+    fn with_analyzer(analyzer: TypeAnalyzer, options: ServerOptions) -> Self {
+        let ServerOptions { fs, cache } = options;
         Self {
-            analyzer: TypeAnalyzer::new(builtin_path),
+            live_types: analyzer.types().len(),
+            analyzer,
+            config: TsConfig::default(),
+            files: vec![],
+            resolver: ModuleResolver::with_file_system(fs.clone()),
+            module_graph: ModuleGraph::default(),
+            sources: Sources::default(),
+            shared_files: FxHashSet::default(),
+            analyses: FxHashMap::default(),
+            fs,
+            cache,
         }
     }
 
-    pub fn analyze(&mut self, paths: Vec<PathBuf>) {
-        for p in paths {
-            let src_type = JsFileSource::ts();
-            let src = std::fs::read_to_string(&p).unwrap();
-            let parsed = parse(&src, src_type, Default::default());
-            if parsed.has_errors() {
-                panic!("Failed to parse source code: {:?}", parsed.diagnostics());
+    /// Loads the lib files named like tsconfig's `lib` option, e.g. `["es2015", "dom"]`.
+    pub fn with_lib(lib_dir: &Path, libs: &[String]) -> Result<Self, LibError> {
+        Self::with_lib_options(lib_dir, libs, ServerOptions::default())
+    }
+
+    pub fn with_lib_options(
+        lib_dir: &Path,
+        libs: &[String],
+        options: ServerOptions,
+    ) -> Result<Self, LibError> {
+        let builtin_path = resolve_lib_files(options.fs.as_ref(), lib_dir, libs)?;
+        Self::with_options(builtin_path, options).map_err(|err| LibError::Io(err.path, err.error))
+    }
+
+    /// Sets up the project described by a `tsconfig.json`: its libs and its
+    /// files, including the `@types` packages it pulls in.
+    pub fn from_tsconfig(lib_dir: &Path, tsconfig: &Path) -> Result<Self, TsConfigError> {
+        Self::from_tsconfig_options(lib_dir, tsconfig, ServerOptions::default())
+    }
+
+    pub fn from_tsconfig_options(
+        lib_dir: &Path,
+        tsconfig: &Path,
+        options: ServerOptions,
+    ) -> Result<Self, TsConfigError> {
+        let fs = options.fs.clone();
+        let config = TsConfig::load(fs.as_ref(), tsconfig)?;
+        let mut server = Self::with_lib_options(lib_dir, &config.lib_names()?, options)?;
+        server.files = config.type_files(fs.as_ref());
+        server.files.extend(config.file_names(fs.as_ref()));
+        server.resolver = ModuleResolver::new(&config, fs);
+        server.config = config;
+        Ok(server)
+    }
+
+    /// Where the server reads sources from.
+    pub fn file_system(&self) -> &Arc<dyn FileSystem> {
+        &self.fs
+    }
+
+    /// The files of the project loaded by [Server::from_tsconfig].
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    pub fn config(&self) -> &TsConfig {
+        &self.config
+    }
+
+    pub fn resolver(&self) -> &ModuleResolver {
+        &self.resolver
+    }
+
+    pub fn module_graph(&self) -> &ModuleGraph {
+        &self.module_graph
+    }
+
+    /// Analyzes `paths` and the modules and files they import or reference,
+    /// transitively. Files are parsed in parallel, then analyzed level by
+    /// level of the module graph, the files of a level in parallel. Fails
+    /// if a file can't be read.
+    pub fn analyze(&mut self, paths: Vec<PathBuf>) -> Result<(), ReadError> {
+        self.load(paths.clone())?;
+        // Imported modules come first so that their exports can be bound.
+        let order = self.module_graph.dependency_order(&paths);
+        self.analyze_in_order(&order);
+        self.live_types = self.types().len();
+        Ok(())
+    }
+
+    /// Replaces the text of the file at `path`, which is added to the
+    /// program if it wasn't part of it, and analyzes it again along with the
+    /// files that import or reference it. A new file resolves the imports of
+    /// it that were left unresolved. Files declaring globals or ambient
+    /// modules are visible from every file, so changing one re-analyzes the
+    /// whole program. Lib files are never analyzed again. Fails if a file
+    /// the new text imports or references can't be read.
+    ///
+    /// Type IDs from before an update may not be valid after it, since the
+    /// arena is rebuilt once updates have doubled it.
+    pub fn update_file(&mut self, path: &Path, text: &str) -> Result<(), ReadError> {
+        let mut file = parse_source(&self.resolver, path, text);
+        let is_new = !self.module_graph.contains(path);
+        let was_shared = self.declares_shared(path);
+        let mut affected = self.affected_files(path);
+
+        let scope = self.module_graph.scope_path(path);
+        if !scope.as_os_str().is_empty() {
+            self.analyzer.remove_scope(&scope);
+        }
+        let dependencies: Vec<_> = file
+            .references
+            .iter()
+            .cloned()
+            .chain(
+                file.imports
+                    .iter()
+                    .filter_map(|import| import.resolved.clone()),
+            )
+            .collect();
+        self.sources.insert(path.to_path_buf(), &mut file);
+        self.module_graph.insert(
+            path.to_path_buf(),
+            file.is_module,
+            file.imports,
+            file.references,
+        );
+        if is_new {
+            if !self.files.iter().any(|file| file == path) {
+                self.files.push(path.to_path_buf());
             }
+            let resolver = &self.resolver;
+            let importers = self
+                .module_graph
+                .resolve_imports_of(path, |specifier, importer| {
+                    resolver.resolve(specifier, importer)
+                });
+            for importer in importers {
+                affected.extend(self.module_graph.dependents(&importer));
+            }
+        }
+        affected.extend(self.load(dependencies)?);
 
-            self.analyzer.visit(&parsed.tree());
+        if file.is_module {
+            self.reanalyze(affected, was_shared);
+        } else {
+            self.reanalyze(self.module_graph.files().into_iter().collect(), true);
         }
+        // The new text may declare globals or ambient modules for the first time.
+        if !was_shared && file.is_module && self.declares_shared(path) {
+            self.reanalyze(self.module_graph.files().into_iter().collect(), true);
+        }
+        self.rebuild_if_grown();
+        Ok(())
     }
 
-    pub fn test_analyze(&mut self, src: &str) {
-        let src_type = JsFileSource::ts();
-        let parsed = parse(src, src_type, Default::default());
-        if parsed.has_errors() {
-            panic!("Failed to parse source code: {:?}", parsed.diagnostics());
+    /// Removes the file at `path` from the program and analyzes the files
+    /// that depended on it again.
+    pub fn remove_file(&mut self, path: &Path) {
+        if !self.module_graph.contains(path) {
+            return;
         }
+        let shared = self.declares_shared(path);
+        let mut affected = self.affected_files(path);
+        affected.remove(path);
 
-        self.analyzer.visit(&parsed.tree());
+        let scope = self.module_graph.scope_path(path);
+        if !scope.as_os_str().is_empty() {
+            self.analyzer.remove_scope(&scope);
+        }
+        self.module_graph.remove(path);
+        self.sources.remove(path);
+        self.shared_files.remove(path);
+        self.analyses.remove(path);
+        self.files.retain(|file| file != path);
+        self.reanalyze(affected, shared);
+        self.rebuild_if_grown();
+    }
+
+    /// Starts over from a new arena with only the built-in types, and
+    /// analyzes the whole program again, once the arena holds twice the
+    /// types of the last full analysis. The types of removed scopes can't
+    /// be freed one by one, since other types may be made of them.
+    fn rebuild_if_grown(&mut self) {
+        if self.types().len() <= 2 * self.live_types {
+            return;
+        }
+        self.analyzer = TypeAnalyzer::from_builtin_snapshot(&self.analyzer.builtin_snapshot());
+        self.shared_files.clear();
+        self.analyses.clear();
+        let order = self
+            .module_graph
+            .dependency_order(&self.module_graph.files());
+        self.analyze_in_order(&order);
+        self.live_types = self.types().len();
+    }
+
+    /// Parses `paths` and the files they import or reference that aren't
+    /// loaded yet, in parallel. Declaration files found in the cache aren't
+    /// parsed. Returns the files loaded.
+    fn load(&mut self, paths: Vec<PathBuf>) -> Result<Vec<PathBuf>, ReadError> {
+        let mut loaded = vec![];
+        let mut wave = paths;
+        while !wave.is_empty() {
+            let mut seen = FxHashSet::default();
+            wave.retain(|path| !self.module_graph.contains(path) && seen.insert(path.clone()));
+            let resolver = &self.resolver;
+            let fs = self.fs.as_ref();
+            let cache = self.cache.as_ref();
+            let files = wave
+                .par_iter()
+                .map(|path| load_file(fs, resolver, cache, path))
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut next = vec![];
+            for (path, mut file) in wave.into_iter().zip(files) {
+                next.extend(file.references.iter().cloned());
+                next.extend(
+                    file.imports
+                        .iter()
+                        .filter_map(|import| import.resolved.clone()),
+                );
+                self.sources.insert(path.clone(), &mut file);
+                self.module_graph.insert(
+                    path.clone(),
+                    file.is_module,
+                    file.imports,
+                    file.references,
+                );
+                loaded.push(path);
+            }
+            wave = next;
+        }
+        Ok(loaded)
+    }
+
+    fn analyze_in_order(&mut self, order: &[PathBuf]) {
+        for level in self.module_graph.levels(order) {
+            for path in &level {
+                for import in self.module_graph.imports(path) {
+                    if let Some(resolved) = &import.resolved {
+                        self.analyzer.add_resolved_module(
+                            path.clone(),
+                            import.specifier.clone(),
+                            resolved.clone(),
+                        );
+                    }
+                }
+            }
+            if self.cache.is_some() {
+                for path in &level {
+                    let key = self.analysis_key(path);
+                    self.sources.set_analysis_key(path, key);
+                }
+            }
+            self.analyzer.freeze();
+            let analyzer = &self.analyzer;
+            let module_graph = &self.module_graph;
+            let sources = &self.sources;
+            let fs = self.fs.as_ref();
+            let cache = self.cache.as_ref();
+            let forks: Vec<_> = level
+                .par_iter()
+                .filter_map(|path| {
+                    let source = sources.get(path)?;
+                    let scope = module_graph.scope_path(path);
+                    let mut fork = analyzer.fork();
+                    fork.set_current_path(scope.clone());
+                    fork.set_current_file(path.clone());
+                    let cached = cache.zip(source.analysis_key);
+                    let analysis = match cached.and_then(|(cache, key)| cache.load(key)) {
+                        Some(snapshot) => {
+                            fork.restore(&snapshot);
+                            FileAnalysis::default()
+                        }
+                        None => {
+                            let root = match &source.root {
+                                Some(root) => AnyJsRoot::unwrap_cast(root.clone().into_node()?),
+                                None => parse_root(path, &fs.read_to_string(path).ok()?).0,
+                            };
+                            fork.visit(&root);
+                            if let Some((cache, key)) = cached {
+                                cache.store(key, &fork.snapshot());
+                            }
+                            FileAnalysis {
+                                references: fork.references(&root),
+                                bindings: fork.bindings().clone(),
+                                arity_mismatches: fork.arity_mismatches().to_vec(),
+                                unresolved_imports: fork.unresolved_imports().to_vec(),
+                                unsupported_declarations: fork.unsupported_declarations().to_vec(),
+                            }
+                        }
+                    };
+                    Some((path, scope, fork, analysis))
+                })
+                .collect();
+            for (path, scope, fork, analysis) in forks {
+                if self.analyzer.merge(fork, &scope).is_shared() {
+                    self.shared_files.insert(path.clone());
+                }
+                self.analyses.insert(path.clone(), analysis);
+            }
+        }
+        self.analyzer.freeze();
+        self.analyzer.set_current_path(PathBuf::new());
+    }
+
+    /// Drops what `affected` declared and analyzes them again. With `shared`,
+    /// the global scope and ambient modules are rebuilt as well, which needs
+    /// `affected` to be every file.
+    fn reanalyze(&mut self, affected: FxHashSet<PathBuf>, shared: bool) {
+        for path in &affected {
+            let scope = self.module_graph.scope_path(path);
+            if !scope.as_os_str().is_empty() {
+                self.analyzer.remove_scope(&scope);
+            }
+            self.shared_files.remove(path);
+        }
+        if shared {
+            self.analyzer.reset_shared_scopes();
+        }
+        let mut order = self
+            .module_graph
+            .dependency_order(&self.module_graph.files());
+        order.retain(|path| affected.contains(path));
+        self.analyze_in_order(&order);
+    }
+
+    /// The key of the cached analysis of the declaration file at `path`. It
+    /// covers the content of the file and of what it depends on: the files it
+    /// imports or references and, when some import is left to ambient
+    /// modules, the files declaring globals.
+    fn analysis_key(&self, path: &Path) -> Option<CacheKey> {
+        if !is_declaration_file(path) {
+            return None;
+        }
+        let key_of = |path: &Path| {
+            let source = self.sources.get(path)?;
+            Some(source.analysis_key.unwrap_or(source.content_key))
+        };
+        let imports = self.module_graph.imports(path);
+        let mut keys = vec![key_of(path)?];
+        keys.extend(
+            self.module_graph
+                .references(path)
+                .iter()
+                .chain(imports.iter().filter_map(|import| import.resolved.as_ref()))
+                .filter_map(|dependency| key_of(dependency.as_path())),
+        );
+        if imports.iter().any(|import| import.resolved.is_none()) {
+            let mut shared: Vec<_> = self.shared_files.iter().collect();
+            shared.sort();
+            keys.extend(shared.into_iter().filter_map(|file| key_of(file.as_path())));
+        }
+        Some(CacheKey::new("analysis", keys))
+    }
+
+    /// Whether the file at `path` declares names other files see without
+    /// importing it: scripts and files with `declare module` blocks.
+    fn declares_shared(&self, path: &Path) -> bool {
+        self.module_graph.is_script(path) || self.shared_files.contains(path)
+    }
+
+    /// The files to analyze again when the file at `path` changes.
+    fn affected_files(&self, path: &Path) -> FxHashSet<PathBuf> {
+        if self.declares_shared(path) {
+            self.module_graph.files().into_iter().collect()
+        } else {
+            self.module_graph.dependents(path)
+        }
+    }
+
+    /// An analyzer looking up names from the file at `path`. Unlike the
+    /// server, it can be queried while other threads query for other files.
+    pub fn scoped_analyzer(&self, path: &Path) -> TypeAnalyzer {
+        let mut analyzer = self.analyzer.fork();
+        analyzer.set_current_path(self.module_graph.scope_path(path));
+        analyzer.set_current_file(path.to_path_buf());
+        if let Some(analysis) = self.analyses.get(path) {
+            analyzer.set_bindings(analysis.bindings.clone());
+        }
+        analyzer
+    }
+
+    /// The syntax errors of the file at `path`.
+    pub fn parse_errors(&self, path: &Path) -> &[ParseError] {
+        self.sources
+            .get(path)
+            .map_or(&[], |source| &source.parse_errors)
+    }
+
+    /// The calls in the file at `path` with more or fewer arguments than
+    /// their callee accepts.
+    pub fn arity_mismatches(&self, path: &Path) -> &[ArityMismatch] {
+        self.analyses
+            .get(path)
+            .map_or(&[], |analysis| &analysis.arity_mismatches)
+    }
+
+    /// The imports in the file at `path` of modules that aren't found or
+    /// of names they don't export.
+    pub fn unresolved_imports(&self, path: &Path) -> &[UnresolvedImport] {
+        self.analyses
+            .get(path)
+            .map_or(&[], |analysis| &analysis.unresolved_imports)
+    }
+
+    /// The classes, enums and namespaces declared in the file at `path`,
+    /// whose names the analyzer leaves unbound.
+    pub fn unsupported_declarations(&self, path: &Path) -> &[UnsupportedDeclaration] {
+        self.analyses
+            .get(path)
+            .map_or(&[], |analysis| &analysis.unsupported_declarations)
+    }
+
+    /// The symbol `node`, a name in the file at `path`, refers to or
+    /// declares. Names bound inside functions or blocks have none.
+    pub fn definition_of(&self, path: &Path, node: &JsSyntaxNode) -> Option<&Symbol> {
+        let range = node.text_trimmed_range();
+        if let Some(reference) = self
+            .analyses
+            .get(path)
+            .into_iter()
+            .flat_map(|analysis| &analysis.references)
+            .find(|reference| reference.range == range)
+        {
+            return Some(&reference.symbol);
+        }
+        if !matches!(
+            node.kind(),
+            JsSyntaxKind::JS_IDENTIFIER_BINDING | JsSyntaxKind::TS_IDENTIFIER_BINDING
+        ) {
+            return None;
+        }
+        let location = Location {
+            path: path.to_path_buf(),
+            range,
+        };
+        let scope = self.module_graph.scope_path(path);
+        let name = node.text_trimmed().to_string();
+        [Namespace::Value, Namespace::Type]
+            .into_iter()
+            .filter_map(|namespace| self.analyzer.lookup_in_scope(&scope, namespace, &name))
+            .find(|symbol| symbol.declarations.contains(&location))
+    }
+
+    /// Where the files of the program refer to `symbol`, by path and then
+    /// by position. Its declarations aren't included.
+    pub fn references_of(&self, symbol: &Symbol) -> Vec<Location> {
+        let mut locations: Vec<_> = self
+            .analyses
+            .iter()
+            .flat_map(|(path, analysis)| {
+                analysis
+                    .references
+                    .iter()
+                    .filter(|reference| {
+                        reference
+                            .symbol
+                            .declarations
+                            .iter()
+                            .any(|location| symbol.declarations.contains(location))
+                    })
+                    .map(|reference| Location {
+                        path: path.clone(),
+                        range: reference.range,
+                    })
+            })
+            .collect();
+        locations.sort_by(|a, b| (&a.path, a.range.start()).cmp(&(&b.path, b.range.start())));
+        locations
+    }
+
+    /// The `.d.ts` declarations of the file at `path`, like `tsc
+    /// --declaration` emits them. `None` for files outside the program.
+    pub fn emit_declarations(&self, path: &Path) -> Option<String> {
+        let root = match &self.sources.get(path)?.root {
+            Some(root) => AnyJsRoot::unwrap_cast(root.clone().into_node()?),
+            None => parse_root(path, &self.fs.read_to_string(path).ok()?).0,
+        };
+        Some(self.scoped_analyzer(path).emit_declarations(&root))
+    }
+
+    /// Makes lookups resolve from the file at `path`.
+    pub fn set_current_path(&mut self, path: &Path) {
+        self.analyzer
+            .set_current_path(self.module_graph.scope_path(path));
     }
 
     pub fn print_symbol_table(&self) {
@@ -46,23 +679,163 @@ impl Server {
     }
 
     pub fn get_type_info(&self, symbol_name: String) -> Option<&Symbol> {
-        dbg!("current_path:", &self.analyzer.current_path());
-        dbg!("lookup:", &symbol_name);
-        let ty = self.analyzer.get_symbol(&symbol_name);
-        dbg!("resolved:", &ty);
-        ty
-    }
-
-    pub fn get_type_info_from_builtin(&self, node: &JsSyntaxNode) -> Type {
-        if matches!(node.kind(), JsSyntaxKind::JS_REFERENCE_IDENTIFIER) {
-            let symbol_name = node.text_trimmed().to_string();
-            if let Some(ty) = self.analyzer.get_builtin_symbol(&symbol_name) {
-                ty.ty.clone()
-            } else {
-                Type::Unknown
-            }
-        } else {
-            Type::Unknown
+        self.analyzer.get_symbol(&symbol_name)
+    }
+
+    pub fn get_type(&self, id: TypeId) -> Arc<Type> {
+        self.analyzer.get_type(id)
+    }
+
+    pub fn types(&self) -> &TypeArena {
+        self.analyzer.types()
+    }
+
+    /// Infers the type of `expr` in the current file, e.g. `Promise<string>`
+    /// for `Promise.resolve("value")`.
+    pub fn infer_expression_type(&self, expr: &AnyJsExpression) -> TypeId {
+        self.analyzer.analyze_expression(expr)
+    }
+
+    pub fn get_type_info_from_builtin(&self, node: &JsSyntaxNode) -> Option<TypeId> {
+        if !matches!(node.kind(), JsSyntaxKind::JS_REFERENCE_IDENTIFIER) {
+            return None;
         }
+        let symbol_name = node.text_trimmed().to_string();
+        self.analyzer
+            .get_builtin_symbol(&symbol_name)
+            .map(|symbol| symbol.ty)
     }
 }
+
+/// A parsed file with its imports and references resolved.
+struct ParsedFile {
+    /// The syntax tree, in a form that can be sent across threads. `None`
+    /// when the file was found in the cache.
+    root: Option<SendNode>,
+    content_key: CacheKey,
+    is_module: bool,
+    imports: Vec<ModuleImport>,
+    references: Vec<PathBuf>,
+    parse_errors: Vec<ParseError>,
+}
+
+fn load_file(
+    fs: &dyn FileSystem,
+    resolver: &ModuleResolver,
+    cache: Option<&DeclarationCache>,
+    path: &Path,
+) -> Result<ParsedFile, ReadError> {
+    let src = fs.read_to_string(path).map_err(|error| ReadError {
+        path: path.to_path_buf(),
+        error,
+    })?;
+    let Some(cache) = cache.filter(|_| is_declaration_file(path)) else {
+        return Ok(parse_source(resolver, path, &src));
+    };
+    let key = CacheKey::new("parsed", [CacheKey::of_content(&src)]);
+    if let Some(parsed) = cache.load::<ParsedDeclarations>(key) {
+        return Ok(resolve_file(resolver, path, &src, None, parsed));
+    }
+    let file = parse_source(resolver, path, &src);
+    // Files with syntax errors are parsed each time, to report them.
+    if !file.parse_errors.is_empty() {
+        return Ok(file);
+    }
+    let parsed = ParsedDeclarations {
+        is_module: file.is_module,
+        specifiers: file
+            .imports
+            .iter()
+            .map(|import| import.specifier.clone())
+            .collect(),
+    };
+    cache.store(key, &parsed);
+    Ok(file)
+}
+
+fn parse_source(resolver: &ModuleResolver, path: &Path, src: &str) -> ParsedFile {
+    let (root, parse_errors) = parse_root(path, src);
+    let parsed = ParsedDeclarations {
+        is_module: is_module(&root),
+        specifiers: module_specifiers(&root),
+    };
+    ParsedFile {
+        parse_errors,
+        ..resolve_file(resolver, path, src, root.syntax().as_send(), parsed)
+    }
+}
+
+/// The tree the parser recovered, with the syntax errors it recovered from.
+fn parse_root(path: &Path, src: &str) -> (AnyJsRoot, Vec<ParseError>) {
+    let src_type = JsFileSource::try_from(path).unwrap_or(JsFileSource::ts());
+    let parsed = parse(src, src_type, Default::default());
+    let errors = parsed.diagnostics().iter().map(ParseError::new).collect();
+    (parsed.tree(), errors)
+}
+
+/// Resolves the imports and triple-slash references of the file at `path`.
+fn resolve_file(
+    resolver: &ModuleResolver,
+    path: &Path,
+    src: &str,
+    root: Option<SendNode>,
+    parsed: ParsedDeclarations,
+) -> ParsedFile {
+    let references = triple_slash_references(src)
+        .into_iter()
+        .filter_map(|reference| match reference {
+            TripleSlashReference::Path(target) => resolver.resolve_reference_path(&target, path),
+            TripleSlashReference::Types(name) => resolver.resolve_type_reference(&name, path),
+            // Libs are loaded once when the server is created.
+            TripleSlashReference::Lib(_) => None,
+        })
+        .collect();
+    let imports = parsed
+        .specifiers
+        .into_iter()
+        .map(|specifier| ModuleImport {
+            resolved: resolver.resolve(&specifier, path),
+            specifier,
+        })
+        .collect();
+    ParsedFile {
+        root,
+        content_key: CacheKey::of_content(src),
+        is_module: parsed.is_module,
+        imports,
+        references,
+        parse_errors: vec![],
+    }
+}
+
+/// The built-in types of the lib files at `builtin_path`, from `cache` when
+/// the same lib files were analyzed before.
+fn cached_builtins(
+    fs: &dyn FileSystem,
+    cache: &DeclarationCache,
+    builtin_path: Vec<PathBuf>,
+) -> Result<TypeAnalyzer, ReadError> {
+    let contents = builtin_path
+        .iter()
+        .map(|path| {
+            fs.read_to_string(path).map_err(|error| ReadError {
+                path: path.clone(),
+                error,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let key = CacheKey::new("lib", &contents);
+    if let Some(snapshot) = cache.load(key) {
+        return Ok(TypeAnalyzer::from_builtin_snapshot(&snapshot));
+    }
+    let analyzer = TypeAnalyzer::with_file_system(fs, builtin_path)?;
+    cache.store(key, &analyzer.builtin_snapshot());
+    Ok(analyzer)
+}
+
+fn is_declaration_file(path: &Path) -> bool {
+    let name = path.to_string_lossy();
+    [".d.ts", ".d.mts", ".d.cts"]
+        .iter()
+        .any(|ext| name.ends_with(ext))
+}
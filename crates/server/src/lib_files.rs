@@ -0,0 +1,91 @@
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+use file_system::FileSystem;
+
+use crate::directive::{triple_slash_references, TripleSlashReference};
+
+#[derive(Debug)]
+pub enum LibError {
+    /// No file in the lib directory defines the lib.
+    NotFound(String),
+    Io(PathBuf, io::Error),
+}
+
+impl fmt::Display for LibError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound(lib) => write!(f, "Cannot find lib definition for '{}'", lib),
+            Self::Io(path, err) => write!(f, "Cannot read {}: {}", path.display(), err),
+        }
+    }
+}
+
+impl std::error::Error for LibError {}
+
+/// Resolves `lib` names, as in tsconfig's `lib` option (`es5`, `es2015.promise`,
+/// `dom`, ...), to the files in `lib_dir` to load. `/// <reference lib="..." />`
+/// directives are followed, and referenced libs come before the files
+/// referencing them.
+pub fn resolve_lib_files(
+    fs: &dyn FileSystem,
+    lib_dir: &Path,
+    libs: &[String],
+) -> Result<Vec<PathBuf>, LibError> {
+    let mut files = vec![];
+    let mut visiting = vec![];
+    for lib in libs {
+        collect_lib_file(fs, lib_dir, lib, &mut visiting, &mut files)?;
+    }
+    Ok(files)
+}
+
+/// The `lib` directory of the `typescript` package installed in `dir` or
+/// one of its ancestors, which has tsc's full lib set.
+pub fn find_lib_dir(fs: &dyn FileSystem, dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .map(|dir| dir.join("node_modules/typescript/lib"))
+        .find(|lib_dir| fs.is_file(&lib_dir.join("lib.es5.d.ts")))
+}
+
+fn collect_lib_file(
+    fs: &dyn FileSystem,
+    lib_dir: &Path,
+    lib: &str,
+    visiting: &mut Vec<PathBuf>,
+    files: &mut Vec<PathBuf>,
+) -> Result<(), LibError> {
+    let path = lib_file_path(fs, lib_dir, lib)?;
+    if files.contains(&path) || visiting.contains(&path) {
+        return Ok(());
+    }
+    visiting.push(path.clone());
+
+    let src = fs
+        .read_to_string(&path)
+        .map_err(|err| LibError::Io(path.clone(), err))?;
+    for reference in triple_slash_references(&src) {
+        if let TripleSlashReference::Lib(lib) = reference {
+            collect_lib_file(fs, lib_dir, &lib, visiting, files)?;
+        }
+    }
+
+    visiting.pop();
+    files.push(path);
+    Ok(())
+}
+
+/// Accepts both tsc's file names (`lib.es2015.promise.d.ts`) and the short
+/// ones used in this repository (`es2015.promise.d.ts`).
+fn lib_file_path(fs: &dyn FileSystem, lib_dir: &Path, lib: &str) -> Result<PathBuf, LibError> {
+    let name = lib.trim().to_lowercase();
+    let name = name.strip_prefix("lib.").unwrap_or(&name);
+    let name = name.strip_suffix(".d.ts").unwrap_or(name);
+    [format!("lib.{}.d.ts", name), format!("{}.d.ts", name)]
+        .into_iter()
+        .map(|file| lib_dir.join(file))
+        .find(|path| fs.is_file(path))
+        .ok_or_else(|| LibError::NotFound(lib.to_string()))
+}
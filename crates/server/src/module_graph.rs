@@ -0,0 +1,245 @@
+use std::path::{Path, PathBuf};
+
+use biome_js_syntax::{
+    AnyJsExportClause, AnyJsImportClause, AnyJsModuleItem, AnyJsRoot, JsModuleSource,
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleImport {
+    pub specifier: String,
+    /// `None` when the specifier couldn't be resolved.
+    pub resolved: Option<PathBuf>,
+}
+
+/// The files of the program and the modules each of them imports.
+#[derive(Debug, Default)]
+pub struct ModuleGraph {
+    imports: FxHashMap<PathBuf, Vec<ModuleImport>>,
+    /// Files pulled in by `/// <reference path>` and `/// <reference types>`.
+    references: FxHashMap<PathBuf, Vec<PathBuf>>,
+    /// Files without imports or exports, whose declarations are global.
+    scripts: FxHashSet<PathBuf>,
+}
+
+impl ModuleGraph {
+    pub fn contains(&self, path: &Path) -> bool {
+        self.imports.contains_key(path)
+    }
+
+    pub fn insert(
+        &mut self,
+        path: PathBuf,
+        is_module: bool,
+        imports: Vec<ModuleImport>,
+        references: Vec<PathBuf>,
+    ) {
+        if is_module {
+            self.scripts.remove(&path);
+        } else {
+            self.scripts.insert(path.clone());
+        }
+        self.references.insert(path.clone(), references);
+        self.imports.insert(path, imports);
+    }
+
+    /// Removes the file at `path`. Imports of it are left unresolved.
+    pub fn remove(&mut self, path: &Path) {
+        self.imports.remove(path);
+        self.references.remove(path);
+        self.scripts.remove(path);
+        for imports in self.imports.values_mut() {
+            for import in imports {
+                if import.resolved.as_deref() == Some(path) {
+                    import.resolved = None;
+                }
+            }
+        }
+        for references in self.references.values_mut() {
+            references.retain(|reference| reference != path);
+        }
+    }
+
+    /// Resolves again the unresolved imports, with `resolve`, and keeps the
+    /// ones that now resolve to `target`, a file just added. Returns the
+    /// files importing it.
+    pub fn resolve_imports_of(
+        &mut self,
+        target: &Path,
+        resolve: impl Fn(&str, &Path) -> Option<PathBuf>,
+    ) -> Vec<PathBuf> {
+        let mut importers = vec![];
+        for (path, imports) in &mut self.imports {
+            for import in imports
+                .iter_mut()
+                .filter(|import| import.resolved.is_none())
+            {
+                if resolve(&import.specifier, path).as_deref() == Some(target) {
+                    import.resolved = Some(target.to_path_buf());
+                    importers.push(path.clone());
+                }
+            }
+        }
+        importers
+    }
+
+    /// Every file of the graph, sorted.
+    pub fn files(&self) -> Vec<PathBuf> {
+        let mut files: Vec<_> = self.imports.keys().cloned().collect();
+        files.sort();
+        files
+    }
+
+    pub fn is_script(&self, path: &Path) -> bool {
+        self.scripts.contains(path)
+    }
+
+    /// `path` and the files that import or reference it, transitively.
+    pub fn dependents(&self, path: &Path) -> FxHashSet<PathBuf> {
+        let mut importers: FxHashMap<&Path, Vec<&Path>> = FxHashMap::default();
+        for file in self.imports.keys() {
+            for dependency in self.dependencies(file) {
+                importers.entry(dependency).or_default().push(file);
+            }
+        }
+        let mut dependents = FxHashSet::default();
+        let mut stack = vec![path];
+        while let Some(path) = stack.pop() {
+            if dependents.insert(path.to_path_buf()) {
+                stack.extend(importers.get(path).into_iter().flatten().copied());
+            }
+        }
+        dependents
+    }
+
+    pub fn imports(&self, path: &Path) -> &[ModuleImport] {
+        self.imports.get(path).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn references(&self, path: &Path) -> &[PathBuf] {
+        self.references.get(path).map_or(&[], Vec::as_slice)
+    }
+
+    /// The analyzer scope of a file: its own path for modules, the global
+    /// scope (the empty path) for scripts and files outside the graph.
+    pub fn scope_path(&self, path: &Path) -> PathBuf {
+        if self.contains(path) && !self.scripts.contains(path) {
+            path.to_path_buf()
+        } else {
+            PathBuf::new()
+        }
+    }
+
+    /// The files reachable from `roots`, referenced files and imported
+    /// modules first. Scripts are visited before modules since they may
+    /// declare the ambient modules that modules import. Cycles are cut where
+    /// they are found.
+    pub fn dependency_order(&self, roots: &[PathBuf]) -> Vec<PathBuf> {
+        let mut visited = FxHashSet::default();
+        let mut order = vec![];
+        let (scripts, modules): (Vec<_>, Vec<_>) =
+            roots.iter().partition(|root| self.scripts.contains(*root));
+        for root in scripts.into_iter().chain(modules) {
+            self.visit_dependencies(root, &mut visited, &mut order);
+        }
+        order
+    }
+
+    /// Groups a [ModuleGraph::dependency_order] into levels whose files only
+    /// depend on files of earlier levels, so that the files of a level can be
+    /// analyzed in parallel. Modules come after every script, since scripts
+    /// may declare the ambient modules they import.
+    pub fn levels(&self, order: &[PathBuf]) -> Vec<Vec<PathBuf>> {
+        let mut level_of: FxHashMap<&Path, usize> = FxHashMap::default();
+        let mut levels: Vec<Vec<PathBuf>> = vec![];
+        let (scripts, modules): (Vec<_>, Vec<_>) =
+            order.iter().partition(|path| self.scripts.contains(*path));
+        for group in [scripts, modules] {
+            let first = levels.len();
+            for path in group {
+                // Dependencies without a level yet are part of a cycle.
+                let level = self
+                    .dependencies(path)
+                    .filter_map(|dependency| level_of.get(dependency))
+                    .map(|level| level + 1)
+                    .fold(first, usize::max);
+                level_of.insert(path.as_path(), level);
+                if levels.len() <= level {
+                    levels.resize_with(level + 1, Vec::new);
+                }
+                levels[level].push(path.clone());
+            }
+        }
+        levels
+    }
+
+    fn visit_dependencies(
+        &self,
+        path: &Path,
+        visited: &mut FxHashSet<PathBuf>,
+        order: &mut Vec<PathBuf>,
+    ) {
+        if !visited.insert(path.to_path_buf()) {
+            return;
+        }
+        for dependency in self.dependencies(path) {
+            self.visit_dependencies(dependency, visited, order);
+        }
+        order.push(path.to_path_buf());
+    }
+
+    /// The files `path` references, then the modules it imports.
+    fn dependencies<'a>(&'a self, path: &Path) -> impl Iterator<Item = &'a Path> + 'a {
+        let references = self.references(path).iter().map(PathBuf::as_path);
+        let imports = self
+            .imports(path)
+            .iter()
+            .filter_map(|import| import.resolved.as_deref());
+        references.chain(imports)
+    }
+}
+
+/// Whether `root` is an ES module, i.e. has any import or export.
+pub fn is_module(root: &AnyJsRoot) -> bool {
+    module_items(root).any(|item| {
+        matches!(
+            item,
+            AnyJsModuleItem::JsImport(_) | AnyJsModuleItem::JsExport(_)
+        )
+    })
+}
+
+/// The specifiers of the imports and re-exports of `root`.
+pub fn module_specifiers(root: &AnyJsRoot) -> Vec<String> {
+    module_items(root)
+        .filter_map(|item| match item {
+            AnyJsModuleItem::JsImport(node) => import_source(&node.import_clause().ok()?),
+            AnyJsModuleItem::JsExport(node) => match node.export_clause().ok()? {
+                AnyJsExportClause::JsExportFromClause(clause) => clause.source().ok(),
+                AnyJsExportClause::JsExportNamedFromClause(clause) => clause.source().ok(),
+                _ => None,
+            },
+            AnyJsModuleItem::AnyJsStatement(_) => None,
+        })
+        .filter_map(|source| Some(source.inner_string_text().ok()?.text().to_string()))
+        .collect()
+}
+
+fn module_items(root: &AnyJsRoot) -> impl Iterator<Item = AnyJsModuleItem> {
+    let items = match root {
+        AnyJsRoot::JsModule(node) => Some(node.items()),
+        AnyJsRoot::TsDeclarationModule(node) => Some(node.items()),
+        _ => None,
+    };
+    items.into_iter().flatten()
+}
+
+fn import_source(clause: &AnyJsImportClause) -> Option<JsModuleSource> {
+    match clause {
+        AnyJsImportClause::JsImportBareClause(clause) => clause.source().ok(),
+        AnyJsImportClause::JsImportDefaultClause(clause) => clause.source().ok(),
+        AnyJsImportClause::JsImportNamedClause(clause) => clause.source().ok(),
+        AnyJsImportClause::JsImportNamespaceClause(clause) => clause.source().ok(),
+        AnyJsImportClause::JsImportCombinedClause(clause) => clause.source().ok(),
+    }
+}
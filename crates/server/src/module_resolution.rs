@@ -0,0 +1,461 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use file_system::{FileSystem, OsFileSystem};
+use serde_json::Value;
+
+use crate::{
+    glob,
+    tsconfig::TsConfig,
+    version::{matches_range, Version},
+};
+
+/// The TypeScript version whose resolution is emulated, which picks the
+/// `typesVersions` entry of packages.
+pub const TYPESCRIPT_VERSION: Version = Version::new(5, 7, 2);
+
+/// tsc's `moduleResolution` strategies.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ModuleResolutionKind {
+    #[default]
+    Node10,
+    Node16,
+    NodeNext,
+    Bundler,
+}
+
+impl ModuleResolutionKind {
+    /// `moduleResolution`, or the strategy implied by `module`.
+    pub fn from_config(config: &TsConfig) -> Self {
+        let options = &config.compiler_options;
+        let name = options
+            .module_resolution
+            .as_ref()
+            .or(options.module.as_ref())
+            .map(|name| name.to_lowercase());
+        match name.as_deref() {
+            Some("node16") => Self::Node16,
+            Some("nodenext") => Self::NodeNext,
+            Some("bundler") => Self::Bundler,
+            _ => Self::Node10,
+        }
+    }
+
+    /// The `exports` conditions matched in `mode`, in addition to `default`.
+    /// Node10 ignores `exports` entirely.
+    fn conditions(self, mode: ResolutionMode) -> Option<&'static [&'static str]> {
+        match (self, mode) {
+            (Self::Node10, _) => None,
+            (Self::Node16 | Self::NodeNext, ResolutionMode::Import) => {
+                Some(&["types", "node", "import"])
+            }
+            (Self::Node16 | Self::NodeNext, ResolutionMode::Require) => {
+                Some(&["types", "node", "require"])
+            }
+            (Self::Bundler, _) => Some(&["types", "import", "module"]),
+        }
+    }
+}
+
+/// Whether an import is resolved like an ES module `import` or a CommonJS
+/// `require`. Under node16 and nodenext it depends on the importing file,
+/// see [ModuleResolver::mode_of].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolutionMode {
+    Import,
+    Require,
+}
+
+/// Resolves import specifiers to files the way tsc does: `paths` mappings,
+/// relative paths, `baseUrl`, then `node_modules` packages and their
+/// `@types` counterparts.
+#[derive(Debug, Clone)]
+pub struct ModuleResolver {
+    fs: Arc<dyn FileSystem>,
+    kind: ModuleResolutionKind,
+    base_url: Option<PathBuf>,
+    paths: Vec<(String, Vec<String>)>,
+    paths_base: Option<PathBuf>,
+    type_roots: Vec<PathBuf>,
+}
+
+impl Default for ModuleResolver {
+    fn default() -> Self {
+        Self {
+            fs: Arc::new(OsFileSystem),
+            kind: ModuleResolutionKind::default(),
+            base_url: None,
+            paths: vec![],
+            paths_base: None,
+            type_roots: vec![],
+        }
+    }
+}
+
+impl ModuleResolver {
+    /// A resolver without `compilerOptions`, reading files from `fs`.
+    pub fn with_file_system(fs: Arc<dyn FileSystem>) -> Self {
+        Self {
+            fs,
+            ..Self::default()
+        }
+    }
+
+    pub fn new(config: &TsConfig, fs: Arc<dyn FileSystem>) -> Self {
+        let options = &config.compiler_options;
+        Self {
+            type_roots: config.type_roots(fs.as_ref()),
+            fs,
+            kind: ModuleResolutionKind::from_config(config),
+            base_url: options.base_url.clone(),
+            paths: options
+                .paths
+                .iter()
+                .flatten()
+                .map(|(pattern, targets)| (pattern.clone(), targets.clone()))
+                .collect(),
+            paths_base: options.paths_base.clone(),
+        }
+    }
+
+    pub fn kind(&self) -> ModuleResolutionKind {
+        self.kind
+    }
+
+    pub fn resolve(&self, specifier: &str, containing_file: &Path) -> Option<PathBuf> {
+        let mode = self.mode_of(containing_file);
+        self.resolve_in_mode(specifier, containing_file, mode)
+    }
+
+    /// Like [ModuleResolver::resolve], whatever the mode of `containing_file`,
+    /// e.g. for `import x = require("...")` in an ES module.
+    pub fn resolve_in_mode(
+        &self,
+        specifier: &str,
+        containing_file: &Path,
+        mode: ResolutionMode,
+    ) -> Option<PathBuf> {
+        if let Some(path) = self.resolve_paths_mapping(specifier) {
+            return Some(path);
+        }
+        if is_relative(specifier) || Path::new(specifier).is_absolute() {
+            let dir = containing_file.parent()?;
+            let path = glob::normalize(&dir.join(specifier));
+            // ES modules in Node.js name the file they import, extension
+            // included, and can't import directories.
+            if self.is_node_esm(mode) {
+                return resolve_file_with_extension(self.fs(), &path);
+            }
+            return resolve_file_or_directory(self.fs(), &path);
+        }
+        if let Some(path) = self
+            .base_url
+            .as_ref()
+            .and_then(|base_url| resolve_file_or_directory(self.fs(), &base_url.join(specifier)))
+        {
+            return Some(path);
+        }
+        self.resolve_node_modules(specifier, containing_file, mode)
+    }
+
+    /// The mode of the imports in `file`. Under node16 and nodenext, like
+    /// Node.js, `.mts` files are ES modules, `.cts` files CommonJS, and other
+    /// files ES modules when the closest `package.json` has
+    /// `"type": "module"`. Node16 and nodenext only differ in what `module`
+    /// emits.
+    pub fn mode_of(&self, file: &Path) -> ResolutionMode {
+        match self.kind {
+            ModuleResolutionKind::Node10 => ResolutionMode::Require,
+            ModuleResolutionKind::Bundler => ResolutionMode::Import,
+            ModuleResolutionKind::Node16 | ModuleResolutionKind::NodeNext => {
+                let name = file.to_string_lossy();
+                if [".mts", ".mjs"].iter().any(|ext| name.ends_with(ext)) {
+                    return ResolutionMode::Import;
+                }
+                if [".cts", ".cjs"].iter().any(|ext| name.ends_with(ext)) {
+                    return ResolutionMode::Require;
+                }
+                let is_module = file
+                    .ancestors()
+                    .skip(1)
+                    .find_map(|dir| read_package_json(self.fs(), dir))
+                    .is_some_and(|manifest| {
+                        manifest.get("type").and_then(Value::as_str) == Some("module")
+                    });
+                if is_module {
+                    ResolutionMode::Import
+                } else {
+                    ResolutionMode::Require
+                }
+            }
+        }
+    }
+
+    fn is_node_esm(&self, mode: ResolutionMode) -> bool {
+        matches!(
+            self.kind,
+            ModuleResolutionKind::Node16 | ModuleResolutionKind::NodeNext
+        ) && mode == ResolutionMode::Import
+    }
+
+    /// `/// <reference path="..." />` is relative to the referencing file.
+    pub fn resolve_reference_path(&self, path: &str, containing_file: &Path) -> Option<PathBuf> {
+        let dir = containing_file.parent()?;
+        resolve_file(self.fs(), &glob::normalize(&dir.join(path)))
+    }
+
+    /// `/// <reference types="node" />` looks in the type roots, then in
+    /// `node_modules` like an import of `node`.
+    pub fn resolve_type_reference(&self, name: &str, containing_file: &Path) -> Option<PathBuf> {
+        self.type_roots
+            .iter()
+            .find_map(|root| resolve_directory(self.fs(), &root.join(name)))
+            .or_else(|| {
+                let mode = self.mode_of(containing_file);
+                self.resolve_node_modules(name, containing_file, mode)
+            })
+    }
+
+    /// Like tsc, the pattern with the longest prefix before `*` wins.
+    fn resolve_paths_mapping(&self, specifier: &str) -> Option<PathBuf> {
+        let base = self.paths_base.as_ref()?;
+        let (targets, matched) = self
+            .paths
+            .iter()
+            .filter_map(|(pattern, targets)| match pattern.split_once('*') {
+                Some((prefix, suffix)) => {
+                    let matched = specifier.strip_prefix(prefix)?.strip_suffix(suffix)?;
+                    Some((prefix.len(), targets, matched))
+                }
+                None => (pattern == specifier).then_some((usize::MAX, targets, "")),
+            })
+            .max_by_key(|(len, _, _)| *len)
+            .map(|(_, targets, matched)| (targets, matched))?;
+        targets.iter().find_map(|target| {
+            let target = target.replace('*', matched);
+            resolve_file_or_directory(self.fs(), &glob::normalize(&base.join(target)))
+        })
+    }
+
+    fn resolve_node_modules(
+        &self,
+        specifier: &str,
+        containing_file: &Path,
+        mode: ResolutionMode,
+    ) -> Option<PathBuf> {
+        let (package, subpath) = split_package_name(specifier);
+        containing_file
+            .ancestors()
+            .skip(1)
+            .map(|dir| dir.join("node_modules"))
+            .filter(|node_modules| self.fs.is_dir(node_modules))
+            .find_map(|node_modules| {
+                self.resolve_package(&node_modules.join(package), subpath, mode)
+                    .or_else(|| {
+                        let types = node_modules
+                            .join("@types")
+                            .join(types_package_name(package));
+                        self.resolve_package(&types, subpath, mode)
+                    })
+            })
+    }
+
+    fn resolve_package(
+        &self,
+        package_dir: &Path,
+        subpath: &str,
+        mode: ResolutionMode,
+    ) -> Option<PathBuf> {
+        if !self.fs.is_dir(package_dir) {
+            return None;
+        }
+        let manifest = read_package_json(self.fs(), package_dir);
+        if let (Some(manifest), Some(conditions)) = (&manifest, self.kind.conditions(mode)) {
+            if let Some(exports) = manifest.get("exports") {
+                let key = if subpath.is_empty() {
+                    ".".to_string()
+                } else {
+                    format!("./{}", subpath)
+                };
+                // A package with `exports` only exposes what it lists.
+                let target = resolve_exports(exports, &key, conditions)?;
+                return resolve_file(self.fs(), &glob::normalize(&package_dir.join(target)));
+            }
+        }
+        if subpath.is_empty() {
+            return resolve_directory(self.fs(), package_dir);
+        }
+        let subpath = manifest
+            .as_ref()
+            .and_then(|manifest| types_versions_redirect(manifest, subpath))
+            .unwrap_or_else(|| subpath.to_string());
+        resolve_file_or_directory(self.fs(), &package_dir.join(subpath))
+    }
+
+    fn fs(&self) -> &dyn FileSystem {
+        self.fs.as_ref()
+    }
+}
+
+fn resolve_file_or_directory(fs: &dyn FileSystem, path: &Path) -> Option<PathBuf> {
+    resolve_file(fs, path).or_else(|| resolve_directory(fs, path))
+}
+
+/// Tries the TypeScript counterparts of `path`: `./a.js` may refer to
+/// `a.ts` or `a.d.ts`, and `./a` to `a.ts`, `a.tsx` or `a.d.ts`.
+fn resolve_file(fs: &dyn FileSystem, path: &Path) -> Option<PathBuf> {
+    resolve_file_with_extension(fs, path).or_else(|| {
+        let path = path.to_string_lossy();
+        [".ts", ".tsx", ".d.ts"]
+            .map(|ext| PathBuf::from(format!("{}{}", path, ext)))
+            .into_iter()
+            .find(|path| fs.is_file(path))
+    })
+}
+
+/// Like [resolve_file], for paths that name the file with its extension.
+fn resolve_file_with_extension(fs: &dyn FileSystem, path: &Path) -> Option<PathBuf> {
+    let path_str = path.to_string_lossy();
+    let mapped: [(&str, &[&str]); 4] = [
+        (".js", &[".ts", ".tsx", ".d.ts"]),
+        (".jsx", &[".tsx", ".ts", ".d.ts"]),
+        (".mjs", &[".mts", ".d.mts"]),
+        (".cjs", &[".cts", ".d.cts"]),
+    ];
+    let mut candidates = vec![];
+    for (js, ts_extensions) in mapped {
+        if let Some(stem) = path_str.strip_suffix(js) {
+            candidates.extend(
+                ts_extensions
+                    .iter()
+                    .map(|ext| PathBuf::from(format!("{}{}", stem, ext))),
+            );
+        }
+    }
+    if [".ts", ".tsx", ".mts", ".cts"]
+        .iter()
+        .any(|ext| path_str.ends_with(ext))
+    {
+        candidates.push(path.to_path_buf());
+    }
+    candidates.into_iter().find(|path| fs.is_file(path))
+}
+
+/// The entry of a directory: the `types`/`typings`/`main` of its
+/// `package.json` (through `typesVersions`), or its `index` file.
+pub(crate) fn resolve_directory(fs: &dyn FileSystem, dir: &Path) -> Option<PathBuf> {
+    if !fs.is_dir(dir) {
+        return None;
+    }
+    if let Some(manifest) = read_package_json(fs, dir) {
+        let entry = ["types", "typings", "main"]
+            .iter()
+            .find_map(|field| manifest.get(*field)?.as_str());
+        if let Some(entry) = entry {
+            let entry = types_versions_redirect(&manifest, entry.trim_start_matches("./"))
+                .unwrap_or_else(|| entry.to_string());
+            let entry = glob::normalize(&dir.join(entry));
+            if entry != dir {
+                if let Some(path) = resolve_file_or_directory(fs, &entry) {
+                    return Some(path);
+                }
+            }
+        }
+    }
+    resolve_file(fs, &dir.join("index"))
+}
+
+fn read_package_json(fs: &dyn FileSystem, dir: &Path) -> Option<Value> {
+    let src = fs.read_to_string(&dir.join("package.json")).ok()?;
+    serde_json::from_str(&src).ok()
+}
+
+/// Resolves `subpath` (`"."` or `"./name"`) through a package's `exports`.
+fn resolve_exports(exports: &Value, subpath: &str, conditions: &[&str]) -> Option<String> {
+    let is_subpath_map = exports
+        .as_object()
+        .is_some_and(|map| map.keys().any(|key| key.starts_with('.')));
+    if !is_subpath_map {
+        // `"exports": "./index.js"` or a conditions object for `"."`.
+        if subpath != "." {
+            return None;
+        }
+        return resolve_export_target(exports, "", conditions);
+    }
+    let map = exports.as_object()?;
+    if let Some(target) = map.get(subpath) {
+        return resolve_export_target(target, "", conditions);
+    }
+    let (key, matched) = map
+        .keys()
+        .filter_map(|key| {
+            let (prefix, suffix) = key.split_once('*')?;
+            let matched = subpath.strip_prefix(prefix)?.strip_suffix(suffix)?;
+            Some((key, matched))
+        })
+        .max_by_key(|(key, _)| key.len())?;
+    resolve_export_target(&map[key], matched, conditions)
+}
+
+fn resolve_export_target(target: &Value, matched: &str, conditions: &[&str]) -> Option<String> {
+    match target {
+        Value::String(target) => Some(target.replace('*', matched)),
+        Value::Array(targets) => targets
+            .iter()
+            .find_map(|target| resolve_export_target(target, matched, conditions)),
+        // Conditions are tried in the order the package lists them.
+        Value::Object(map) => map
+            .iter()
+            .filter(|(condition, _)| {
+                *condition == "default" || conditions.contains(&condition.as_str())
+            })
+            .find_map(|(_, target)| resolve_export_target(target, matched, conditions)),
+        _ => None,
+    }
+}
+
+/// Applies the first `typesVersions` entry whose range includes
+/// [TYPESCRIPT_VERSION], e.g. `{ ">=4.2": { "*": ["ts4.2/*"] } }` maps
+/// `index.d.ts` to `ts4.2/index.d.ts`.
+fn types_versions_redirect(manifest: &Value, path: &str) -> Option<String> {
+    let (_, mappings) = manifest
+        .get("typesVersions")?
+        .as_object()?
+        .iter()
+        .find(|(range, _)| matches_range(range, TYPESCRIPT_VERSION))?;
+    mappings.as_object()?.iter().find_map(|(pattern, targets)| {
+        let matched = match pattern.split_once('*') {
+            Some((prefix, suffix)) => path.strip_prefix(prefix)?.strip_suffix(suffix)?,
+            None if pattern == path => "",
+            None => return None,
+        };
+        let target = targets.as_array()?.first()?.as_str()?;
+        Some(target.replace('*', matched))
+    })
+}
+
+fn is_relative(specifier: &str) -> bool {
+    specifier == "."
+        || specifier == ".."
+        || specifier.starts_with("./")
+        || specifier.starts_with("../")
+}
+
+/// Splits `@scope/name/sub/path` into `@scope/name` and `sub/path`.
+fn split_package_name(specifier: &str) -> (&str, &str) {
+    let name_segments = if specifier.starts_with('@') { 2 } else { 1 };
+    match specifier.match_indices('/').nth(name_segments - 1) {
+        Some((index, _)) => (&specifier[..index], &specifier[index + 1..]),
+        None => (specifier, ""),
+    }
+}
+
+/// `@scope/name` is published to `@types/scope__name`.
+fn types_package_name(package: &str) -> String {
+    match package.strip_prefix('@') {
+        Some(scoped) => scoped.replacen('/', "__", 1),
+        None => package.to_string(),
+    }
+}
@@ -0,0 +1,423 @@
+use std::{
+    collections::BTreeMap,
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+use file_system::FileSystem;
+use serde::Deserialize;
+
+use crate::{glob, lib_files::LibError, module_resolution::resolve_directory};
+
+/// The `target`s after ES2015, whose default libs are named after them.
+const ES_TARGETS: [&str; 10] = [
+    "es2016", "es2017", "es2018", "es2019", "es2020", "es2021", "es2022", "es2023", "es2024",
+    "esnext",
+];
+
+/// Directories skipped when `exclude` isn't set.
+const DEFAULT_EXCLUDE: [&str; 3] = ["node_modules", "bower_components", "jspm_packages"];
+
+/// The subset of `compilerOptions` the analyzer cares about. Paths are made
+/// absolute relative to the config file that sets them.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompilerOptions {
+    pub target: Option<String>,
+    pub module: Option<String>,
+    pub module_resolution: Option<String>,
+    pub lib: Option<Vec<String>>,
+    pub strict: Option<bool>,
+    pub strict_null_checks: Option<bool>,
+    pub base_url: Option<PathBuf>,
+    pub paths: Option<BTreeMap<String, Vec<String>>>,
+    /// The directory `paths` are relative to when there's no `baseUrl`.
+    #[serde(skip)]
+    pub paths_base: Option<PathBuf>,
+    pub types: Option<Vec<String>>,
+    pub type_roots: Option<Vec<PathBuf>>,
+    pub allow_js: Option<bool>,
+}
+
+impl CompilerOptions {
+    fn resolve_paths(&mut self, dir: &Path) {
+        self.base_url = self
+            .base_url
+            .as_ref()
+            .map(|url| glob::normalize(&dir.join(url)));
+        if self.paths.is_some() {
+            self.paths_base = Some(self.base_url.clone().unwrap_or_else(|| dir.to_path_buf()));
+        }
+        if let Some(type_roots) = &mut self.type_roots {
+            for root in type_roots {
+                *root = glob::normalize(&dir.join(&*root));
+            }
+        }
+    }
+
+    /// Options set here take precedence over the ones in `base`.
+    fn inherit(self, base: CompilerOptions) -> Self {
+        let (paths, paths_base) = match self.paths {
+            Some(paths) => (Some(paths), self.paths_base),
+            None => (base.paths, base.paths_base),
+        };
+        Self {
+            target: self.target.or(base.target),
+            module: self.module.or(base.module),
+            module_resolution: self.module_resolution.or(base.module_resolution),
+            lib: self.lib.or(base.lib),
+            strict: self.strict.or(base.strict),
+            strict_null_checks: self.strict_null_checks.or(base.strict_null_checks),
+            base_url: self.base_url.or(base.base_url),
+            paths,
+            paths_base,
+            types: self.types.or(base.types),
+            type_roots: self.type_roots.or(base.type_roots),
+            allow_js: self.allow_js.or(base.allow_js),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Extends {
+    One(String),
+    Many(Vec<String>),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawTsConfig {
+    extends: Option<Extends>,
+    #[serde(default)]
+    compiler_options: CompilerOptions,
+    files: Option<Vec<String>>,
+    include: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+}
+
+/// A `tsconfig.json` with its `extends` chain applied. `files`, `include`
+/// and `exclude` hold absolute paths and patterns.
+#[derive(Debug, Default, Clone)]
+pub struct TsConfig {
+    pub path: PathBuf,
+    pub compiler_options: CompilerOptions,
+    pub files: Option<Vec<PathBuf>>,
+    pub include: Option<Vec<PathBuf>>,
+    pub exclude: Option<Vec<PathBuf>>,
+}
+
+#[derive(Debug)]
+pub enum TsConfigError {
+    Io(PathBuf, io::Error),
+    Parse(PathBuf, serde_json::Error),
+    ExtendsNotFound(PathBuf, String),
+    CircularExtends(PathBuf),
+    UnknownTarget(PathBuf, String),
+    Lib(LibError),
+}
+
+impl fmt::Display for TsConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(path, err) => write!(f, "Cannot read {}: {}", path.display(), err),
+            Self::Parse(path, err) => write!(f, "Cannot parse {}: {}", path.display(), err),
+            Self::ExtendsNotFound(path, spec) => write!(
+                f,
+                "File '{}' extended by {} not found",
+                spec,
+                path.display()
+            ),
+            Self::CircularExtends(path) => {
+                write!(f, "Circularity detected while extending {}", path.display())
+            }
+            Self::UnknownTarget(path, target) => {
+                write!(f, "Unknown target '{}' in {}", target, path.display())
+            }
+            Self::Lib(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for TsConfigError {}
+
+impl From<LibError> for TsConfigError {
+    fn from(err: LibError) -> Self {
+        Self::Lib(err)
+    }
+}
+
+impl TsConfig {
+    pub fn load(fs: &dyn FileSystem, path: &Path) -> Result<Self, TsConfigError> {
+        Self::load_extending(fs, path, &mut vec![])
+    }
+
+    fn load_extending(
+        fs: &dyn FileSystem,
+        path: &Path,
+        visiting: &mut Vec<PathBuf>,
+    ) -> Result<Self, TsConfigError> {
+        let path = std::path::absolute(path)
+            .map(|path| glob::normalize(&path))
+            .map_err(|err| TsConfigError::Io(path.to_path_buf(), err))?;
+        if visiting.contains(&path) {
+            return Err(TsConfigError::CircularExtends(path));
+        }
+        visiting.push(path.clone());
+
+        let src = fs
+            .read_to_string(&path)
+            .map_err(|err| TsConfigError::Io(path.clone(), err))?;
+        let raw: RawTsConfig = serde_json::from_str(&strip_jsonc(&src))
+            .map_err(|err| TsConfigError::Parse(path.clone(), err))?;
+
+        let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        let resolve = |entries: Vec<String>| -> Vec<PathBuf> {
+            entries
+                .iter()
+                .map(|entry| glob::normalize(&dir.join(entry)))
+                .collect()
+        };
+        let mut compiler_options = raw.compiler_options;
+        compiler_options.resolve_paths(&dir);
+        let mut config = Self {
+            path: path.clone(),
+            compiler_options,
+            files: raw.files.map(resolve),
+            include: raw.include.map(resolve),
+            exclude: raw.exclude.map(resolve),
+        };
+
+        let extends = match raw.extends {
+            Some(Extends::One(spec)) => vec![spec],
+            Some(Extends::Many(specs)) => specs,
+            None => vec![],
+        };
+        // With several bases, later ones override earlier ones.
+        for spec in extends.iter().rev() {
+            let base_path = resolve_extends(fs, &dir, spec)
+                .ok_or_else(|| TsConfigError::ExtendsNotFound(path.clone(), spec.clone()))?;
+            let base = Self::load_extending(fs, &base_path, visiting)?;
+            config = config.inherit(base);
+        }
+
+        visiting.pop();
+        Ok(config)
+    }
+
+    fn inherit(self, base: TsConfig) -> Self {
+        Self {
+            path: self.path,
+            compiler_options: self.compiler_options.inherit(base.compiler_options),
+            files: self.files.or(base.files),
+            include: self.include.or(base.include),
+            exclude: self.exclude.or(base.exclude),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        self.path.parent().unwrap_or(Path::new(""))
+    }
+
+    /// `compilerOptions.lib`, or else the libs tsc loads by default for
+    /// `target`, the DOM included.
+    pub fn lib_names(&self) -> Result<Vec<String>, TsConfigError> {
+        if let Some(lib) = &self.compiler_options.lib {
+            return Ok(lib.clone());
+        }
+        let target = self
+            .compiler_options
+            .target
+            .as_deref()
+            .unwrap_or("es5")
+            .to_lowercase();
+        let es = match target.as_str() {
+            "es3" | "es5" => "es5",
+            "es6" | "es2015" => "es2015",
+            target if ES_TARGETS.contains(&target) => target,
+            _ => return Err(TsConfigError::UnknownTarget(self.path.clone(), target)),
+        };
+        // The references of tsc's `lib.d.ts`, `lib.es6.d.ts` and `lib.<target>.full.d.ts`.
+        let mut libs = vec![es, "dom", "webworker.importscripts", "scripthost"];
+        if es != "es5" {
+            libs.push("dom.iterable");
+        }
+        if !matches!(es, "es5" | "es2015" | "es2016" | "es2017") {
+            libs.push("dom.asynciterable");
+        }
+        Ok(libs.into_iter().map(String::from).collect())
+    }
+
+    pub fn strict_null_checks(&self) -> bool {
+        let options = &self.compiler_options;
+        options
+            .strict_null_checks
+            .or(options.strict)
+            .unwrap_or(false)
+    }
+
+    pub fn allow_js(&self) -> bool {
+        self.compiler_options.allow_js.unwrap_or(false)
+    }
+
+    /// The source files of the project: `files`, then the files matching
+    /// `include` but not `exclude`. Like tsc, `include` defaults to `**/*`
+    /// unless `files` is set.
+    pub fn file_names(&self, fs: &dyn FileSystem) -> Vec<PathBuf> {
+        let mut files = self.files.clone().unwrap_or_default();
+        let include = match (&self.include, &self.files) {
+            (Some(include), _) => include.clone(),
+            (None, Some(_)) => vec![],
+            (None, None) => vec![self.dir().join("**/*")],
+        };
+        let exclude = self.exclude.clone().unwrap_or_else(|| {
+            DEFAULT_EXCLUDE
+                .iter()
+                .map(|dir| self.dir().join(dir))
+                .collect()
+        });
+
+        let mut included = vec![];
+        for pattern in include {
+            let pattern = glob::include_pattern(pattern);
+            glob::walk(fs, &glob::base_dir(&pattern), &exclude, &mut |path| {
+                if glob::matches(&pattern, path) && self.is_supported_file(path) {
+                    included.push(path.to_path_buf());
+                }
+            });
+        }
+        included.sort();
+        for file in included {
+            if !files.contains(&file) {
+                files.push(file);
+            }
+        }
+        files
+    }
+
+    /// `compilerOptions.typeRoots`, or every `node_modules/@types` from the
+    /// config's directory up.
+    pub fn type_roots(&self, fs: &dyn FileSystem) -> Vec<PathBuf> {
+        match &self.compiler_options.type_roots {
+            Some(type_roots) => type_roots.clone(),
+            None => self
+                .dir()
+                .ancestors()
+                .map(|dir| dir.join("node_modules/@types"))
+                .filter(|dir| fs.is_dir(dir))
+                .collect(),
+        }
+    }
+
+    /// The declaration files of the packages in `compilerOptions.types`, or
+    /// of every package under the type roots when it isn't set.
+    pub fn type_files(&self, fs: &dyn FileSystem) -> Vec<PathBuf> {
+        let type_roots = self.type_roots(fs);
+        let names = match &self.compiler_options.types {
+            Some(types) => types.clone(),
+            None => {
+                let mut names: Vec<_> = type_roots
+                    .iter()
+                    .flat_map(|root| fs.read_dir(root).into_iter().flatten())
+                    .filter(|entry| fs.is_dir(entry))
+                    .filter_map(|entry| Some(entry.file_name()?.to_string_lossy().into_owned()))
+                    .collect();
+                names.sort();
+                names
+            }
+        };
+        let mut files = vec![];
+        for name in names {
+            let file = type_roots
+                .iter()
+                .find_map(|root| resolve_directory(fs, &root.join(&name)));
+            if let Some(file) = file {
+                if !files.contains(&file) {
+                    files.push(file);
+                }
+            }
+        }
+        files
+    }
+
+    fn is_supported_file(&self, path: &Path) -> bool {
+        let name = path.to_string_lossy();
+        let mut extensions = vec![".ts", ".tsx", ".mts", ".cts"];
+        if self.allow_js() {
+            extensions.extend([".js", ".jsx", ".mjs", ".cjs"]);
+        }
+        extensions.iter().any(|ext| name.ends_with(ext))
+    }
+}
+
+/// Finds the config a relative or package `extends` points to.
+fn resolve_extends(fs: &dyn FileSystem, dir: &Path, spec: &str) -> Option<PathBuf> {
+    let candidates = |path: PathBuf| {
+        let with_json = PathBuf::from(format!("{}.json", path.display()));
+        [path.clone(), with_json, path.join("tsconfig.json")]
+    };
+    if spec.starts_with('.') || Path::new(spec).is_absolute() {
+        return candidates(dir.join(spec))
+            .into_iter()
+            .find(|path| fs.is_file(path));
+    }
+    dir.ancestors()
+        .flat_map(|dir| candidates(dir.join("node_modules").join(spec)))
+        .find(|path| fs.is_file(path))
+}
+
+/// tsconfig files are JSONC: comments and trailing commas are allowed.
+pub fn strip_jsonc(src: &str) -> String {
+    let mut stripped = String::with_capacity(src.len());
+    let mut chars = src.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                stripped.push(c);
+                while let Some(c) = chars.next() {
+                    stripped.push(c);
+                    match c {
+                        '\\' => stripped.extend(chars.next()),
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => while chars.next_if(|&c| c != '\n').is_some() {},
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = '\0';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            _ => stripped.push(c),
+        }
+    }
+    remove_trailing_commas(&stripped)
+}
+
+fn remove_trailing_commas(src: &str) -> String {
+    let mut result = String::with_capacity(src.len());
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in src.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+        } else if c == '"' {
+            in_string = true;
+        } else if c == ',' && src[i + 1..].trim_start().starts_with(['}', ']']) {
+            continue;
+        }
+        result.push(c);
+    }
+    result
+}
@@ -0,0 +1,139 @@
+use std::cmp::Ordering;
+
+/// A `major.minor.patch` version, such as the TypeScript version
+/// `typesVersions` ranges are matched against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl Version {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+/// Whether `version` is in `range`, a semver range as tsc reads it in
+/// `typesVersions`: `*`, `>=4.2`, `<5`, `~4.1`, `^4.1`, `4.x`, `4.0 - 4.5`,
+/// space-separated comparators that all have to match, and `||` between
+/// alternatives. Prerelease tags aren't supported.
+pub fn matches_range(range: &str, version: Version) -> bool {
+    range.split("||").any(|alternative| {
+        comparators(alternative).is_some_and(|comparators| {
+            comparators
+                .iter()
+                .all(|(op, bound)| op.matches(version.cmp(bound)))
+        })
+    })
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl Op {
+    fn matches(self, ordering: Ordering) -> bool {
+        match self {
+            Self::Less => ordering.is_lt(),
+            Self::LessOrEqual => ordering.is_le(),
+            Self::Greater => ordering.is_gt(),
+            Self::GreaterOrEqual => ordering.is_ge(),
+        }
+    }
+}
+
+/// The comparators a space-separated list stands for, or `None` if a part
+/// isn't a valid comparator.
+fn comparators(range: &str) -> Option<Vec<(Op, Version)>> {
+    let parts = range.split_whitespace().collect::<Vec<_>>();
+    let mut comparators = vec![];
+    let mut index = 0;
+    while index < parts.len() {
+        // `a - b`, from the lowest `a` to the highest `b`.
+        if parts.get(index + 1) == Some(&"-") {
+            let (from, _) = partial(parts[index])?;
+            let (_, to) = partial(parts.get(index + 2)?)?;
+            comparators.push((Op::GreaterOrEqual, from));
+            comparators.extend(to.map(|to| (Op::Less, to)));
+            index += 3;
+            continue;
+        }
+        comparators.extend(comparator(parts[index])?);
+        index += 1;
+    }
+    Some(comparators)
+}
+
+fn comparator(part: &str) -> Option<Vec<(Op, Version)>> {
+    let (op, version) = [">=", "<=", ">", "<", "=", "~", "^"]
+        .into_iter()
+        .find_map(|op| Some((op, part.strip_prefix(op)?)))
+        .unwrap_or(("", part));
+    let (lowest, above) = partial(version)?;
+    let comparators = match op {
+        ">=" => vec![(Op::GreaterOrEqual, lowest)],
+        ">" => match above {
+            Some(above) => vec![(Op::GreaterOrEqual, above)],
+            None => vec![(Op::Greater, lowest)],
+        },
+        "<" => vec![(Op::Less, lowest)],
+        "<=" => match above {
+            Some(above) => vec![(Op::Less, above)],
+            None => vec![(Op::LessOrEqual, lowest)],
+        },
+        // `~4.1` allows patches, `~4` minors.
+        "~" if version.contains('.') => vec![
+            (Op::GreaterOrEqual, lowest),
+            (Op::Less, Version::new(lowest.major, lowest.minor + 1, 0)),
+        ],
+        "~" | "^" => vec![
+            (Op::GreaterOrEqual, lowest),
+            (Op::Less, Version::new(lowest.major + 1, 0, 0)),
+        ],
+        // `4.2` and `4.x` are the versions they are a prefix of.
+        _ => [(Op::GreaterOrEqual, lowest)]
+            .into_iter()
+            .chain(above.map(|above| (Op::Less, above)))
+            .collect(),
+    };
+    Some(comparators)
+}
+
+/// A version where parts may be missing or `x`/`*`: the lowest version it
+/// covers, and the first version above it, `None` for `*`.
+fn partial(version: &str) -> Option<(Version, Option<Version>)> {
+    let version = version.strip_prefix('v').unwrap_or(version);
+    let mut parts = vec![];
+    for part in version.split('.') {
+        if matches!(part, "x" | "X" | "*") {
+            break;
+        }
+        parts.push(part.parse::<u32>().ok()?);
+    }
+    let range = match parts[..] {
+        [] => (Version::new(0, 0, 0), None),
+        [major] => (
+            Version::new(major, 0, 0),
+            Some(Version::new(major + 1, 0, 0)),
+        ),
+        [major, minor] => (
+            Version::new(major, minor, 0),
+            Some(Version::new(major, minor + 1, 0)),
+        ),
+        [major, minor, patch, ..] => (
+            Version::new(major, minor, patch),
+            Some(Version::new(major, minor, patch + 1)),
+        ),
+    };
+    Some(range)
+}
@@ -0,0 +1,636 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+        sync::Arc,
+    };
+
+    use biome_js_parser::parse;
+    use biome_js_syntax::JsFileSource;
+    use file_system::{OsFileSystem, OverlayFileSystem};
+    use server::{
+        module_resolution::{ModuleResolutionKind, ModuleResolver},
+        tsconfig::TsConfig,
+        DeclarationCache, Server, ServerOptions,
+    };
+
+    /// The lib files and fixtures are at the root of the repository.
+    fn repo_path(path: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .ancestors()
+            .nth(2)
+            .unwrap()
+            .join(path)
+    }
+
+    #[test]
+    fn test_resolve_lib_files() {
+        let files = server::resolve_lib_files(
+            &OsFileSystem,
+            &repo_path("src/lib"),
+            &["es2018".to_string()],
+        )
+        .unwrap();
+        assert_eq!(
+            files,
+            vec![
+                repo_path("src/lib/es5.d.ts"),
+                repo_path("src/lib/es2015.promise.d.ts"),
+                repo_path("src/lib/es2015.d.ts"),
+                repo_path("src/lib/es2018.promise.d.ts"),
+                repo_path("src/lib/es2018.d.ts"),
+            ]
+        );
+
+        let err = server::resolve_lib_files(
+            &OsFileSystem,
+            &repo_path("src/lib"),
+            &["es2099".to_string()],
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Cannot find lib definition for 'es2099'");
+    }
+
+    #[test]
+    fn test_find_lib_dir() {
+        use file_system::MemoryFileSystem;
+
+        let fs: MemoryFileSystem = [(
+            "/project/node_modules/typescript/lib/lib.es5.d.ts",
+            "interface Array<T> {}",
+        )]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            server::find_lib_dir(&fs, Path::new("/project/packages/app")),
+            Some(PathBuf::from("/project/node_modules/typescript/lib"))
+        );
+        assert_eq!(server::find_lib_dir(&fs, Path::new("/other")), None);
+    }
+
+    #[test]
+    fn test_tsconfig_project() {
+        let tsconfig = &repo_path("src/fixtures/project/tsconfig.json");
+        let config = TsConfig::load(&OsFileSystem, tsconfig).unwrap();
+        let dir = config.dir().to_path_buf();
+        let relative = |files: Vec<PathBuf>| {
+            files
+                .iter()
+                .map(|file| file.strip_prefix(&dir).unwrap().to_path_buf())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            config.lib_names().unwrap(),
+            [
+                "es2018",
+                "dom",
+                "webworker.importscripts",
+                "scripthost",
+                "dom.iterable",
+                "dom.asynciterable"
+            ]
+        );
+        assert!(!config.strict_null_checks());
+        assert_eq!(config.compiler_options.base_url, Some(dir.join("src")));
+        assert_eq!(config.compiler_options.paths_base, Some(dir.join("src")));
+        assert_eq!(
+            relative(config.file_names(&OsFileSystem)),
+            vec![
+                PathBuf::from("src/app/util.ts"),
+                PathBuf::from("src/client.ts"),
+                PathBuf::from("src/index.ts")
+            ]
+        );
+        assert_eq!(
+            relative(config.type_files(&OsFileSystem)),
+            vec![
+                PathBuf::from("node_modules/@types/legacy-lib/index.d.ts"),
+                PathBuf::from("node_modules/@types/shim/shim.d.ts")
+            ]
+        );
+
+        let server = Server::from_tsconfig(&repo_path("src/lib"), tsconfig).unwrap();
+        assert_eq!(server.files().len(), 5);
+    }
+
+    #[test]
+    fn test_default_libs() {
+        let fs = Arc::new(OverlayFileSystem::new(Arc::new(OsFileSystem)));
+        let tsconfig = Path::new("/memory/project/tsconfig.json");
+        let load = |json: &str| {
+            fs.open(tsconfig, json);
+            TsConfig::load(fs.as_ref(), tsconfig).unwrap()
+        };
+
+        assert_eq!(
+            load("{}").lib_names().unwrap(),
+            ["es5", "dom", "webworker.importscripts", "scripthost"]
+        );
+        assert_eq!(
+            load(r#"{ "compilerOptions": { "target": "ES6" } }"#)
+                .lib_names()
+                .unwrap(),
+            [
+                "es2015",
+                "dom",
+                "webworker.importscripts",
+                "scripthost",
+                "dom.iterable"
+            ]
+        );
+        assert_eq!(
+            load(r#"{ "compilerOptions": { "target": "es1999" } }"#)
+                .lib_names()
+                .unwrap_err()
+                .to_string(),
+            "Unknown target 'es1999' in /memory/project/tsconfig.json"
+        );
+
+        let options = ServerOptions {
+            fs: fs.clone(),
+            cache: None,
+        };
+        fs.open(tsconfig, r#"{ "compilerOptions": { "target": "es2018" } }"#);
+        assert!(
+            Server::from_tsconfig_options(&repo_path("src/lib"), tsconfig, options.clone()).is_ok()
+        );
+        fs.open(
+            tsconfig,
+            r#"{ "compilerOptions": { "lib": ["es2018", "webgpu"] } }"#,
+        );
+        assert_eq!(
+            Server::from_tsconfig_options(&repo_path("src/lib"), tsconfig, options)
+                .unwrap_err()
+                .to_string(),
+            "Cannot find lib definition for 'webgpu'"
+        );
+    }
+
+    #[test]
+    fn test_module_resolution() {
+        let mut config = TsConfig::load(
+            &OsFileSystem,
+            &repo_path("src/fixtures/project/tsconfig.json"),
+        )
+        .unwrap();
+        let dir = config.dir().to_path_buf();
+        let importer = dir.join("src/client.ts");
+        let resolve = |resolver: &ModuleResolver, specifier: &str| {
+            resolver
+                .resolve(specifier, &importer)
+                .map(|path| path.strip_prefix(&dir).unwrap().to_path_buf())
+        };
+
+        let bundler = ModuleResolver::new(&config, Arc::new(OsFileSystem));
+        assert_eq!(bundler.kind(), ModuleResolutionKind::Bundler);
+        let cases = [
+            ("./index.js", Some("src/index.ts")),
+            ("./app/util", Some("src/app/util.ts")),
+            ("@app/util", Some("src/app/util.ts")),
+            ("app/util", Some("src/app/util.ts")),
+            ("axios", Some("node_modules/axios/index.d.ts")),
+            ("esm-lib", Some("node_modules/esm-lib/dist/index.d.ts")),
+            (
+                "esm-lib/utils/time",
+                Some("node_modules/esm-lib/dist/utils/time.d.ts"),
+            ),
+            ("esm-lib/dist/index", None),
+            (
+                "legacy-lib",
+                Some("node_modules/@types/legacy-lib/index.d.ts"),
+            ),
+            (
+                "@scope/versioned",
+                Some("node_modules/@scope/versioned/ts4/index.d.ts"),
+            ),
+            ("missing", None),
+        ];
+        for (specifier, expected) in cases {
+            assert_eq!(
+                resolve(&bundler, specifier),
+                expected.map(PathBuf::from),
+                "{}",
+                specifier
+            );
+        }
+
+        // Node10 ignores `exports`.
+        config.compiler_options.module_resolution = Some("node10".to_string());
+        let node10 = ModuleResolver::new(&config, Arc::new(OsFileSystem));
+        assert_eq!(
+            resolve(&node10, "esm-lib/dist/index"),
+            Some(PathBuf::from("node_modules/esm-lib/dist/index.d.ts"))
+        );
+        assert_eq!(resolve(&node10, "esm-lib"), None);
+    }
+
+    #[test]
+    fn test_node16_resolution_modes() {
+        use file_system::MemoryFileSystem;
+        use server::module_resolution::ResolutionMode;
+
+        let fs: MemoryFileSystem = [
+            ("/p/src/util.ts", "export {};"),
+            ("/p/src/esm/package.json", r#"{ "type": "module" }"#),
+            ("/p/src/esm/index.ts", "export {};"),
+            (
+                "/p/node_modules/dual/package.json",
+                r#"{
+                    "exports": {
+                        ".": {
+                            "import": { "types": "./esm.d.mts" },
+                            "require": { "types": "./cjs.d.cts" }
+                        }
+                    }
+                }"#,
+            ),
+            ("/p/node_modules/dual/esm.d.mts", "export {};"),
+            ("/p/node_modules/dual/cjs.d.cts", "export {};"),
+        ]
+        .into_iter()
+        .collect();
+        let mut config = TsConfig::default();
+        config.compiler_options.module_resolution = Some("nodenext".to_string());
+        let resolver = ModuleResolver::new(&config, Arc::new(fs));
+        let resolve = |specifier: &str, importer: &str| {
+            resolver
+                .resolve(specifier, Path::new(importer))
+                .map(|path| path.to_string_lossy().into_owned())
+        };
+
+        assert_eq!(
+            resolver.mode_of(Path::new("/p/src/index.mts")),
+            ResolutionMode::Import
+        );
+        assert_eq!(
+            resolver.mode_of(Path::new("/p/src/esm/index.cts")),
+            ResolutionMode::Require
+        );
+        assert_eq!(
+            resolver.mode_of(Path::new("/p/src/esm/index.ts")),
+            ResolutionMode::Import
+        );
+        assert_eq!(
+            resolver.mode_of(Path::new("/p/src/index.ts")),
+            ResolutionMode::Require
+        );
+
+        let esm = "/p/src/index.mts";
+        let cjs = "/p/src/index.cts";
+        assert_eq!(
+            resolve("dual", esm).as_deref(),
+            Some("/p/node_modules/dual/esm.d.mts")
+        );
+        assert_eq!(
+            resolve("dual", cjs).as_deref(),
+            Some("/p/node_modules/dual/cjs.d.cts")
+        );
+        // ES modules name the files they import.
+        assert_eq!(resolve("./util", esm), None);
+        assert_eq!(resolve("./util.js", esm).as_deref(), Some("/p/src/util.ts"));
+        assert_eq!(resolve("./util", cjs).as_deref(), Some("/p/src/util.ts"));
+        assert_eq!(resolve("./esm", esm), None);
+        assert_eq!(
+            resolver
+                .resolve_in_mode("dual", Path::new(esm), ResolutionMode::Require)
+                .as_deref(),
+            Some(Path::new("/p/node_modules/dual/cjs.d.cts"))
+        );
+    }
+
+    #[test]
+    fn test_types_versions_ranges() {
+        use server::version::{matches_range, Version};
+
+        let version = Version::new(5, 7, 2);
+        for range in [
+            "*",
+            ">=4.2",
+            ">5.6",
+            "<6",
+            "<=5.7",
+            "5.x",
+            "~5.7.0",
+            "^5.1",
+            "5.0 - 5.7",
+        ] {
+            assert!(matches_range(range, version), "{}", range);
+        }
+        for range in [
+            "<5",
+            ">5.7",
+            "~5.6",
+            "4.x",
+            "4.0 - 5.6",
+            ">=4 <5",
+            "invalid",
+        ] {
+            assert!(!matches_range(range, version), "{}", range);
+        }
+        assert!(matches_range("<4 || >=5.7", version));
+
+        use file_system::MemoryFileSystem;
+        let fs: MemoryFileSystem = [
+            (
+                "/p/node_modules/versioned/package.json",
+                r#"{
+                    "types": "index.d.ts",
+                    "typesVersions": {
+                        "<4.0": { "*": ["ts3/*"] },
+                        ">=4.0 <5": { "*": ["ts4/*"] },
+                        ">=5.0": { "*": ["ts5/*"] }
+                    }
+                }"#,
+            ),
+            ("/p/node_modules/versioned/ts3/index.d.ts", "export {};"),
+            ("/p/node_modules/versioned/ts4/index.d.ts", "export {};"),
+            ("/p/node_modules/versioned/ts5/index.d.ts", "export {};"),
+        ]
+        .into_iter()
+        .collect();
+        let resolver = ModuleResolver::with_file_system(Arc::new(fs));
+        assert_eq!(
+            resolver.resolve("versioned", Path::new("/p/index.ts")),
+            Some(PathBuf::from("/p/node_modules/versioned/ts5/index.d.ts"))
+        );
+    }
+
+    #[test]
+    fn test_parallel_queries() {
+        fn assert_sync<T: Sync>(_: &T) {}
+
+        let tsconfig = &repo_path("src/fixtures/project/tsconfig.json");
+        let mut server = Server::from_tsconfig(&repo_path("src/lib"), tsconfig).unwrap();
+        let paths = server.files().to_vec();
+        server.analyze(paths.clone()).unwrap();
+        assert_sync(&server);
+
+        let server = &server;
+        std::thread::scope(|scope| {
+            for path in &paths {
+                scope.spawn(move || {
+                    let analyzer = server.scoped_analyzer(path);
+                    let imports_load = path.ends_with("src/client.ts");
+                    let declares_load = path.ends_with("src/index.ts");
+                    assert_eq!(
+                        analyzer.get_symbol("load").is_some(),
+                        imports_load || declares_load,
+                        "{}",
+                        path.display()
+                    );
+                });
+            }
+        });
+    }
+
+    #[test]
+    fn test_declaration_cache() {
+        let dir = std::env::temp_dir().join(format!("type_poc_cache_{}", std::process::id()));
+        let tsconfig = &repo_path("src/fixtures/project/tsconfig.json");
+        let run = || {
+            let options = ServerOptions {
+                cache: Some(DeclarationCache::new(&dir)),
+                ..Default::default()
+            };
+            let mut server =
+                Server::from_tsconfig_options(&repo_path("src/lib"), tsconfig, options).unwrap();
+            let paths = server.files().to_vec();
+            server.analyze(paths.clone()).unwrap();
+            let client = paths
+                .iter()
+                .find(|path| path.ends_with("src/client.ts"))
+                .unwrap();
+            let analyzer = server.scoped_analyzer(client);
+            // Each run has its own arena, so types are compared printed.
+            let axios = analyzer
+                .get_symbol("axios")
+                .map(|symbol| server.types().print(symbol.ty, &Default::default()));
+            let promise = analyzer
+                .get_symbol("Promise")
+                .map(|symbol| server.types().print(symbol.ty, &Default::default()));
+            (axios, promise)
+        };
+
+        let (axios, promise) = run();
+        let entries = fs::read_dir(&dir).unwrap().count();
+        assert!(entries > 0);
+        // The second run loads the lib files and `.d.ts` files from the cache.
+        assert_eq!(run(), (axios.clone(), promise.clone()));
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), entries);
+        assert!(axios.is_some() && promise.is_some());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_definitions_and_references() {
+        use biome_js_syntax::{JsSyntaxKind, TextRange};
+
+        let fs = Arc::new(OverlayFileSystem::new(Arc::new(OsFileSystem)));
+        let util = PathBuf::from("/memory/references/util.ts");
+        let index = PathBuf::from("/memory/references/index.ts");
+        let util_src = "export declare function load(): Promise<string>;";
+        let index_src = "import { load } from \"./util\";\nload();\nvoid load();";
+        fs.open(&util, util_src);
+        fs.open(&index, index_src);
+        let options = ServerOptions {
+            fs: fs.clone(),
+            cache: None,
+        };
+        let mut server =
+            Server::with_lib_options(&repo_path("src/lib"), &["es2018".to_string()], options)
+                .unwrap();
+        server.analyze(vec![index.clone()]).unwrap();
+
+        let find = |src: &str, kind: JsSyntaxKind| {
+            let parsed = parse(src, JsFileSource::ts(), Default::default());
+            parsed
+                .syntax()
+                .descendants()
+                .find(|node| node.kind() == kind)
+                .unwrap()
+        };
+        let at = |path: &PathBuf, start: usize| {
+            (path.clone(), TextRange::at((start as u32).into(), 4.into()))
+        };
+
+        // From a call in one file to the declaration in the other.
+        let call = find(index_src, JsSyntaxKind::JS_REFERENCE_IDENTIFIER);
+        let symbol = server.definition_of(&index, &call).unwrap();
+        let declaration = at(&util, util_src.find("load").unwrap());
+        let declarations: Vec<_> = symbol
+            .declarations
+            .iter()
+            .map(|location| (location.path.clone(), location.range))
+            .collect();
+        assert_eq!(declarations, [declaration]);
+
+        let binding = find(util_src, JsSyntaxKind::JS_IDENTIFIER_BINDING);
+        let declared = server.definition_of(&util, &binding).unwrap();
+        let calls: Vec<_> = index_src
+            .match_indices("load()")
+            .map(|(start, _)| at(&index, start))
+            .collect();
+        let references: Vec<_> = server
+            .references_of(declared)
+            .into_iter()
+            .map(|location| (location.path, location.range))
+            .collect();
+        assert_eq!(references, calls);
+    }
+
+    #[test]
+    fn test_unreadable_lib_files() {
+        let missing = PathBuf::from("/memory/libs/missing.d.ts");
+        let dir = std::env::temp_dir().join(format!("lib-errors-{}", std::process::id()));
+        for cache in [None, Some(DeclarationCache::new(&dir))] {
+            let options = ServerOptions {
+                cache,
+                ..Default::default()
+            };
+            let err = Server::with_options(vec![missing.clone()], options).unwrap_err();
+            assert_eq!(err.path, missing);
+        }
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_call_arity() {
+        let fs = Arc::new(OverlayFileSystem::new(Arc::new(OsFileSystem)));
+        let index = PathBuf::from("/memory/arity/index.ts");
+        let src = r#"declare function twoArgs(a: number, b?: string): void;
+function wrap(callback: (value: number) => void) {
+    callback();
+    twoArgs(1);
+}
+twoArgs(1, "a", 2);
+Promise.resolve();
+Promise.resolve(1);
+"#;
+        fs.open(&index, src);
+        let options = ServerOptions {
+            fs: fs.clone(),
+            cache: None,
+        };
+        let mut server =
+            Server::with_lib_options(&repo_path("src/lib"), &["es2018".to_string()], options)
+                .unwrap();
+        server.analyze(vec![index.clone()]).unwrap();
+
+        // `callback` is only known inside `wrap`, as a parameter.
+        let mismatches: Vec<_> = server
+            .arity_mismatches(&index)
+            .iter()
+            .map(|mismatch| (&src[mismatch.range], mismatch.to_string()))
+            .collect();
+        assert_eq!(
+            mismatches,
+            [
+                ("callback()", "Expected 1 arguments, but got 0.".to_string()),
+                (
+                    r#"twoArgs(1, "a", 2)"#,
+                    "Expected 1-2 arguments, but got 3.".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_update_and_remove_file() {
+        let tsconfig = &repo_path("src/fixtures/project/tsconfig.json");
+        let mut server = Server::from_tsconfig(&repo_path("src/lib"), tsconfig).unwrap();
+        let paths = server.files().to_vec();
+        server.analyze(paths.clone()).unwrap();
+        let index = paths
+            .iter()
+            .find(|path| path.ends_with("src/index.ts"))
+            .unwrap();
+        let client = paths
+            .iter()
+            .find(|path| path.ends_with("src/client.ts"))
+            .unwrap();
+        let load_type = |server: &Server| {
+            let analyzer = server.scoped_analyzer(client);
+            analyzer
+                .get_symbol("load")
+                .map(|symbol| server.get_type(symbol.ty))
+        };
+
+        let before = load_type(&server).unwrap();
+        server
+            .update_file(index, "export declare function load(): string;")
+            .unwrap();
+        let after = load_type(&server).unwrap();
+        assert_ne!(before, after);
+        server
+            .update_file(index, "export declare function load(): Promise<string>;")
+            .unwrap();
+        assert_eq!(load_type(&server).unwrap(), before);
+
+        server.remove_file(index);
+        assert!(!server.module_graph().contains(index));
+        assert!(!server.files().contains(index));
+        assert!(load_type(&server).is_none());
+
+        // A file that didn't exist before, e.g. created in an editor.
+        server
+            .update_file(index, "export declare function load(): Promise<string>;")
+            .unwrap();
+        assert!(server.module_graph().contains(index));
+        assert!(server.files().contains(index));
+        assert_eq!(load_type(&server).unwrap(), before);
+    }
+
+    #[test]
+    fn test_type_arena_rebuilds() {
+        use file_system::MemoryFileSystem;
+
+        let (index, util) = (
+            PathBuf::from("/memory/arena/index.ts"),
+            PathBuf::from("/memory/arena/util.ts"),
+        );
+        let fs = MemoryFileSystem::from_iter([
+            (index.clone(), "import { value } from \"./util\";\nvalue;"),
+            (util.clone(), "export declare const value: \"0\";"),
+        ]);
+        let mut server = Server::without_libs(ServerOptions {
+            fs: Arc::new(fs),
+            cache: None,
+        });
+        server.analyze(vec![index.clone()]).unwrap();
+        let live = server.types().len();
+
+        for i in 1..=50 {
+            let text = format!("export declare const value: \"{i}\";");
+            server.update_file(&util, &text).unwrap();
+            assert!(server.types().len() <= 2 * live + 2);
+        }
+        // The import is bound again to the latest declaration.
+        let analyzer = server.scoped_analyzer(&index);
+        let value = analyzer.get_symbol("value").unwrap();
+        assert_eq!(
+            server.types().print(value.ty, &Default::default()),
+            "\"50\""
+        );
+    }
+
+    #[test]
+    fn test_emit_declarations() {
+        // The expected files are the output of `tsc --declaration`.
+        let dir = &repo_path("src/fixtures/declarations");
+        let source = |name: &str| dir.join("src").join(format!("{}.ts", name));
+        let mut server = Server::with_lib(&repo_path("src/lib"), &["es2018".to_string()]).unwrap();
+        server.analyze(vec![source("index")]).unwrap();
+
+        for name in ["types", "index"] {
+            let expected = fs::read_to_string(dir.join("expected").join(format!("{}.d.ts", name)));
+            assert_eq!(
+                server.emit_declarations(&source(name)),
+                Some(expected.unwrap()),
+                "{}",
+                name
+            );
+        }
+    }
+}
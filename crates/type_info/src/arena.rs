@@ -0,0 +1,143 @@
+use std::sync::{Arc, RwLock};
+
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::Type;
+
+/// Handle to a type interned in a [TypeArena]. Structurally equal types get
+/// the same ID, so comparing IDs compares types.
+///
+/// IDs only mean something in their arena. Serialized types go through a
+/// [TypeExport], whose IDs are indices into its own list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct TypeId(u32);
+
+impl TypeId {
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Hash-consed storage for types. Interning only needs `&self`, so types can
+/// be created while the analyzer is queried.
+///
+/// Types are never freed, since any later type may be made of them, so an
+/// arena only grows. Long-lived owners that analyze changing files start
+/// over from a new arena from time to time.
+#[derive(Debug, Default)]
+pub struct TypeArena {
+    inner: RwLock<Interned>,
+}
+
+#[derive(Debug, Default)]
+struct Interned {
+    types: Vec<Arc<Type>>,
+    ids: FxHashMap<Arc<Type>, TypeId>,
+    /// Instantiations of generic types by their type arguments.
+    instantiations: FxHashMap<(TypeId, Vec<TypeId>), TypeId>,
+}
+
+impl TypeArena {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intern(&self, ty: Type) -> TypeId {
+        if let Some(id) = self.inner.read().unwrap().ids.get(&ty) {
+            return *id;
+        }
+        let mut inner = self.inner.write().unwrap();
+        if let Some(id) = inner.ids.get(&ty) {
+            return *id;
+        }
+        let id = TypeId(inner.types.len() as u32);
+        let ty = Arc::new(ty);
+        inner.types.push(ty.clone());
+        inner.ids.insert(ty, id);
+        id
+    }
+
+    pub fn get(&self, id: TypeId) -> Arc<Type> {
+        self.inner.read().unwrap().types[id.index()].clone()
+    }
+
+    /// The number of distinct types interned.
+    pub fn len(&self) -> usize {
+        self.inner.read().unwrap().types.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Interns `ty` with `f` applied to its children, without interning
+    /// anything when no child changes.
+    pub fn map(&self, id: TypeId, f: impl FnMut(TypeId) -> TypeId) -> TypeId {
+        let ty = self.get(id);
+        let mapped = ty.map_children(f);
+        if mapped == *ty {
+            id
+        } else {
+            self.intern(mapped)
+        }
+    }
+
+    /// Returns the instantiation of `generic` with `args`, computing it with
+    /// `instantiate` the first time.
+    pub fn instantiate(
+        &self,
+        generic: TypeId,
+        args: Vec<TypeId>,
+        instantiate: impl FnOnce(&Type) -> TypeId,
+    ) -> TypeId {
+        let key = (generic, args);
+        if let Some(id) = self.inner.read().unwrap().instantiations.get(&key) {
+            return *id;
+        }
+        let instance = instantiate(&self.get(generic));
+        self.inner
+            .write()
+            .unwrap()
+            .instantiations
+            .insert(key, instance);
+        instance
+    }
+
+    /// Adds `id` and the types it is made of to `export`, children first,
+    /// and returns its ID there.
+    pub fn export(&self, id: TypeId, export: &mut TypeExport) -> TypeId {
+        if let Some(exported) = export.ids.get(&id) {
+            return *exported;
+        }
+        // Interned types only refer to types interned before them, so this
+        // terminates.
+        let ty = self
+            .get(id)
+            .map_children(|child| self.export(child, export));
+        let exported = TypeId(export.types.len() as u32);
+        export.types.push(ty);
+        export.ids.insert(id, exported);
+        exported
+    }
+
+    /// Interns the types of an export, returning their IDs in this arena by
+    /// their index there.
+    pub fn import(&self, types: &[Type]) -> Vec<TypeId> {
+        let mut ids: Vec<TypeId> = Vec::with_capacity(types.len());
+        for ty in types {
+            let ty = ty.map_children(|child| ids[child.index()]);
+            ids.push(self.intern(ty));
+        }
+        ids
+    }
+}
+
+/// Types copied out of an arena, to be serialized and interned in another
+/// with [TypeArena::import].
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TypeExport {
+    pub types: Vec<Type>,
+    #[serde(skip)]
+    ids: FxHashMap<TypeId, TypeId>,
+}
@@ -1,19 +1,141 @@
+pub mod arena;
+pub mod printer;
 pub mod symbol;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+use serde::{Deserialize, Serialize};
+
+pub use arena::{TypeArena, TypeExport, TypeId};
+pub use printer::PrintOptions;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Type {
     KeywordType(TsKeywordTypeKind),
-    Union(Vec<Type>),
-    Intersection(Vec<Type>),
+    Union(Vec<TypeId>),
+    Intersection(Vec<TypeId>),
+    Tuple(Vec<TupleElement>),
     Function(TsFunctionSignature),
     Alias(TsTypeAlias),
     Interface(TsInterface),
     Literal(TsLiteralTypeKind),
     TypeRef(TsTypeRef),
+    /// The polymorphic `this` type, substituted with the receiver at member access.
+    This,
+    /// A reference that leads back to itself, e.g. `type A = B; type B = A;`.
+    Circular,
+    /// `keyof T`. Like the other deferred types, it is computed when its
+    /// operands are known, see [Type::is_deferred].
+    KeyOf(TypeId),
+    /// `T[K]`.
+    IndexedAccess(IndexedAccessType),
+    /// `{ [P in K]: T }`.
+    Mapped(MappedType),
+    /// `typeof a.b`, the type of a value.
+    TypeQuery(String),
+    /// A type the analyzer doesn't model, such as a conditional type, kept as
+    /// its source text.
+    Unsupported(String),
     Unknown,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+impl Type {
+    /// Whether the type is computed from other types once they are resolved.
+    pub fn is_deferred(&self) -> bool {
+        matches!(
+            self,
+            Type::KeyOf(_) | Type::IndexedAccess(_) | Type::Mapped(_) | Type::TypeQuery(_)
+        )
+    }
+
+    /// The same type with `f` applied to the ID of every type it is made of.
+    pub fn map_children(&self, mut f: impl FnMut(TypeId) -> TypeId) -> Type {
+        match self {
+            Type::Union(types) => Type::Union(types.iter().copied().map(f).collect()),
+            Type::Intersection(types) => Type::Intersection(types.iter().copied().map(f).collect()),
+            Type::Tuple(elements) => Type::Tuple(
+                elements
+                    .iter()
+                    .map(|element| TupleElement {
+                        ty: f(element.ty),
+                        ..*element
+                    })
+                    .collect(),
+            ),
+            Type::Function(func) => Type::Function(TsFunctionSignature {
+                type_params: map_type_params(&func.type_params, &mut f),
+                this_param: func.this_param.map(&mut f),
+                params: func
+                    .params
+                    .iter()
+                    .map(|param| FunctionParam {
+                        param_type: f(param.param_type),
+                        ..param.clone()
+                    })
+                    .collect(),
+                return_type: f(func.return_type),
+                is_async: func.is_async,
+            }),
+            Type::Alias(alias) => Type::Alias(TsTypeAlias {
+                name: alias.name.clone(),
+                type_params: map_type_params(&alias.type_params, &mut f),
+                aliased_type: f(alias.aliased_type),
+            }),
+            Type::Interface(interface) => Type::Interface(TsInterface {
+                name: interface.name.clone(),
+                extends: interface.extends.iter().copied().map(&mut f).collect(),
+                properties: interface
+                    .properties
+                    .iter()
+                    .map(|property| TsInterfaceProperty {
+                        type_info: f(property.type_info),
+                        ..property.clone()
+                    })
+                    .collect(),
+                type_params: map_type_params(&interface.type_params, &mut f),
+                type_args: interface.type_args.iter().copied().map(&mut f).collect(),
+            }),
+            Type::Literal(TsLiteralTypeKind::Object(object)) => {
+                Type::Literal(TsLiteralTypeKind::Object(ObjectLiteral {
+                    properties: object
+                        .properties
+                        .iter()
+                        .map(|property| ObjectPropertyType {
+                            name: property.name.clone(),
+                            type_info: f(property.type_info),
+                        })
+                        .collect(),
+                }))
+            }
+            Type::TypeRef(type_ref) => Type::TypeRef(TsTypeRef {
+                name: type_ref.name.clone(),
+                type_params: type_ref.type_params.iter().copied().map(f).collect(),
+            }),
+            Type::KeyOf(ty) => Type::KeyOf(f(*ty)),
+            Type::IndexedAccess(access) => Type::IndexedAccess(IndexedAccessType {
+                object: f(access.object),
+                index: f(access.index),
+            }),
+            Type::Mapped(mapped) => Type::Mapped(MappedType {
+                keys: f(mapped.keys),
+                value: f(mapped.value),
+                ..mapped.clone()
+            }),
+            _ => self.clone(),
+        }
+    }
+}
+
+fn map_type_params(params: &[TypeParam], f: &mut impl FnMut(TypeId) -> TypeId) -> Vec<TypeParam> {
+    params
+        .iter()
+        .map(|param| TypeParam {
+            name: param.name.clone(),
+            constraint: param.constraint.map(&mut *f),
+            default: param.default.map(&mut *f),
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TsKeywordTypeKind {
     // primitive
     BigInt,
@@ -31,7 +153,7 @@ pub enum TsKeywordTypeKind {
     Object,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TsLiteralTypeKind {
     Number(i64),
     String(String),
@@ -39,71 +161,161 @@ pub enum TsLiteralTypeKind {
     Object(ObjectLiteral),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum BoolLiteral {
     True,
     False,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ObjectLiteral {
     pub properties: Vec<ObjectPropertyType>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ObjectPropertyType {
     pub name: String,
-    pub type_info: Type,
+    pub type_info: TypeId,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct TsFunctionSignature {
     pub type_params: Vec<TypeParam>,
-    pub this_param: Option<Box<Type>>,
+    pub this_param: Option<TypeId>,
     pub params: Vec<FunctionParam>,
-    pub return_type: Box<Type>,
+    pub return_type: TypeId,
     pub is_async: bool,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+impl TsFunctionSignature {
+    /// Minimum and maximum number of arguments the signature accepts.
+    /// The maximum is `None` when a rest parameter makes it unbounded.
+    pub fn arity(&self, types: &TypeArena) -> (usize, Option<usize>) {
+        let mut min = 0;
+        let mut max = Some(0);
+        for (index, param) in self.params.iter().enumerate() {
+            if param.is_rest {
+                match &*types.get(param.param_type) {
+                    Type::Tuple(elements) => {
+                        let required = elements
+                            .iter()
+                            .filter(|element| !element.is_optional && !element.is_rest)
+                            .count();
+                        if required > 0 {
+                            min = index + required;
+                        }
+                        max = if elements.iter().any(|element| element.is_rest) {
+                            None
+                        } else {
+                            max.map(|m| m + elements.len())
+                        };
+                    }
+                    _ => max = None,
+                }
+                break;
+            }
+            if !param.is_optional {
+                min = index + 1;
+            }
+            max = max.map(|m| m + 1);
+        }
+        (min, max)
+    }
+
+    pub fn accepts_argument_count(&self, types: &TypeArena, count: usize) -> bool {
+        let (min, max) = self.arity(types);
+        count >= min && max.is_none_or(|max| count <= max)
+    }
+}
+
+/// An element of a tuple type: `T`, `T?` or `...T`, where `T` of a rest
+/// element is an array or tuple type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TupleElement {
+    pub ty: TypeId,
+    pub is_optional: bool,
+    pub is_rest: bool,
+}
+
+impl TupleElement {
+    pub fn required(ty: TypeId) -> Self {
+        Self {
+            ty,
+            is_optional: false,
+            is_rest: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct FunctionParam {
     pub name: String,
-    pub param_type: Type,
+    pub param_type: TypeId,
     pub is_optional: bool,
+    pub is_rest: bool,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct TsInterface {
     pub name: String,
-    pub extends: Vec<Type>,
+    pub extends: Vec<TypeId>,
     pub properties: Vec<TsInterfaceProperty>,
     pub type_params: Vec<TypeParam>,
+    /// The arguments an instance was created with, empty for the declaration.
+    pub type_args: Vec<TypeId>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct TsInterfaceProperty {
     pub name: String,
-    pub type_info: Type,
+    pub type_info: TypeId,
     pub is_optional: bool,
     pub is_readonly: bool,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct TsTypeAlias {
     pub name: String,
     pub type_params: Vec<TypeParam>,
-    pub aliased_type: Box<Type>,
+    pub aliased_type: TypeId,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct TsTypeRef {
     pub name: String,
-    pub type_params: Vec<Type>,
+    pub type_params: Vec<TypeId>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct IndexedAccessType {
+    pub object: TypeId,
+    pub index: TypeId,
+}
+
+/// `{ readonly [P in K]?: T }`, where `T` may refer to the parameter `P`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MappedType {
+    pub param: String,
+    pub keys: TypeId,
+    pub value: TypeId,
+    pub optional: MappedModifier,
+    pub readonly: MappedModifier,
+}
+
+/// The `?` or `readonly` modifier of a mapped type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MappedModifier {
+    /// `?` or `+?`.
+    Add,
+    /// `-?`.
+    Remove,
+    /// No modifier: `{ [P in keyof T]: T[P] }` keeps the modifiers of `T`.
+    Preserve,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct TypeParam {
     pub name: String,
-    pub constraint: Option<Type>,
-    pub default: Option<Type>,
+    pub constraint: Option<TypeId>,
+    pub default: Option<TypeId>,
 }
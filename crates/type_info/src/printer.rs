@@ -0,0 +1,433 @@
+use crate::{
+    BoolLiteral, MappedModifier, MappedType, TsFunctionSignature, TsKeywordTypeKind,
+    TsLiteralTypeKind, Type, TypeArena, TypeId, TypeParam,
+};
+
+/// Where tsc cuts types in error messages and hovers.
+pub const DEFAULT_MAX_LENGTH: usize = 160;
+
+/// How [TypeArena::print] renders a type.
+#[derive(Debug, Clone, Default)]
+pub struct PrintOptions {
+    /// Longer output is cut to this many characters, ending with `...`.
+    pub max_length: Option<usize>,
+    /// Prints what aliases stand for instead of their names. References are
+    /// followed with the resolver given to [TypeArena::print_with].
+    pub expand_aliases: bool,
+    /// Puts each member of an object type on a line of its own.
+    pub multiline: bool,
+}
+
+impl TypeArena {
+    /// The type in TypeScript syntax, like tsc's `typeToString`, e.g.
+    /// `Promise<string | number>` or `(value: T) => void`.
+    pub fn print(&self, id: TypeId, options: &PrintOptions) -> String {
+        self.print_with(id, options, &|id| id)
+    }
+
+    /// Like [TypeArena::print], with `resolve` giving the type a reference
+    /// stands for, e.g. the aliased type of `Id`, to expand aliases with.
+    pub fn print_with(
+        &self,
+        id: TypeId,
+        options: &PrintOptions,
+        resolve: &dyn Fn(TypeId) -> TypeId,
+    ) -> String {
+        let mut printer = Printer {
+            types: self,
+            options,
+            resolve,
+            expanding: vec![],
+            out: String::new(),
+            indent: 0,
+        };
+        printer.print(id);
+        truncate(printer.out, options.max_length)
+    }
+}
+
+/// The name the analyzer gives object types, like tsc.
+const ANONYMOUS_TYPE_NAME: &str = "__type";
+
+struct Member<'a> {
+    name: &'a str,
+    ty: TypeId,
+    is_optional: bool,
+    is_readonly: bool,
+}
+
+/// Where a type is printed, for the types that need parentheses there.
+#[derive(Clone, Copy)]
+enum Position {
+    Union,
+    Intersection,
+    ArrayElement,
+}
+
+struct Printer<'a> {
+    types: &'a TypeArena,
+    options: &'a PrintOptions,
+    resolve: &'a dyn Fn(TypeId) -> TypeId,
+    /// The references being expanded, to stop at recursive aliases.
+    expanding: Vec<TypeId>,
+    out: String,
+    indent: usize,
+}
+
+impl Printer<'_> {
+    fn print(&mut self, id: TypeId) {
+        match &*self.types.get(id) {
+            Type::KeywordType(keyword) => self.out.push_str(keyword_name(keyword)),
+            Type::Union(types) => self.list(types, " | ", Position::Union),
+            Type::Intersection(types) => self.list(types, " & ", Position::Intersection),
+            Type::Tuple(elements) => {
+                self.out.push('[');
+                self.separated(elements, ", ", |printer, element| {
+                    if element.is_rest {
+                        printer.out.push_str("...");
+                        printer.print(element.ty);
+                    } else if element.is_optional {
+                        printer.operand(element.ty, Position::ArrayElement);
+                        printer.out.push('?');
+                    } else {
+                        printer.print(element.ty);
+                    }
+                });
+                self.out.push(']');
+            }
+            Type::Function(signature) => self.function(signature),
+            Type::Alias(alias) if self.options.expand_aliases => self.print(alias.aliased_type),
+            Type::Alias(alias) => self.declared(&alias.name, &alias.type_params),
+            Type::Interface(interface) if interface.name == ANONYMOUS_TYPE_NAME => {
+                let members = interface.properties.iter().map(|property| Member {
+                    name: &property.name,
+                    ty: property.type_info,
+                    is_optional: property.is_optional,
+                    is_readonly: property.is_readonly,
+                });
+                self.object(members.collect());
+            }
+            Type::Interface(interface) if !interface.type_args.is_empty() => {
+                self.reference(&interface.name, &interface.type_args)
+            }
+            Type::Interface(interface) => self.declared(&interface.name, &interface.type_params),
+            Type::Literal(literal) => self.literal(literal),
+            Type::TypeRef(type_ref) => match self.expanded(id) {
+                Some(target) => {
+                    self.expanding.push(id);
+                    self.print(target);
+                    self.expanding.pop();
+                }
+                None => self.reference(&type_ref.name, &type_ref.type_params),
+            },
+            Type::This => self.out.push_str("this"),
+            // Like tsc, which gives up on circular types with `any`.
+            Type::Circular => self.out.push_str("any"),
+            Type::KeyOf(ty) => {
+                self.out.push_str("keyof ");
+                self.operand(*ty, Position::ArrayElement);
+            }
+            Type::IndexedAccess(access) => {
+                self.operand(access.object, Position::ArrayElement);
+                self.out.push('[');
+                self.print(access.index);
+                self.out.push(']');
+            }
+            Type::Mapped(mapped) => self.mapped(mapped),
+            Type::TypeQuery(name) => {
+                self.out.push_str("typeof ");
+                self.out.push_str(name);
+            }
+            Type::Unsupported(source) => self.out.push_str(source),
+            Type::Unknown => self.out.push_str("unknown"),
+        }
+    }
+
+    fn list(&mut self, types: &[TypeId], separator: &str, position: Position) {
+        self.separated(types, separator, |printer, ty| {
+            printer.operand(*ty, position)
+        });
+    }
+
+    fn operand(&mut self, ty: TypeId, position: Position) {
+        let parenthesized = matches!(
+            (&*self.types.get(self.shown(ty)), position),
+            (Type::Function(_), _)
+                | (
+                    Type::Union(_),
+                    Position::Intersection | Position::ArrayElement
+                )
+                | (
+                    Type::Intersection(_) | Type::KeyOf(_),
+                    Position::ArrayElement
+                )
+        );
+        if parenthesized {
+            self.out.push('(');
+            self.print(ty);
+            self.out.push(')');
+        } else {
+            self.print(ty);
+        }
+    }
+
+    /// The type printed for `ty`, which differs for expanded aliases.
+    fn shown(&self, ty: TypeId) -> TypeId {
+        let mut shown = ty;
+        let mut followed = vec![];
+        loop {
+            match &*self.types.get(shown) {
+                Type::Alias(alias) if self.options.expand_aliases => shown = alias.aliased_type,
+                Type::TypeRef(_) if !followed.contains(&shown) => match self.expanded(shown) {
+                    Some(target) => {
+                        followed.push(shown);
+                        shown = target;
+                    }
+                    None => return shown,
+                },
+                _ => return shown,
+            }
+        }
+    }
+
+    /// What the reference `id` is printed as when aliases are expanded.
+    /// References to interfaces stay as they are, like in tsc.
+    fn expanded(&self, id: TypeId) -> Option<TypeId> {
+        if !self.options.expand_aliases || self.expanding.contains(&id) {
+            return None;
+        }
+        let Type::TypeRef(type_ref) = &*self.types.get(id) else {
+            return None;
+        };
+        let target = (self.resolve)(id);
+        match &*self.types.get(target) {
+            _ if target == id => None,
+            Type::Unknown | Type::Circular => None,
+            Type::Interface(interface) if interface.name == type_ref.name => None,
+            _ => Some(target),
+        }
+    }
+
+    fn function(&mut self, signature: &TsFunctionSignature) {
+        self.type_params(&signature.type_params);
+        self.out.push('(');
+        if let Some(this) = signature.this_param {
+            self.out.push_str("this: ");
+            self.print(this);
+            if !signature.params.is_empty() {
+                self.out.push_str(", ");
+            }
+        }
+        self.separated(&signature.params, ", ", |printer, param| {
+            if param.is_rest {
+                printer.out.push_str("...");
+            }
+            printer.out.push_str(&param.name);
+            if param.is_optional {
+                printer.out.push('?');
+            }
+            printer.out.push_str(": ");
+            printer.print(param.param_type);
+        });
+        self.out.push_str(") => ");
+        self.print(signature.return_type);
+    }
+
+    /// An interface or alias is shown by name, with its type parameters.
+    fn declared(&mut self, name: &str, type_params: &[TypeParam]) {
+        self.out.push_str(name);
+        if !type_params.is_empty() {
+            self.out.push('<');
+            self.separated(type_params, ", ", |printer, param| {
+                printer.out.push_str(&param.name)
+            });
+            self.out.push('>');
+        }
+    }
+
+    fn type_params(&mut self, type_params: &[TypeParam]) {
+        if type_params.is_empty() {
+            return;
+        }
+        self.out.push('<');
+        self.separated(type_params, ", ", |printer, param| {
+            printer.out.push_str(&param.name);
+            if let Some(constraint) = param.constraint {
+                printer.out.push_str(" extends ");
+                printer.print(constraint);
+            }
+            if let Some(default) = param.default {
+                printer.out.push_str(" = ");
+                printer.print(default);
+            }
+        });
+        self.out.push('>');
+    }
+
+    /// A reference or an instance of a generic interface, with its type
+    /// arguments.
+    fn reference(&mut self, name: &str, type_args: &[TypeId]) {
+        match (name, type_args) {
+            ("Array", [element]) => {
+                self.operand(*element, Position::ArrayElement);
+                self.out.push_str("[]");
+            }
+            ("ReadonlyArray", [element]) => {
+                self.out.push_str("readonly ");
+                self.operand(*element, Position::ArrayElement);
+                self.out.push_str("[]");
+            }
+            (name, []) => self.out.push_str(name),
+            (name, type_args) => {
+                self.out.push_str(name);
+                self.out.push('<');
+                self.separated(type_args, ", ", |printer, ty| printer.print(*ty));
+                self.out.push('>');
+            }
+        }
+    }
+
+    fn literal(&mut self, literal: &TsLiteralTypeKind) {
+        match literal {
+            TsLiteralTypeKind::Number(value) => self.out.push_str(&value.to_string()),
+            TsLiteralTypeKind::String(value) => self.string(value),
+            TsLiteralTypeKind::Boolean(BoolLiteral::True) => self.out.push_str("true"),
+            TsLiteralTypeKind::Boolean(BoolLiteral::False) => self.out.push_str("false"),
+            TsLiteralTypeKind::Object(object) => {
+                let members = object.properties.iter().map(|property| Member {
+                    name: &property.name,
+                    ty: property.type_info,
+                    is_optional: false,
+                    is_readonly: false,
+                });
+                self.object(members.collect());
+            }
+        }
+    }
+
+    /// String literal types hold either the string or its source text,
+    /// quotes included. Both are printed in double quotes.
+    fn string(&mut self, value: &str) {
+        if value.len() > 1 && value.starts_with('"') && value.ends_with('"') {
+            self.out.push_str(value);
+            return;
+        }
+        if let Some(source) = value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+            self.out.push('"');
+            self.out
+                .push_str(&source.replace("\\'", "'").replace('"', "\\\""));
+            self.out.push('"');
+            return;
+        }
+        self.out.push('"');
+        for c in value.chars() {
+            match c {
+                '"' => self.out.push_str("\\\""),
+                '\\' => self.out.push_str("\\\\"),
+                '\n' => self.out.push_str("\\n"),
+                c => self.out.push(c),
+            }
+        }
+        self.out.push('"');
+    }
+
+    /// `{ a: number; b?: string; }`, or one member per line.
+    fn object(&mut self, members: Vec<Member>) {
+        if members.is_empty() {
+            self.out.push_str("{}");
+            return;
+        }
+        self.out.push('{');
+        self.indent += 1;
+        for member in members {
+            self.member_break();
+            if member.is_readonly {
+                self.out.push_str("readonly ");
+            }
+            self.out.push_str(member.name);
+            if member.is_optional {
+                self.out.push('?');
+            }
+            self.out.push_str(": ");
+            self.print(member.ty);
+            self.out.push(';');
+        }
+        self.indent -= 1;
+        self.member_break();
+        self.out.push('}');
+    }
+
+    /// `{ readonly [P in K]?: T; }`.
+    fn mapped(&mut self, mapped: &MappedType) {
+        self.out.push('{');
+        self.indent += 1;
+        self.member_break();
+        match mapped.readonly {
+            MappedModifier::Add => self.out.push_str("readonly "),
+            MappedModifier::Remove => self.out.push_str("-readonly "),
+            MappedModifier::Preserve => {}
+        }
+        self.out.push('[');
+        self.out.push_str(&mapped.param);
+        self.out.push_str(" in ");
+        self.print(mapped.keys);
+        self.out.push(']');
+        match mapped.optional {
+            MappedModifier::Add => self.out.push('?'),
+            MappedModifier::Remove => self.out.push_str("-?"),
+            MappedModifier::Preserve => {}
+        }
+        self.out.push_str(": ");
+        self.print(mapped.value);
+        self.out.push(';');
+        self.indent -= 1;
+        self.member_break();
+        self.out.push('}');
+    }
+
+    fn member_break(&mut self) {
+        if self.options.multiline {
+            self.out.push('\n');
+            self.out.push_str(&"    ".repeat(self.indent));
+        } else {
+            self.out.push(' ');
+        }
+    }
+
+    fn separated<T>(&mut self, items: &[T], separator: &str, mut print: impl FnMut(&mut Self, &T)) {
+        for (index, item) in items.iter().enumerate() {
+            if index > 0 {
+                self.out.push_str(separator);
+            }
+            print(self, item);
+        }
+    }
+}
+
+fn keyword_name(keyword: &TsKeywordTypeKind) -> &'static str {
+    match keyword {
+        TsKeywordTypeKind::BigInt => "bigint",
+        TsKeywordTypeKind::Boolean => "boolean",
+        TsKeywordTypeKind::Null => "null",
+        TsKeywordTypeKind::Number => "number",
+        TsKeywordTypeKind::String => "string",
+        TsKeywordTypeKind::Symbol => "symbol",
+        TsKeywordTypeKind::Undefined => "undefined",
+        TsKeywordTypeKind::Any => "any",
+        TsKeywordTypeKind::Unknown => "unknown",
+        TsKeywordTypeKind::Never => "never",
+        TsKeywordTypeKind::Void => "void",
+        TsKeywordTypeKind::Object => "object",
+    }
+}
+
+/// Like tsc, keeps `max_length` characters in all, `...` included.
+fn truncate(text: String, max_length: Option<usize>) -> String {
+    match max_length {
+        Some(max_length) if text.chars().count() > max_length => {
+            let kept: String = text.chars().take(max_length.saturating_sub(3)).collect();
+            kept + "..."
+        }
+        _ => text,
+    }
+}
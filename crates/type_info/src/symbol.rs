@@ -1,61 +1,270 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, sync::Arc};
 
-use crate::Type;
+use crate::TypeId;
+use biome_rowan::TextRange;
 use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub struct Symbol {
     pub name: String,
-    pub ty: Type,
+    /// The declared type.
+    pub ty: TypeId,
+    /// Whether the binding can be reassigned (`let`/`var`).
+    pub is_mutable: bool,
+    /// The type narrowed by the latest assignment, when it differs from `ty`.
+    pub flow_ty: Option<TypeId>,
+    /// Where the symbol is declared, several times for merged declarations.
+    /// Imported symbols keep the declarations of the exported ones.
+    pub declarations: Vec<Location>,
+}
+
+/// Declarations don't take part in equality, which compares what the name
+/// is bound to.
+impl PartialEq for Symbol {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.ty == other.ty
+            && self.is_mutable == other.is_mutable
+            && self.flow_ty == other.flow_ty
+    }
+}
+
+impl Eq for Symbol {}
+
+/// A range in a file, such as the name a symbol is declared with.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Location {
+    pub path: PathBuf,
+    pub range: TextRange,
 }
 
 impl Symbol {
-    pub fn new(name: String, ty: Type) -> Self {
-        Self { name, ty }
+    pub fn new(name: String, ty: TypeId) -> Self {
+        Self {
+            name,
+            ty,
+            is_mutable: false,
+            flow_ty: None,
+            declarations: vec![],
+        }
+    }
+
+    pub fn declared_at(mut self, location: Location) -> Self {
+        self.declarations.push(location);
+        self
     }
+
+    pub fn mutable(name: String, ty: TypeId) -> Self {
+        Self {
+            is_mutable: true,
+            ..Self::new(name, ty)
+        }
+    }
+
+    pub fn current_type(&self) -> TypeId {
+        self.flow_ty.unwrap_or(self.ty)
+    }
+}
+
+/// A name can be declared both as a type and as a value, e.g. `Promise` is
+/// an interface and a `declare var` of type `PromiseConstructor`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Namespace {
+    Value,
+    Type,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Symbols {
+    values: FxHashMap<String, Symbol>,
+    types: FxHashMap<String, Symbol>,
+    /// What a module exports, by the exported name, which may differ from
+    /// the local one.
+    exports: FxHashMap<(Namespace, String), Symbol>,
 }
 
+impl Symbols {
+    pub fn iter(&self) -> impl Iterator<Item = (Namespace, &Symbol)> {
+        self.values
+            .values()
+            .map(|symbol| (Namespace::Value, symbol))
+            .chain(self.types.values().map(|symbol| (Namespace::Type, symbol)))
+    }
+
+    pub fn insert(&mut self, namespace: Namespace, symbol: Symbol) {
+        self.table_mut(namespace)
+            .insert(symbol.name.clone(), symbol);
+    }
+
+    pub fn get(&self, namespace: Namespace, name: &str) -> Option<&Symbol> {
+        match namespace {
+            Namespace::Value => self.values.get(name),
+            Namespace::Type => self.types.get(name),
+        }
+    }
+
+    pub fn get_mut(&mut self, namespace: Namespace, name: &str) -> Option<&mut Symbol> {
+        self.table_mut(namespace).get_mut(name)
+    }
+
+    pub fn exports(&self) -> impl Iterator<Item = (Namespace, &Symbol)> {
+        self.exports
+            .iter()
+            .map(|((namespace, _), symbol)| (*namespace, symbol))
+    }
+
+    pub fn export(&mut self, namespace: Namespace, symbol: Symbol) {
+        self.exports
+            .insert((namespace, symbol.name.clone()), symbol);
+    }
+
+    pub fn get_export(&self, namespace: Namespace, name: &str) -> Option<&Symbol> {
+        self.exports.get(&(namespace, name.to_string()))
+    }
+
+    fn table_mut(&mut self, namespace: Namespace) -> &mut FxHashMap<String, Symbol> {
+        match namespace {
+            Namespace::Value => &mut self.values,
+            Namespace::Type => &mut self.types,
+        }
+    }
+}
+
+/// The symbols of each file scope. Scopes are shared with the tables forked
+/// from this one and copied when a fork changes them.
 #[derive(Debug, Default)]
-pub struct SymbolTable(FxHashMap<PathBuf, FxHashMap<String, Symbol>>);
+pub struct SymbolTable {
+    shared: Arc<FxHashMap<PathBuf, Arc<Symbols>>>,
+    /// Scopes changed since the table was forked or frozen.
+    local: FxHashMap<PathBuf, Symbols>,
+}
 
 impl SymbolTable {
     pub fn new() -> Self {
-        Self(FxHashMap::default())
+        Self::default()
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = (&PathBuf, &FxHashMap<String, Symbol>)> {
-        self.0.iter()
+    pub fn iter(&self) -> impl Iterator<Item = (&PathBuf, &Symbols)> {
+        let shared = self
+            .shared
+            .iter()
+            .filter(|(path, _)| !self.local.contains_key(*path))
+            .map(|(path, symbols)| (path, symbols.as_ref()));
+        self.local.iter().chain(shared)
     }
 
-    pub fn insert(&mut self, path: PathBuf, symbol: Symbol) {
-        self.0
-            .entry(path)
-            .or_default()
-            .insert(symbol.name.clone(), symbol);
+    pub fn insert(&mut self, path: PathBuf, namespace: Namespace, symbol: Symbol) {
+        self.scope_mut(path).insert(namespace, symbol);
+    }
+
+    pub fn get(&self, path: &PathBuf, namespace: Namespace, name: &str) -> Option<&Symbol> {
+        self.symbols(path)?.get(namespace, name)
+    }
+
+    pub fn export(&mut self, path: PathBuf, namespace: Namespace, symbol: Symbol) {
+        self.scope_mut(path).export(namespace, symbol);
+    }
+
+    /// The symbol the module at `path` exports as `name`.
+    pub fn get_export(&self, path: &PathBuf, namespace: Namespace, name: &str) -> Option<&Symbol> {
+        self.symbols(path)?.get_export(namespace, name)
+    }
+
+    pub fn get_mut(
+        &mut self,
+        path: &PathBuf,
+        namespace: Namespace,
+        name: &str,
+    ) -> Option<&mut Symbol> {
+        self.symbols(path)?.get(namespace, name)?;
+        self.scope_mut(path.clone()).get_mut(namespace, name)
     }
 
-    pub fn get(&self, path: &PathBuf, name: &str) -> Option<&Symbol> {
-        self.0.get(path)?.get(name)
+    pub fn symbols(&self, path: &PathBuf) -> Option<&Symbols> {
+        match self.local.get(path) {
+            Some(symbols) => Some(symbols),
+            None => self.shared.get(path).map(Arc::as_ref),
+        }
+    }
+
+    /// Replaces the scope at `path`, e.g. with the one a fork collected.
+    pub fn replace(&mut self, path: PathBuf, symbols: Symbols) {
+        self.local.insert(path, symbols);
+    }
+
+    /// Removes the scope at `path` from the changes of this table.
+    pub fn take_local(&mut self, path: &PathBuf) -> Option<Symbols> {
+        self.local.remove(path)
+    }
+
+    /// Removes the scope at `path`, e.g. of a file that changed.
+    pub fn remove(&mut self, path: &PathBuf) {
+        self.local.remove(path);
+        if self.shared.contains_key(path) {
+            Arc::make_mut(&mut self.shared).remove(path);
+        }
+    }
+
+    /// A table sharing every scope of this one.
+    pub fn fork(&self) -> Self {
+        let mut shared = self.shared.clone();
+        if !self.local.is_empty() {
+            let scopes = Arc::make_mut(&mut shared);
+            for (path, symbols) in &self.local {
+                scopes.insert(path.clone(), Arc::new(symbols.clone()));
+            }
+        }
+        Self {
+            shared,
+            local: FxHashMap::default(),
+        }
+    }
+
+    /// Moves the changed scopes to the shared part so that forking doesn't
+    /// copy them.
+    pub fn freeze(&mut self) {
+        if self.local.is_empty() {
+            return;
+        }
+        let scopes = Arc::make_mut(&mut self.shared);
+        for (path, symbols) in self.local.drain() {
+            scopes.insert(path, Arc::new(symbols));
+        }
+    }
+
+    fn scope_mut(&mut self, path: PathBuf) -> &mut Symbols {
+        let shared = &self.shared;
+        self.local.entry(path).or_insert_with_key(|path| {
+            shared
+                .get(path)
+                .map(|symbols| Symbols::clone(symbols))
+                .unwrap_or_default()
+        })
     }
 }
 
 #[derive(Debug, Default)]
-pub struct BuiltinTable(FxHashMap<String, Symbol>);
+pub struct BuiltinTable(Symbols);
 
 impl BuiltinTable {
     pub fn new() -> Self {
-        Self(FxHashMap::default())
+        Self(Symbols::default())
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = (&String, &Symbol)> {
+    pub fn iter(&self) -> impl Iterator<Item = (Namespace, &Symbol)> {
         self.0.iter()
     }
 
-    pub fn insert(&mut self, symbol: Symbol) {
-        self.0.insert(symbol.name.clone(), symbol);
+    pub fn insert(&mut self, namespace: Namespace, symbol: Symbol) {
+        self.0.insert(namespace, symbol);
+    }
+
+    pub fn get(&self, namespace: Namespace, name: &str) -> Option<&Symbol> {
+        self.0.get(namespace, name)
     }
 
-    pub fn get(&self, name: &str) -> Option<&Symbol> {
-        self.0.get(name)
+    pub fn symbols(&self) -> &Symbols {
+        &self.0
     }
 }
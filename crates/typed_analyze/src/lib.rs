@@ -0,0 +1,128 @@
+use std::{
+    ops::{ControlFlow, Deref},
+    path::Path,
+    sync::LazyLock,
+};
+
+use biome_analyze::{
+    AnalysisFilter, Analyzer, AnalyzerContext, AnalyzerDiagnostic, AnalyzerOptions, AnalyzerSignal,
+    AnalyzerSuppression, ApplySuppression, MetadataRegistry, Never, RegistryVisitor, RuleRegistry,
+    SuppressionAction, SuppressionDiagnostic,
+};
+use biome_diagnostics::Error;
+use biome_js_syntax::{AnyJsRoot, JsLanguage, JsSyntaxToken, TextRange};
+use biome_rowan::BatchMutation;
+use server::Server;
+
+pub mod lint;
+mod services;
+
+pub use services::{TypeServices, Typed};
+
+/// The metadata of the type-aware rules.
+pub static METADATA: LazyLock<MetadataRegistry> = LazyLock::new(|| {
+    let mut metadata = MetadataRegistry::default();
+    visit_registry(&mut metadata);
+    metadata
+});
+
+/// Records the type-aware rules in `registry`, so that a Biome analyzer
+/// pipeline can run them next to its own. The pipeline has to insert
+/// [TypeServices] in its service bag.
+pub fn visit_registry<V: RegistryVisitor<JsLanguage>>(registry: &mut V) {
+    registry.record_category::<lint::Lint>();
+}
+
+/// Runs the type-aware rules enabled by `filter` on `root`, and calls
+/// `emit_signal` with what they find, like `biome_js_analyze::analyze`.
+pub fn analyze<'a, F, B>(
+    root: &AnyJsRoot,
+    filter: AnalysisFilter,
+    options: &'a AnalyzerOptions,
+    types: TypeServices,
+    mut emit_signal: F,
+) -> (Option<B>, Vec<Error>)
+where
+    F: FnMut(&dyn AnalyzerSignal<JsLanguage>) -> ControlFlow<B> + 'a,
+    B: 'a,
+{
+    let mut registry = RuleRegistry::builder(&filter, root);
+    visit_registry(&mut registry);
+    let (registry, mut services, diagnostics, visitors, categories) = registry.build();
+    // Rule options that don't parse.
+    if !diagnostics.is_empty() {
+        return (None, diagnostics);
+    }
+
+    let mut analyzer = Analyzer::new(
+        METADATA.deref(),
+        registry,
+        parse_suppression_comment,
+        Box::new(NoSuppressionAction),
+        &mut emit_signal,
+        categories,
+    );
+    for ((phase, _), visitor) in visitors {
+        analyzer.add_visitor(phase, visitor);
+    }
+    services.insert_service(types);
+
+    let context = AnalyzerContext {
+        root: root.clone(),
+        range: filter.range,
+        services,
+        options,
+    };
+    (analyzer.run(context), diagnostics)
+}
+
+/// The diagnostics of the type-aware rules for `root`, the file at `path`
+/// analyzed by `server`.
+pub fn lint(server: &Server, path: &Path, root: &AnyJsRoot) -> Vec<AnalyzerDiagnostic> {
+    let mut diagnostics = vec![];
+    let options = AnalyzerOptions::default();
+    let types = TypeServices::new(server.scoped_analyzer(path));
+    analyze(root, AnalysisFilter::default(), &options, types, |signal| {
+        diagnostics.extend(signal.diagnostic());
+        ControlFlow::<Never>::Continue(())
+    });
+    diagnostics
+}
+
+/// Suppression comments are left to the pipeline the rules run in.
+fn parse_suppression_comment(
+    _text: &str,
+    _piece_range: TextRange,
+) -> Vec<Result<AnalyzerSuppression, SuppressionDiagnostic>> {
+    vec![]
+}
+
+struct NoSuppressionAction;
+
+impl SuppressionAction for NoSuppressionAction {
+    type Language = JsLanguage;
+
+    fn find_token_for_inline_suppression(
+        &self,
+        _original_token: JsSyntaxToken,
+    ) -> Option<ApplySuppression<Self::Language>> {
+        None
+    }
+
+    fn apply_inline_suppression(
+        &self,
+        _mutation: &mut BatchMutation<Self::Language>,
+        _apply_suppression: ApplySuppression<Self::Language>,
+        _suppression_text: &str,
+        _suppression_reason: &str,
+    ) {
+    }
+
+    fn apply_top_level_suppression(
+        &self,
+        _mutation: &mut BatchMutation<Self::Language>,
+        _token: JsSyntaxToken,
+        _suppression_text: &str,
+    ) {
+    }
+}
@@ -0,0 +1,12 @@
+use biome_analyze::declare_category;
+
+pub mod nursery;
+
+declare_category! {
+    pub Lint {
+        kind: Lint,
+        groups: [
+            self::nursery::Nursery,
+        ]
+    }
+}
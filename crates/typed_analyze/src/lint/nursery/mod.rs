@@ -0,0 +1,12 @@
+use biome_analyze::declare_lint_group;
+
+pub mod no_floating_promises;
+
+declare_lint_group! {
+    pub Nursery {
+        name: "nursery",
+        rules: [
+            self::no_floating_promises::NoFloatingPromises,
+        ]
+    }
+}
@@ -0,0 +1,136 @@
+use analyzer::FloatingPromise;
+use biome_analyze::{
+    context::RuleContext, declare_lint_rule, FixKind, Rule, RuleAction, RuleDiagnostic, RuleSource,
+};
+use biome_console::markup;
+use biome_js_factory::make;
+use biome_js_syntax::{AnyJsExpression, JsExpressionStatement, JsLanguage, T};
+use biome_rowan::{AstNode, BatchMutationExt, TriviaPieceKind};
+use serde::Deserialize;
+
+use crate::services::Typed;
+
+declare_lint_rule! {
+    /// Require Promises to be awaited or to have their rejections handled.
+    ///
+    /// A Promise whose rejection isn't handled fails silently. The types
+    /// come from the program analyzed by the `Server`, so Promises returned
+    /// by imported functions are found too.
+    ///
+    /// In async functions the fix adds `await`, elsewhere it adds `void`.
+    ///
+    /// ## Examples
+    ///
+    /// ### Invalid
+    ///
+    /// ```ts,expect_diagnostic
+    /// async function load(): Promise<string> {
+    ///     return "";
+    /// }
+    /// load();
+    /// ```
+    ///
+    /// ### Valid
+    ///
+    /// ```ts
+    /// async function load(): Promise<string> {
+    ///     return "";
+    /// }
+    /// await load();
+    /// load().catch(() => {});
+    /// void load();
+    /// ```
+    ///
+    /// ## Options
+    ///
+    /// With `ignoreVoid` set to `false`, `void` doesn't mark a Promise as
+    /// handled.
+    ///
+    /// ```json
+    /// {
+    ///     "options": {
+    ///         "ignoreVoid": false
+    ///     }
+    /// }
+    /// ```
+    pub NoFloatingPromises {
+        version: "next",
+        name: "noFloatingPromises",
+        language: "ts",
+        recommended: false,
+        sources: &[RuleSource::EslintTypeScript("no-floating-promises").same()],
+        fix_kind: FixKind::Unsafe,
+    }
+}
+
+/// What handling a Promise takes.
+const NOTE: &str = "Promises must be awaited, end with a call to `.catch`, or end with a call \
+    to `.then` with a rejection handler.";
+
+/// The options of the rule, named like typescript-eslint's.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields, default)]
+pub struct NoFloatingPromisesOptions {
+    /// Whether `void` marks a Promise as deliberately not awaited.
+    pub ignore_void: bool,
+}
+
+impl Default for NoFloatingPromisesOptions {
+    fn default() -> Self {
+        Self { ignore_void: true }
+    }
+}
+
+impl Rule for NoFloatingPromises {
+    type Query = Typed<JsExpressionStatement>;
+    type State = FloatingPromise;
+    type Signals = Option<Self::State>;
+    type Options = NoFloatingPromisesOptions;
+
+    fn run(ctx: &RuleContext<Self>) -> Self::Signals {
+        ctx.types()
+            .find_floating_promise(ctx.query())
+            .filter(|floating| !(floating.is_void && ctx.options().ignore_void))
+    }
+
+    fn diagnostic(ctx: &RuleContext<Self>, state: &Self::State) -> Option<RuleDiagnostic> {
+        let message = if state.non_function_handler {
+            "Unhandled Promise with non-function handler"
+        } else {
+            "Unhandled Promise"
+        };
+        let mut diagnostic =
+            RuleDiagnostic::new(rule_category!(), ctx.query().range(), message).note(NOTE);
+        if let Some(range) = state.handler_arguments {
+            diagnostic = diagnostic.detail(range, "not a function");
+        }
+        Some(diagnostic)
+    }
+
+    fn action(ctx: &RuleContext<Self>, state: &Self::State) -> Option<RuleAction<JsLanguage>> {
+        let (operator, message) = if state.can_await {
+            (T![await], "Add `await`")
+        } else if !state.is_void {
+            (T![void], "Add `void` to ignore the Promise")
+        } else {
+            return None;
+        };
+        let expression = state.expression.clone();
+        let operator =
+            make::token(operator).with_trailing_trivia([(TriviaPieceKind::Whitespace, " ")]);
+        let argument = expression.clone().trim_leading_trivia()?;
+        let replacement = if state.can_await {
+            AnyJsExpression::JsAwaitExpression(make::js_await_expression(operator, argument))
+        } else {
+            AnyJsExpression::JsUnaryExpression(make::js_unary_expression(operator, argument))
+        };
+        let mut mutation = ctx.root().begin();
+        mutation.replace_node(expression, replacement);
+        Some(RuleAction::new(
+            ctx.metadata().action_category(ctx.category(), ctx.group()),
+            ctx.metadata().applicability(),
+            markup! { {message} }.to_owned(),
+            mutation,
+        ))
+    }
+}
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use analyzer::TypeAnalyzer;
+use biome_analyze::{
+    AddVisitor, FromServices, Phase, Phases, QueryKey, Queryable, RuleKey, RuleMetadata,
+    ServiceBag, ServicesDiagnostic, SyntaxVisitor,
+};
+use biome_js_syntax::{AnyJsRoot, JsLanguage, JsSyntaxNode};
+use biome_rowan::AstNode;
+
+/// The types of the file being analyzed, for rules to query like they
+/// query the semantic model.
+#[derive(Clone)]
+pub struct TypeServices {
+    types: Arc<TypeAnalyzer>,
+}
+
+impl TypeServices {
+    /// `types` looks names up from the file being analyzed, e.g. the
+    /// analyzer of [Server::scoped_analyzer](server::Server::scoped_analyzer).
+    pub fn new(types: TypeAnalyzer) -> Self {
+        Self {
+            types: Arc::new(types),
+        }
+    }
+
+    pub fn types(&self) -> &TypeAnalyzer {
+        &self.types
+    }
+}
+
+impl FromServices for TypeServices {
+    fn from_services(
+        rule_key: &RuleKey,
+        _rule_metadata: &RuleMetadata,
+        services: &ServiceBag,
+    ) -> Result<Self, ServicesDiagnostic> {
+        let services: &TypeServices = services
+            .get_service()
+            .ok_or_else(|| ServicesDiagnostic::new(rule_key.rule_name(), &["TypeServices"]))?;
+        Ok(services.clone())
+    }
+}
+
+impl Phase for TypeServices {
+    fn phase() -> Phases {
+        Phases::Syntax
+    }
+}
+
+/// Queries the nodes of type `N`, like [Ast](biome_analyze::Ast), for rules
+/// that need [TypeServices].
+#[derive(Clone)]
+pub struct Typed<N>(pub N);
+
+impl<N> Queryable for Typed<N>
+where
+    N: AstNode<Language = JsLanguage> + 'static,
+{
+    type Input = JsSyntaxNode;
+    type Output = N;
+
+    type Language = JsLanguage;
+    type Services = TypeServices;
+
+    fn build_visitor(analyzer: &mut impl AddVisitor<JsLanguage>, _root: &AnyJsRoot) {
+        analyzer.add_visitor(Phases::Syntax, SyntaxVisitor::default);
+    }
+
+    fn key() -> QueryKey<Self::Language> {
+        QueryKey::Syntax(N::KIND_SET)
+    }
+
+    fn unwrap_match(_: &ServiceBag, node: &Self::Input) -> Self::Output {
+        N::unwrap_cast(node.clone())
+    }
+}
@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        path::{Path, PathBuf},
+        sync::Arc,
+    };
+
+    use biome_js_parser::parse;
+    use biome_js_syntax::JsFileSource;
+    use fake_linter::Linter;
+    use file_system::{OsFileSystem, OverlayFileSystem};
+    use server::{Server, ServerOptions};
+
+    /// The lib files and fixtures are at the root of the repository.
+    fn repo_path(path: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .ancestors()
+            .nth(2)
+            .unwrap()
+            .join(path)
+    }
+
+    /// A server that analyzed `src` as the unsaved file `test.ts`.
+    fn setup_server(src: &str) -> Server {
+        let fs = Arc::new(OverlayFileSystem::new(Arc::new(OsFileSystem)));
+        let path = PathBuf::from("test.ts");
+        fs.open(&path, src);
+        let options = ServerOptions { fs, cache: None };
+        let mut server =
+            Server::with_lib_options(&repo_path("src/lib"), &["es2018".to_string()], options)
+                .unwrap();
+        server.analyze(vec![path]).unwrap();
+        server.print_symbol_table();
+        server
+    }
+
+    #[test]
+    fn test_biome_analyze_rule() {
+        use biome_analyze::{AnalysisFilter, AnalyzerOptions, Never};
+        use biome_diagnostics::Diagnostic;
+        use std::ops::ControlFlow;
+
+        let src = "async function load(): Promise<string> {\n  return \"\";\n}\nload();\nload().catch(1);\nvoid load();\n";
+        let server = setup_server(src);
+        let root = parse(src, JsFileSource::ts(), Default::default()).tree();
+        let path = Path::new("test.ts");
+        let diagnostics = typed_analyze::lint(&server, path, &root);
+        let categories: Vec<_> = diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.category().map(|category| category.name()))
+            .collect();
+        assert_eq!(categories, [Some("lint/nursery/noFloatingPromises"); 2]);
+
+        // The same statements as the built-in rule reports.
+        let ranges: Vec<_> = diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.location().span)
+            .collect();
+        let expected: Vec<_> = Linter::default()
+            .lint(&server, path, &root)
+            .iter()
+            .map(|diagnostic| Some(diagnostic.span.range))
+            .collect();
+        assert_eq!(ranges, expected);
+
+        // Outside of async functions the fix adds `void`.
+        let mut fixed = vec![];
+        let types = typed_analyze::TypeServices::new(server.scoped_analyzer(path));
+        let options = AnalyzerOptions::default();
+        typed_analyze::analyze(
+            &root,
+            AnalysisFilter::default(),
+            &options,
+            types,
+            |signal| {
+                for action in signal.actions() {
+                    let text = action.mutation.commit().to_string();
+                    if text != src {
+                        fixed.push(text);
+                    }
+                }
+                ControlFlow::<Never>::Continue(())
+            },
+        );
+        assert_eq!(
+            fixed,
+            [
+                src.replace("\nload();", "\nvoid load();"),
+                src.replace("load().catch(1);", "void load().catch(1);"),
+            ]
+        );
+    }
+}
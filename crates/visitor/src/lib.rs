@@ -17,6 +17,8 @@ pub trait Visitor {
 
     fn visit_js_function_declaration(&mut self, node: &JsFunctionDeclaration);
 
+    fn visit_js_call_expression(&mut self, node: &JsCallExpression);
+
     fn visit_ts_declare_statement(&mut self, node: &TsDeclareStatement);
 
     fn visit_js_variable_declaration_clause(&mut self, node: &JsVariableDeclarationClause);
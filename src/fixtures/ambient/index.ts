@@ -0,0 +1,9 @@
+/// <reference path="./types/shims.d.ts" />
+/// <reference types="fetcher" />
+import logo from "./logo.svg";
+import { loadConfig } from "config";
+
+export const logoUrl: string = logo;
+
+loadConfig();
+fetchData();
@@ -0,0 +1,8 @@
+declare module "*.svg" {
+  const content: string;
+  export default content;
+}
+
+declare module "config" {
+  export function loadConfig(): Promise<string>;
+}
@@ -0,0 +1,36 @@
+import { Item } from "./types";
+export declare const VERSION = "1.0.0";
+export declare let count: number;
+export declare const defaults: {
+    retries: number;
+    verbose: boolean;
+};
+export declare function total(items: Item[]): number;
+export declare function ping(): Promise<string>;
+export declare function reset(): void;
+export declare const double: (value: number) => number;
+declare function label(item: Item): string;
+export declare class Cart {
+    owner: string;
+    private items;
+    readonly currency = "EUR";
+    discount: number;
+    constructor(owner: string);
+    add(item: Item): void;
+    get size(): number;
+}
+export declare enum Status {
+    Active = 0,
+    Archived = 5,
+    Deleted = 6
+}
+export declare namespace Format {
+    const separator = ", ";
+    function join(items: string[]): string;
+}
+export interface Options {
+    retries?: number;
+    onDone(total: number): void;
+}
+export type Id = string | number;
+export { label };
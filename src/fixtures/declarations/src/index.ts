@@ -0,0 +1,66 @@
+import { Item } from "./types";
+
+export const VERSION = "1.0.0";
+export let count = 0;
+export const defaults = { retries: 3, verbose: false };
+
+export function total(items: Item[]): number {
+    let sum = 0;
+    for (const item of items) {
+        sum += item.price;
+    }
+    return sum;
+}
+
+export async function ping() {
+    return "pong";
+}
+
+export function reset() {
+    count = 0;
+}
+
+export const double = (value: number): number => value * 2;
+
+function label(item: Item): string {
+    return item.id;
+}
+
+export class Cart {
+    private items: Item[] = [];
+    readonly currency = "EUR";
+    discount = 0;
+
+    constructor(public owner: string) {}
+
+    add(item: Item): void {
+        this.items.push(item);
+    }
+
+    get size(): number {
+        return this.items.length;
+    }
+}
+
+export enum Status {
+    Active,
+    Archived = 5,
+    Deleted,
+}
+
+export namespace Format {
+    export const separator = ", ";
+    export function join(items: string[]): string {
+        return items.join(separator);
+    }
+    function hidden() {}
+}
+
+export interface Options {
+    retries?: number;
+    onDone(total: number): void;
+}
+
+export type Id = string | number;
+
+export { label };
@@ -0,0 +1,5 @@
+import axios from "axios";
+import { load } from "./index.js";
+
+axios.get("/users");
+load();
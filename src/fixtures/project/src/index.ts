@@ -0,0 +1 @@
+export declare function load(): Promise<string>;
@@ -0,0 +1,13 @@
+// Excerpts of the window and timers of TypeScript's lib.dom.d.ts.
+/// <reference no-default-lib="true"/>
+
+interface Window {
+  name: string;
+}
+
+declare var window: Window;
+
+declare function clearTimeout(id: number | undefined): void;
+declare function setTimeout(handler: TimerHandler, timeout?: number): number;
+
+type TimerHandler = string | Function;
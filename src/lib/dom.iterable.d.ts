@@ -0,0 +1,2 @@
+// TypeScript's lib.dom.iterable.d.ts, without its declarations: it is here for tsc's default libs.
+/// <reference no-default-lib="true"/>
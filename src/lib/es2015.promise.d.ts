@@ -0,0 +1,64 @@
+// https://github.com/microsoft/TypeScript/blob/caf1aee269d1660b4d2a8b555c2d602c97cb28d7/src/lib/es2015.promise.d.ts
+/// <reference no-default-lib="true"/>
+
+interface PromiseConstructor {
+  /**
+   * A reference to the prototype.
+   */
+  readonly prototype: Promise<any>;
+
+  /**
+   * Creates a new Promise.
+   * @param executor A callback used to initialize the promise. This callback is passed two arguments:
+   * a resolve callback used to resolve the promise with a value or the result of another promise,
+   * and a reject callback used to reject the promise with a provided reason or error.
+   */
+  new <T>(
+    executor: (
+      resolve: (value: T | PromiseLike<T>) => void,
+      reject: (reason?: any) => void
+    ) => void
+  ): Promise<T>;
+
+  /**
+   * Creates a Promise that is resolved with an array of results when all of the provided Promises
+   * resolve, or rejected when any Promise is rejected.
+   * @param values An array of Promises.
+   * @returns A new Promise.
+   */
+  all<T extends readonly unknown[] | []>(
+    values: T
+  ): Promise<{ -readonly [P in keyof T]: Awaited<T[P]> }>;
+
+  /**
+   * Creates a Promise that is resolved or rejected when any of the provided Promises are resolved
+   * or rejected.
+   * @param values An array of Promises.
+   * @returns A new Promise.
+   */
+  race<T extends readonly unknown[] | []>(
+    values: T
+  ): Promise<Awaited<T[number]>>;
+
+  /**
+   * Creates a new rejected promise for the provided reason.
+   * @param reason The reason the promise was rejected.
+   * @returns A new rejected Promise.
+   */
+  reject<T = never>(reason?: any): Promise<T>;
+
+  /**
+   * Creates a new resolved promise.
+   * @returns A resolved promise.
+   */
+  resolve(): Promise<void>;
+
+  /**
+   * Creates a new resolved promise for the provided value.
+   * @param value A promise.
+   * @returns A promise whose internal state matches the provided promise.
+   */
+  resolve<T>(value: T): Promise<Awaited<T>>;
+}
+
+declare var Promise: PromiseConstructor;
@@ -0,0 +1,6 @@
+// https://github.com/microsoft/TypeScript/blob/caf1aee269d1660b4d2a8b555c2d602c97cb28d7/src/lib/es2018.d.ts
+// Only the libs available in this directory are referenced.
+/// <reference no-default-lib="true"/>
+
+/// <reference lib="es2015" />
+/// <reference lib="es2018.promise" />
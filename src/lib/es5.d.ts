@@ -51,3 +51,74 @@ interface Promise<T> {
       | null
   ): Promise<T | TResult>;
 }
+
+// https://github.com/microsoft/TypeScript/blob/caf1aee269d1660b4d2a8b555c2d602c97cb28d7/src/lib/es5.d.ts
+// Excerpts of the global constructors and namespaces.
+interface ObjectConstructor {
+  /**
+   * Returns the names of the enumerable string properties and methods of an object.
+   * @param o Object that contains the properties and methods.
+   */
+  keys(o: object): string[];
+}
+
+declare var Object: ObjectConstructor;
+
+interface JSON {
+  /**
+   * Converts a JavaScript Object Notation (JSON) string into an object.
+   * @param text A valid JSON string.
+   */
+  parse(text: string): any;
+
+  /**
+   * Converts a JavaScript value to a JavaScript Object Notation (JSON) string.
+   * @param value A JavaScript value, usually an object or array, to be converted.
+   */
+  stringify(value: any): string;
+}
+
+declare var JSON: JSON;
+
+interface ArrayConstructor {
+  isArray(arg: any): arg is any[];
+}
+
+declare var Array: ArrayConstructor;
+
+// https://github.com/microsoft/TypeScript/blob/caf1aee269d1660b4d2a8b555c2d602c97cb28d7/src/lib/es5.d.ts
+// Excerpts of the utility types.
+/**
+ * Make all properties in T optional
+ */
+type Partial<T> = {
+  [P in keyof T]?: T[P];
+};
+
+/**
+ * Make all properties in T required
+ */
+type Required<T> = {
+  [P in keyof T]-?: T[P];
+};
+
+/**
+ * Make all properties in T readonly
+ */
+type Readonly<T> = {
+  readonly [P in keyof T]: T[P];
+};
+
+/**
+ * From T, pick a set of properties whose keys are in the union K
+ */
+type Pick<T, K extends keyof T> = {
+  [P in K]: T[P];
+};
+
+/**
+ * Construct a type with a set of properties K of type T
+ */
+type Record<K extends keyof any, T> = {
+  [P in K]: T;
+};
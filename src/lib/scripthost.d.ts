@@ -0,0 +1,2 @@
+// TypeScript's lib.scripthost.d.ts, without its declarations: it is here for tsc's default libs.
+/// <reference no-default-lib="true"/>
@@ -0,0 +1,4 @@
+// TypeScript's lib.webworker.importscripts.d.ts.
+/// <reference no-default-lib="true"/>
+
+declare function importScripts(...urls: string[]): void;
@@ -1,205 +1,228 @@
-use std::{fs, path::Path};
+use std::{
+    fmt,
+    io::IsTerminal,
+    path::{Path, PathBuf},
+};
 
 use biome_js_parser::parse;
-use biome_js_syntax::JsFileSource;
-use fake_linter::NoFloatingPromisesLinter;
-use server::Server;
+use biome_js_syntax::{JsFileSource, TextRange};
+use diagnostic::{Diagnostic, LineIndex, RenderOptions, Report, ReportFormat, Severity, Span};
+use fake_linter::{LintConfig, Linter};
+use file_system::OsFileSystem;
+use server::{DeclarationCache, Server, ServerOptions};
 
-fn main() {
-    let current_dir = std::env::current_dir().unwrap();
-    let builtin = vec![current_dir.join("src/lib/es5.d.ts")];
-    let mut server = Server::new(builtin);
+/// Used when no `--lib` option is given, like tsc with `"target": "es2018"`.
+const DEFAULT_LIB: &str = "es2018";
 
-    let tests_dir = current_dir.join("src/tests");
-    let paths = get_ts_files(&tests_dir);
-    server.analyze(paths.clone());
-    server.print_symbol_table();
+/// The libs shipped with this repository, used when no `typescript` package
+/// is installed.
+const BUNDLED_LIB_DIR: &str = "src/lib";
 
-    let mut linter = NoFloatingPromisesLinter::new(server);
+/// The exit code when linting can't run, unlike 1 for lint errors.
+const EXIT_FAILURE: i32 = 2;
+
+fn main() {
+    let current_dir = std::env::current_dir()
+        .unwrap_or_else(|err| fail(format!("Cannot read the working directory: {}", err)));
+    let lib_dir = server::find_lib_dir(&OsFileSystem, &current_dir)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join(BUNDLED_LIB_DIR));
+    if std::env::args().any(|arg| arg == "--lsp") {
+        let libs = lib_option(std::env::args()).unwrap_or_else(|| vec![DEFAULT_LIB.to_string()]);
+        lsp::run_stdio(lib_dir, libs).unwrap_or_else(|err| fail(err));
+        return;
+    }
+    let options = ServerOptions {
+        cache: option_value(std::env::args(), "--cache-dir").map(DeclarationCache::new),
+        ..Default::default()
+    };
+    let fs = options.fs.clone();
+    let config = option_value(std::env::args(), "--config")
+        .map(PathBuf::from)
+        .or_else(|| LintConfig::find(fs.as_ref(), &current_dir))
+        .map(|path| LintConfig::load(fs.as_ref(), &path).unwrap_or_else(|err| fail(err)));
+    let (mut server, paths) = match project_option(std::env::args()) {
+        Some(tsconfig) => {
+            let server = Server::from_tsconfig_options(&lib_dir, &tsconfig, options)
+                .unwrap_or_else(|err| fail(err));
+            let paths = server.files().to_vec();
+            (server, paths)
+        }
+        None => {
+            let libs =
+                lib_option(std::env::args()).unwrap_or_else(|| vec![DEFAULT_LIB.to_string()]);
+            let server =
+                Server::with_lib_options(&lib_dir, &libs, options).unwrap_or_else(|err| fail(err));
+            let paths = config
+                .as_ref()
+                .and_then(|config| config.files(fs.as_ref()))
+                .unwrap_or_else(|| {
+                    fail("No files to lint: set `include` in the lint config, or pass --project")
+                });
+            (server, paths)
+        }
+    };
+    server
+        .analyze(paths.clone())
+        .unwrap_or_else(|err| fail(err));
+    if std::env::args().any(|arg| arg == "--declaration") {
+        for path in &paths {
+            if let Some(declarations) = server.emit_declarations(path) {
+                println!("// {}", path.display());
+                print!("{}", declarations);
+            }
+        }
+        return;
+    }
+    let format = option_value(std::env::args(), "--format")
+        .map(|value| value.parse().unwrap_or_else(|err| fail(err)))
+        .unwrap_or_default();
+    // Machine-readable formats have stdout to themselves.
+    if format == ReportFormat::Pretty {
+        server.print_symbol_table();
+    }
+
+    let mut report = Report::new(env!("CARGO_PKG_NAME")).with_root(current_dir);
+    let report_unused_suppressions = std::env::args()
+        .any(|arg| arg == "--report-unused-suppressions")
+        || config
+            .as_ref()
+            .is_some_and(|config| config.report_unused_suppressions);
 
     for path in paths {
-        let src = fs::read_to_string(&path).unwrap();
-        let src_type = JsFileSource::ts();
+        let linter = match &config {
+            Some(config) if config.is_ignored(&path) => continue,
+            Some(config) => Linter::new(config.registry(&path)),
+            None => Linter::default(),
+        }
+        .with_unused_suppressions(report_unused_suppressions);
+        let src = fs
+            .read_to_string(&path)
+            .unwrap_or_else(|err| fail(format!("Cannot read {}: {}", path.display(), err)));
+        let src_type = JsFileSource::try_from(path.as_path()).unwrap_or(JsFileSource::ts());
         let root = parse(&src, src_type, Default::default()).tree();
 
-        linter.set_current_path(path.clone());
-        linter.visit(&root);
+        let mut diagnostics = parse_error_diagnostics(&server, &path, &src);
+        diagnostics.extend(linter.lint(&server, &path, &root));
+        report.add_file(path, src, diagnostics);
     }
 
-    for diagnostic in linter.diagnostics() {
-        println!("{}", diagnostic);
+    let render_options = RenderOptions {
+        colors: std::io::stdout().is_terminal(),
+    };
+    print!("{}", report.format(format, &render_options));
+    let summary = report.summary();
+    if format != ReportFormat::Pretty {
+        eprintln!("{}", summary);
+    }
+    if summary.has_errors() {
+        std::process::exit(1);
     }
-    println!();
 }
 
-fn get_ts_files(dir: &Path) -> Vec<std::path::PathBuf> {
-    fs::read_dir(dir)
-        .unwrap()
-        .filter_map(|entry| {
-            let entry = entry.unwrap();
-            let path = entry.path();
-            if path.extension().and_then(|s| s.to_str()) == Some("ts") {
-                Some(path)
-            } else {
-                None
-            }
+/// The syntax errors in the file at `path`, which fail the run like lint
+/// errors do: the rules only saw what the parser could recover.
+fn parse_error_diagnostics(server: &Server, path: &Path, src: &str) -> Vec<Diagnostic> {
+    let index = LineIndex::new(src);
+    server
+        .parse_errors(path)
+        .iter()
+        .map(|error| {
+            let range = error.range.unwrap_or_else(|| TextRange::empty(0.into()));
+            Diagnostic::new(
+                "parse",
+                Severity::Error,
+                path.to_path_buf(),
+                Span::new(&index, range),
+                error.message.clone(),
+            )
         })
         .collect()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use biome_js_parser::parse;
-    use biome_js_syntax::JsFileSource;
-    use server::Server;
-    use std::path::PathBuf;
-
-    fn setup_server(src: &str) -> Server {
-        let builtin = vec![PathBuf::from("src/lib/es5.d.ts")];
-        let mut server = Server::new(builtin);
-        server.test_analyze(src);
-        server.print_symbol_table();
-        server
-    }
-
-    #[test]
-    fn test_no_floating_promises_linter() {
-        let src = r#"
-        async function test(): Promise<void> {
-            Promise.resolve("value");
-            Promise.resolve("value").then(() => {});
-            Promise.resolve("value").catch();
-            Promise.resolve("value").finally();
-        }
-        "#;
-
-        let server = setup_server(src);
-        let mut linter = NoFloatingPromisesLinter::new(server);
-
-        let src_type = JsFileSource::ts();
-        let root = parse(src, src_type, Default::default()).tree();
-
-        linter.set_current_path(PathBuf::from("test.ts"));
-        linter.visit(&root);
-
-        let diagnostics = linter.diagnostics();
-        for d in diagnostics {
-            println!("{}", d);
-        }
-    }
+/// Parses `--lib es2015,dom` (or `--lib=es2015,dom`) from the command line.
+fn lib_option(args: impl Iterator<Item = String>) -> Option<Vec<String>> {
+    let value = option_value(args, "--lib")?;
+    Some(value.split(',').map(|lib| lib.trim().to_string()).collect())
+}
 
-    #[test]
-    fn test_1() {
-        let src = r#"
-        async function returnPromise(): Promise<string> {
-            return "value";
-        }
-        // invalid
-        returnPromise();
-        returnPromise().then(() => {});
-        returnPromise().catch();
-        returnPromise().finally();
-
-        //valid
-        await returnPromise();
-        (async () => {
-            await returnPromise();
-            await returnPromise().then(() => {});
-            await returnPromise().catch();
-            await returnPromise().finally();
-        })();
-        "#;
-
-        let server = setup_server(src);
-        let mut linter = NoFloatingPromisesLinter::new(server);
-
-        let src_type = JsFileSource::ts();
-        let root = parse(src, src_type, Default::default()).tree();
-
-        linter.set_current_path(PathBuf::from("test.ts"));
-        linter.visit(&root);
-
-        let diagnostics = linter.diagnostics();
-        for d in diagnostics {
-            println!("{}", d);
-        }
+/// Parses `--project path/to/tsconfig.json`, like tsc's `-p`.
+fn project_option(args: impl Iterator<Item = String>) -> Option<PathBuf> {
+    let value = option_value(args, "--project")?;
+    let path = PathBuf::from(value);
+    if path.is_dir() {
+        Some(path.join("tsconfig.json"))
+    } else {
+        Some(path)
     }
+}
 
-    #[test]
-    fn test_2() {
-        let src = r#"
-        declare const promiseValue: Promise<number>;
-        async function test4(): Promise<void> {
-            promiseValue;
-            promiseValue.then(() => {});
-            promiseValue.catch();
-            await promiseValue.finally();
-        }
-        test4();
-        "#;
-
-        let server = setup_server(src);
-        let mut linter = NoFloatingPromisesLinter::new(server);
-
-        let src_type = JsFileSource::ts();
-        let root = parse(src, src_type, Default::default()).tree();
-
-        linter.set_current_path(PathBuf::from("test.ts"));
-        linter.visit(&root);
-
-        let diagnostics = linter.diagnostics();
-        for d in diagnostics {
-            println!("{}", d);
-        }
+fn option_value(args: impl Iterator<Item = String>, name: &str) -> Option<String> {
+    let mut args = args.skip(1);
+    while let Some(arg) = args.next() {
+        return match arg.strip_prefix(name) {
+            Some("") => args.next(),
+            Some(value) => match value.strip_prefix('=') {
+                Some(value) => Some(value.to_string()),
+                None => continue,
+            },
+            None => continue,
+        };
     }
-
-    #[test]
-    fn test_3() {
-        let src = r#"
-        declare const promiseOrNumber: Promise<number> | number;
-
-async function test() {
-  promiseOrNumber;
+    None
 }
-        "#;
-
-        let server = setup_server(src);
-        let mut linter = NoFloatingPromisesLinter::new(server);
 
-        let src_type = JsFileSource::ts();
-        let root = parse(src, src_type, Default::default()).tree();
+/// Prints `error` and exits with [EXIT_FAILURE].
+fn fail(error: impl fmt::Display) -> ! {
+    eprintln!("error: {}", error);
+    std::process::exit(EXIT_FAILURE)
+}
 
-        linter.set_current_path(PathBuf::from("test.ts"));
-        linter.visit(&root);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use file_system::OverlayFileSystem;
+    use std::sync::Arc;
 
-        let diagnostics = linter.diagnostics();
-        for d in diagnostics {
-            println!("{}", d);
-        }
+    #[test]
+    fn test_lib_option() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            lib_option(args(&["bin", "--lib", "es2015,dom"]).into_iter()),
+            Some(vec!["es2015".to_string(), "dom".to_string()])
+        );
+        assert_eq!(
+            lib_option(args(&["bin", "--lib=es5"]).into_iter()),
+            Some(vec!["es5".to_string()])
+        );
+        assert_eq!(lib_option(args(&["bin"]).into_iter()), None);
     }
 
     #[test]
-    fn test_4() {
-        let src = r#"
-        const foo = async (): Promise<void> => {
-            Promise.resolve("value");
-        }
-        foo();
-        "#;
-
-        let server = setup_server(src);
-        let mut linter = NoFloatingPromisesLinter::new(server);
-
-        let src_type = JsFileSource::ts();
-        let root = parse(src, src_type, Default::default()).tree();
-
-        linter.set_current_path(PathBuf::from("test.ts"));
-        linter.visit(&root);
-
-        let diagnostics = linter.diagnostics();
-        for d in diagnostics {
-            println!("{}", d);
-        }
+    fn test_parse_error_diagnostics() {
+        let fs = Arc::new(OverlayFileSystem::new(Arc::new(OsFileSystem)));
+        let index = PathBuf::from("/memory/parse-errors/index.ts");
+        let src = "const a = 1;\nconst = 2;";
+        fs.open(&index, src);
+        let options = ServerOptions {
+            fs: fs.clone(),
+            cache: None,
+        };
+        let mut server =
+            Server::with_lib_options(Path::new("src/lib"), &[DEFAULT_LIB.to_string()], options)
+                .unwrap();
+        server.analyze(vec![index.clone()]).unwrap();
+
+        let diagnostics = parse_error_diagnostics(&server, &index, src);
+        assert!(!diagnostics.is_empty());
+        let diagnostic = &diagnostics[0];
+        assert_eq!(
+            (diagnostic.rule.as_str(), diagnostic.severity),
+            ("parse", Severity::Error)
+        );
+        assert_eq!(diagnostic.span.start.line, 2);
+
+        let mut report = Report::new("test");
+        report.add_file(index, src.to_string(), diagnostics);
+        assert!(report.summary().has_errors());
     }
 }